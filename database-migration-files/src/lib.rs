@@ -1,12 +1,19 @@
 #![doc(html_root_url = "https://docs.rs/database-migration-files/0.2.0")]
 
+pub mod schema;
+pub mod template;
+
+use database_migration::callback::{Callback, CallbackScript};
 use database_migration::checksum::hash_migration_script;
 use database_migration::definition::{ExcludedFiles, GetFilename, ParseMigration};
 use database_migration::error::Error;
 use database_migration::migration::{Migration, NewMigration, ScriptContent};
-use database_migration::repository::{CreateNewMigration, ListMigrations, ReadScriptContent};
+use database_migration::repository::{
+    CreateNewMigration, ListMigrations, ReadCallbackScript, ReadScriptContent,
+};
 use std::fs;
 use std::fs::File;
+use std::io::Write;
 #[cfg(target_family = "windows")]
 use std::os::windows::fs::FileTypeExt;
 use std::path::Path;
@@ -72,6 +79,22 @@ impl ReadScriptContent for MigrationDirectory<'_> {
     }
 }
 
+impl ReadCallbackScript for MigrationDirectory<'_> {
+    fn read_callback_script(&self, callback: Callback) -> Result<Option<CallbackScript>, Error> {
+        let script_path = self.path.join(callback.filename());
+        if !script_path.is_file() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(&script_path)
+            .map_err(|err| Error::ReadingMigrationFile(err.to_string()))?;
+        Ok(Some(CallbackScript {
+            callback,
+            script_path,
+            content,
+        }))
+    }
+}
+
 #[derive(Debug)]
 pub struct MigDirIter {
     walker: walkdir::IntoIter,
@@ -98,6 +121,10 @@ impl Iterator for MigDirIter {
                     if self.excluded_files.matches(file_path) {
                         continue;
                     }
+                    // callback scripts are located directly in the migrations folder
+                    if entry.depth() == 1 && Callback::from_path(file_path).is_some() {
+                        continue;
+                    }
                     Some(file_path.parse_migration().map_err(Error::from))
                 },
                 Err(err) => Some(Err(Error::ScanningMigrationDirectory(err.to_string()))),
@@ -130,10 +157,18 @@ impl<S> CreateNewMigration for MigrationFiles<'_, S>
 where
     S: GetFilename,
 {
-    fn create_new_migration(&self, new_migration: NewMigration) -> Result<Migration, Error> {
+    fn create_new_migration_with_content(
+        &self,
+        new_migration: NewMigration,
+        content: &str,
+    ) -> Result<Migration, Error> {
         let filename = self.filename_strategy.get_filename(&new_migration);
         let script_path = self.path.join(&filename);
-        File::create_new(&script_path).map_err(|err| Error::CreatingScriptFile(err.to_string()))?;
+        let mut script_file = File::create_new(&script_path)
+            .map_err(|err| Error::CreatingScriptFile(err.to_string()))?;
+        script_file
+            .write_all(content.as_bytes())
+            .map_err(|err| Error::CreatingScriptFile(err.to_string()))?;
         Ok(Migration {
             key: new_migration.key,
             title: new_migration.title,
@@ -0,0 +1,74 @@
+use database_migration::definition::SCRIPT_FILE_EXTENSION;
+use database_migration::error::Error;
+use std::ffi::OsStr;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// A file with declarative definitions describing the desired state of the
+/// database schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaDefinition {
+    pub path: PathBuf,
+    pub content: String,
+}
+
+/// The folder holding the declarative schema definition files.
+///
+/// Each file with the script extension (`.surql`) in the folder or one of its
+/// subfolders is a schema definition file. Typically, there is one file per
+/// table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SchemaFolder<'a> {
+    path: &'a Path,
+}
+
+impl<'a> SchemaFolder<'a> {
+    pub const fn new(path: &'a Path) -> Self {
+        Self { path }
+    }
+
+    /// Reads all schema definition files sorted by their path.
+    ///
+    /// Hidden files and folders (starting with a '.') are ignored.
+    pub fn read_definitions(&self) -> Result<Vec<SchemaDefinition>, Error> {
+        if !self.path.exists() {
+            return Err(Error::ReadingSchemaDefinition(format!(
+                r#"schema folder "{}" does not exist"#,
+                self.path.display()
+            )));
+        }
+        let mut definitions = Vec::new();
+        let walker = WalkDir::new(self.path)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|entry| entry.depth() == 0 || !is_hidden(entry.file_name()));
+        for dir_entry in walker {
+            let entry = dir_entry.map_err(|err| Error::ReadingSchemaDefinition(err.to_string()))?;
+            let path = entry.path();
+            if !entry.file_type().is_file()
+                || !path
+                    .file_name()
+                    .and_then(OsStr::to_str)
+                    .is_some_and(|name| name.ends_with(SCRIPT_FILE_EXTENSION))
+            {
+                continue;
+            }
+            let content = fs::read_to_string(path).map_err(|err| {
+                Error::ReadingSchemaDefinition(format!("{}: {err}", path.display()))
+            })?;
+            definitions.push(SchemaDefinition {
+                path: path.to_path_buf(),
+                content,
+            });
+        }
+        Ok(definitions)
+    }
+}
+
+fn is_hidden(filename: &OsStr) -> bool {
+    filename.to_str().is_some_and(|name| name.starts_with('.'))
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,86 @@
+use super::*;
+use assert_fs::TempDir;
+use asserting::prelude::*;
+
+fn write_file(path: &Path, content: &str) {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).expect("failed to create folder");
+    }
+    fs::write(path, content).expect("failed to write file");
+}
+
+#[test]
+fn read_definitions_from_not_existing_folder() {
+    let schema_folder = SchemaFolder::new(Path::new("not_existing/schema"));
+
+    let definitions = schema_folder.read_definitions();
+
+    assert_that!(definitions).is_equal_to(Err(Error::ReadingSchemaDefinition(
+        r#"schema folder "not_existing/schema" does not exist"#.into(),
+    )));
+}
+
+#[test]
+fn read_definitions_sorted_by_path() {
+    let temp_dir = TempDir::new().expect("failed to create temp dir");
+    write_file(
+        &temp_dir.path().join("quote.surql"),
+        "DEFINE TABLE quote SCHEMAFULL;",
+    );
+    write_file(
+        &temp_dir.path().join("author.surql"),
+        "DEFINE TABLE author SCHEMAFULL;",
+    );
+    write_file(
+        &temp_dir.path().join("access/user.surql"),
+        "DEFINE TABLE user SCHEMAFULL;",
+    );
+    let schema_folder = SchemaFolder::new(temp_dir.path());
+
+    let definitions = schema_folder
+        .read_definitions()
+        .expect("failed to read schema definitions");
+
+    assert_that!(definitions).is_equal_to(vec![
+        SchemaDefinition {
+            path: temp_dir.path().join("access/user.surql"),
+            content: "DEFINE TABLE user SCHEMAFULL;".into(),
+        },
+        SchemaDefinition {
+            path: temp_dir.path().join("author.surql"),
+            content: "DEFINE TABLE author SCHEMAFULL;".into(),
+        },
+        SchemaDefinition {
+            path: temp_dir.path().join("quote.surql"),
+            content: "DEFINE TABLE quote SCHEMAFULL;".into(),
+        },
+    ]);
+}
+
+#[test]
+fn read_definitions_ignores_hidden_and_other_files() {
+    let temp_dir = TempDir::new().expect("failed to create temp dir");
+    write_file(
+        &temp_dir.path().join("quote.surql"),
+        "DEFINE TABLE quote SCHEMAFULL;",
+    );
+    write_file(&temp_dir.path().join("README.md"), "# Schema");
+    write_file(
+        &temp_dir.path().join(".draft.surql"),
+        "DEFINE TABLE draft SCHEMAFULL;",
+    );
+    write_file(
+        &temp_dir.path().join(".old/author.surql"),
+        "DEFINE TABLE author SCHEMAFULL;",
+    );
+    let schema_folder = SchemaFolder::new(temp_dir.path());
+
+    let definitions = schema_folder
+        .read_definitions()
+        .expect("failed to read schema definitions");
+
+    assert_that!(definitions).is_equal_to(vec![SchemaDefinition {
+        path: temp_dir.path().join("quote.surql"),
+        content: "DEFINE TABLE quote SCHEMAFULL;".into(),
+    }]);
+}
@@ -0,0 +1,173 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use database_migration::config::MIGRATION_KEY_FORMAT_STR;
+use database_migration::definition::{
+    DOWN_SCRIPT_FILE_EXTENSION, SCRIPT_FILE_EXTENSION, UP_SCRIPT_FILE_EXTENSION,
+};
+use database_migration::error::Error;
+use database_migration::migration::{MigrationKind, NewMigration};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const DEFAULT_TEMPLATE: &str = "empty";
+
+pub const BUILTIN_TEMPLATES: [&str; 5] = ["empty", "table", "field", "index", "backfill"];
+
+const TABLE_UP_TEMPLATE: &str = include_str!("../../templates/table.up.surql");
+const TABLE_DOWN_TEMPLATE: &str = include_str!("../../templates/table.down.surql");
+const FIELD_UP_TEMPLATE: &str = include_str!("../../templates/field.up.surql");
+const FIELD_DOWN_TEMPLATE: &str = include_str!("../../templates/field.down.surql");
+const INDEX_UP_TEMPLATE: &str = include_str!("../../templates/index.up.surql");
+const INDEX_DOWN_TEMPLATE: &str = include_str!("../../templates/index.down.surql");
+const BACKFILL_UP_TEMPLATE: &str = include_str!("../../templates/backfill.up.surql");
+const BACKFILL_DOWN_TEMPLATE: &str = include_str!("../../templates/backfill.down.surql");
+
+const KEY_VARIABLE: &str = "{{key}}";
+const TITLE_VARIABLE: &str = "{{title}}";
+const AUTHOR_VARIABLE: &str = "{{author}}";
+const DATE_VARIABLE: &str = "{{date}}";
+
+/// Values that replace the variables in a migration template.
+///
+/// Supported variables are `{{key}}`, `{{title}}`, `{{author}}` and
+/// `{{date}}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateVariables {
+    pub key: NaiveDateTime,
+    pub title: String,
+    pub author: String,
+    pub date: NaiveDate,
+}
+
+impl TemplateVariables {
+    pub fn new(new_migration: &NewMigration, author: impl Into<String>, date: NaiveDate) -> Self {
+        Self {
+            key: new_migration.key,
+            title: new_migration.title.clone(),
+            author: author.into(),
+            date,
+        }
+    }
+}
+
+/// A template for the content of new migration files.
+///
+/// A template consists of a content for the forward (up) migration and a
+/// content for the backward (down) migration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationTemplate {
+    name: String,
+    up_content: String,
+    down_content: String,
+}
+
+impl MigrationTemplate {
+    pub fn new(
+        name: impl Into<String>,
+        up_content: impl Into<String>,
+        down_content: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            up_content: up_content.into(),
+            down_content: down_content.into(),
+        }
+    }
+
+    /// Returns the built-in template with the given name or `None` if there is
+    /// no built-in template with this name.
+    pub fn builtin(name: &str) -> Option<Self> {
+        let (up_content, down_content) = match name {
+            "empty" => ("", ""),
+            "table" => (TABLE_UP_TEMPLATE, TABLE_DOWN_TEMPLATE),
+            "field" => (FIELD_UP_TEMPLATE, FIELD_DOWN_TEMPLATE),
+            "index" => (INDEX_UP_TEMPLATE, INDEX_DOWN_TEMPLATE),
+            "backfill" => (BACKFILL_UP_TEMPLATE, BACKFILL_DOWN_TEMPLATE),
+            _ => return None,
+        };
+        Some(Self::new(name, up_content, down_content))
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn content(&self, kind: MigrationKind) -> &str {
+        match kind {
+            MigrationKind::Baseline | MigrationKind::Up => &self.up_content,
+            MigrationKind::Down => &self.down_content,
+        }
+    }
+
+    /// Renders the content for the given kind of migration replacing all
+    /// variables with the given values.
+    pub fn render(&self, kind: MigrationKind, variables: &TemplateVariables) -> String {
+        self.content(kind)
+            .replace(
+                KEY_VARIABLE,
+                &variables.key.format(MIGRATION_KEY_FORMAT_STR).to_string(),
+            )
+            .replace(TITLE_VARIABLE, &variables.title)
+            .replace(AUTHOR_VARIABLE, &variables.author)
+            .replace(
+                DATE_VARIABLE,
+                &variables.date.format("%Y-%m-%d").to_string(),
+            )
+    }
+}
+
+/// The folder containing custom migration templates.
+///
+/// A custom template named `my_template` is defined by the file
+/// `my_template.up.surql` or `my_template.surql` for forward migrations and
+/// the optional file `my_template.down.surql` for backward migrations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TemplateFolder<'a> {
+    path: &'a Path,
+}
+
+impl<'a> TemplateFolder<'a> {
+    pub const fn new(path: &'a Path) -> Self {
+        Self { path }
+    }
+
+    /// Finds the template with the given name.
+    ///
+    /// Custom templates in the template folder take precedence over the
+    /// built-in templates with the same name.
+    pub fn find_template(&self, name: &str) -> Result<MigrationTemplate, Error> {
+        if let Some(template) = self.read_custom_template(name)? {
+            return Ok(template);
+        }
+        MigrationTemplate::builtin(name).ok_or_else(|| Error::TemplateNotFound(name.to_string()))
+    }
+
+    fn read_custom_template(&self, name: &str) -> Result<Option<MigrationTemplate>, Error> {
+        let up_script = [UP_SCRIPT_FILE_EXTENSION, SCRIPT_FILE_EXTENSION]
+            .into_iter()
+            .map(|extension| self.template_path(name, extension))
+            .find(|path| path.is_file());
+        let Some(up_script) = up_script else {
+            return Ok(None);
+        };
+        let up_content = read_template_file(&up_script)?;
+        let down_script = self.template_path(name, DOWN_SCRIPT_FILE_EXTENSION);
+        let down_content = if down_script.is_file() {
+            read_template_file(&down_script)?
+        } else {
+            String::new()
+        };
+        Ok(Some(MigrationTemplate::new(name, up_content, down_content)))
+    }
+
+    fn template_path(&self, name: &str, extension: &str) -> PathBuf {
+        self.path.join(format!("{name}{extension}"))
+    }
+}
+
+fn read_template_file(path: &Path) -> Result<String, Error> {
+    fs::read_to_string(path)
+        .map_err(|err| Error::ReadingTemplate(format!("{}: {err}", path.display())))
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,169 @@
+use super::*;
+use assert_fs::TempDir;
+use asserting::prelude::*;
+use database_migration::test_dsl::key;
+
+fn template_variables() -> TemplateVariables {
+    TemplateVariables {
+        key: key("20250614_101520"),
+        title: "define some table".into(),
+        author: "some.user".into(),
+        date: NaiveDate::from_ymd_opt(2025, 6, 14).expect("invalid date"),
+    }
+}
+
+mod migration_template {
+    use super::*;
+
+    #[test]
+    fn all_builtin_templates_are_available() {
+        for name in BUILTIN_TEMPLATES {
+            let template = MigrationTemplate::builtin(name);
+
+            assert_that!(template.map(|tpl| tpl.name().to_string())).is_equal_to(Some(name.into()));
+        }
+    }
+
+    #[test]
+    fn builtin_template_with_unknown_name_is_none() {
+        let template = MigrationTemplate::builtin("not_existing");
+
+        assert_that!(template).is_none();
+    }
+
+    #[test]
+    fn default_template_is_empty() {
+        let template = MigrationTemplate::builtin(DEFAULT_TEMPLATE).expect("missing template");
+
+        assert_that!(template.render(MigrationKind::Up, &template_variables())).is_empty();
+        assert_that!(template.render(MigrationKind::Down, &template_variables())).is_empty();
+    }
+
+    #[test]
+    fn render_template_replaces_all_variables() {
+        let template = MigrationTemplate::new(
+            "custom",
+            "-- {{key}}: {{title}} by {{author}} at {{date}}\n-- {{title}}\n",
+            "",
+        );
+
+        let content = template.render(MigrationKind::Up, &template_variables());
+
+        assert_that!(content).is_equal_to(
+            "-- 20250614_101520: define some table by some.user at 2025-06-14\n-- define some table\n"
+                .to_string(),
+        );
+    }
+
+    #[test]
+    fn render_template_for_down_migration_uses_down_content() {
+        let template = MigrationTemplate::new("custom", "up: {{title}}", "down: {{title}}");
+
+        let content = template.render(MigrationKind::Down, &template_variables());
+
+        assert_that!(content).is_equal_to("down: define some table".to_string());
+    }
+
+    #[test]
+    fn render_builtin_table_template() {
+        let template = MigrationTemplate::builtin("table").expect("missing template");
+
+        let up_content = template.render(MigrationKind::Up, &template_variables());
+        let down_content = template.render(MigrationKind::Down, &template_variables());
+
+        assert_that!(up_content)
+            .contains("-- define some table")
+            .contains("-- key:     20250614_101520")
+            .contains("-- author:  some.user")
+            .contains("-- created: 2025-06-14")
+            .contains("DEFINE TABLE some_table");
+        assert_that!(down_content).contains("REMOVE TABLE some_table;");
+    }
+}
+
+mod template_folder {
+    use super::*;
+
+    #[test]
+    fn find_builtin_template_if_template_folder_does_not_exist() {
+        let template_folder = TemplateFolder::new(Path::new("not_existing/templates"));
+
+        let template = template_folder.find_template("index");
+
+        assert_that!(template).is_equal_to(Ok(
+            MigrationTemplate::builtin("index").expect("missing template")
+        ));
+    }
+
+    #[test]
+    fn find_custom_template_with_up_and_down_script() {
+        let temp_dir = TempDir::new().expect("failed to create temp dir");
+        fs::write(
+            temp_dir.path().join("relation.up.surql"),
+            "DEFINE TABLE likes TYPE RELATION;",
+        )
+        .expect("failed to write up template");
+        fs::write(
+            temp_dir.path().join("relation.down.surql"),
+            "REMOVE TABLE likes;",
+        )
+        .expect("failed to write down template");
+        let template_folder = TemplateFolder::new(temp_dir.path());
+
+        let template = template_folder.find_template("relation");
+
+        assert_that!(template).is_equal_to(Ok(MigrationTemplate::new(
+            "relation",
+            "DEFINE TABLE likes TYPE RELATION;",
+            "REMOVE TABLE likes;",
+        )));
+    }
+
+    #[test]
+    fn find_custom_template_without_direction_and_without_down_script() {
+        let temp_dir = TempDir::new().expect("failed to create temp dir");
+        fs::write(
+            temp_dir.path().join("relation.surql"),
+            "DEFINE TABLE likes TYPE RELATION;",
+        )
+        .expect("failed to write up template");
+        let template_folder = TemplateFolder::new(temp_dir.path());
+
+        let template = template_folder.find_template("relation");
+
+        assert_that!(template).is_equal_to(Ok(MigrationTemplate::new(
+            "relation",
+            "DEFINE TABLE likes TYPE RELATION;",
+            "",
+        )));
+    }
+
+    #[test]
+    fn custom_template_takes_precedence_over_builtin_template() {
+        let temp_dir = TempDir::new().expect("failed to create temp dir");
+        fs::write(
+            temp_dir.path().join("table.up.surql"),
+            "DEFINE TABLE {{title}};",
+        )
+        .expect("failed to write up template");
+        let template_folder = TemplateFolder::new(temp_dir.path());
+
+        let template = template_folder.find_template("table");
+
+        assert_that!(template).is_equal_to(Ok(MigrationTemplate::new(
+            "table",
+            "DEFINE TABLE {{title}};",
+            "",
+        )));
+    }
+
+    #[test]
+    fn find_template_that_does_not_exist() {
+        let temp_dir = TempDir::new().expect("failed to create temp dir");
+        let template_folder = TemplateFolder::new(temp_dir.path());
+
+        let template = template_folder.find_template("not_existing");
+
+        assert_that!(template).is_equal_to(Err(Error::TemplateNotFound("not_existing".into())));
+    }
+}
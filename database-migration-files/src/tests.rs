@@ -488,6 +488,57 @@ fn list_migrations_ignores_configured_filenames_default_pattern_todo_txt_file()
     assert_that!(migrations).is_empty();
 }
 
+#[test]
+fn list_all_migrations_ignores_callback_scripts() {
+    let migrations_folder = Path::new("../fixtures/with_callbacks/migrations");
+    let excluded_files = ExcludedFiles::default();
+    let migration_directory = MigrationDirectory::new(migrations_folder, &excluded_files);
+
+    let migrations = migration_directory
+        .list_all_migrations()
+        .unwrap_or_else(|err| panic!("failed to list all migrations: {err}"))
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap_or_else(|err| panic!("failed to parse migrations: {err}"));
+
+    assert_that!(
+        migrations
+            .iter()
+            .map(|mig| (mig.key, mig.kind))
+            .collect::<Vec<_>>()
+    )
+    .contains_exactly_in_any_order([
+        (key("20250103_140520"), MigrationKind::Up),
+        (key("20250103_140520"), MigrationKind::Down),
+        (key("20250103_141521"), MigrationKind::Up),
+        (key("20250103_141521"), MigrationKind::Down),
+    ]);
+}
+
+#[test]
+fn list_all_migrations_reports_callback_script_in_subfolder_as_invalid() {
+    let temp_dir = TempDir::new().unwrap_or_else(|err| panic!("could not create temp dir: {err}"));
+    let migrations_folder = temp_dir.path();
+    let subfolder = migrations_folder.join("subdir01");
+    fs::create_dir(&subfolder).unwrap_or_else(|err| panic!("could not create subfolder: {err}"));
+    fs::write(subfolder.join("before_migrate.surql"), "")
+        .unwrap_or_else(|err| panic!("could not write callback script: {err}"));
+
+    let excluded_files = ExcludedFiles::default();
+    let migration_directory = MigrationDirectory::new(migrations_folder, &excluded_files);
+
+    let migrations = migration_directory
+        .list_all_migrations()
+        .unwrap_or_else(|err| panic!("failed to list all migrations: {err}"))
+        .collect::<Vec<_>>();
+
+    assert_that!(&migrations).has_length(1);
+    assert_that!(matches!(
+        migrations[0],
+        Err(Error::Definition(DefinitionError::InvalidDate(_)))
+    ))
+    .is_true();
+}
+
 #[test]
 fn read_script_content_for_basic_migrations() {
     let migrations_folder = Path::new("../fixtures/basic/migrations");
@@ -551,6 +602,36 @@ fn read_script_content_for_non_existing_migration() {
     assert_that!(matches!(result, Err(Error::ReadingMigrationFile(_)))).is_true();
 }
 
+#[test]
+fn read_callback_script_that_exists() {
+    let migrations_folder = Path::new("../fixtures/with_callbacks/migrations");
+    let excluded_files = ExcludedFiles::empty();
+    let migration_directory = MigrationDirectory::new(migrations_folder, &excluded_files);
+
+    let callback_script = migration_directory
+        .read_callback_script(Callback::BeforeMigrate)
+        .unwrap_or_else(|err| panic!("failed to read callback script: {err}"));
+
+    assert_that!(callback_script).is_equal_to(Some(CallbackScript {
+        callback: Callback::BeforeMigrate,
+        script_path: migrations_folder.join("before_migrate.surql"),
+        content: "CREATE audit SET callback = 'before_migrate', at = time::now();\n".into(),
+    }));
+}
+
+#[test]
+fn read_callback_script_that_does_not_exist() {
+    let migrations_folder = Path::new("../fixtures/with_callbacks/migrations");
+    let excluded_files = ExcludedFiles::empty();
+    let migration_directory = MigrationDirectory::new(migrations_folder, &excluded_files);
+
+    let callback_script = migration_directory
+        .read_callback_script(Callback::BeforeEachMigrate)
+        .unwrap_or_else(|err| panic!("failed to read callback script: {err}"));
+
+    assert_that!(callback_script).is_none();
+}
+
 #[test]
 fn create_migrations_folder_if_not_existing_folder_does_not_exist() {
     let parent_dir = TempDir::new().expect("failed to create temp dir");
@@ -636,6 +717,29 @@ fn create_migration_file_for_new_migration() {
         .is_true();
 }
 
+#[test]
+fn create_migration_file_for_new_migration_with_content() {
+    let temp_dir = TempDir::new().expect("failed to create temp dir");
+    let migrations_folder = temp_dir.path();
+
+    let filename_strategy = MigrationFilenameStrategy::default();
+    let migration_files = MigrationFiles::new(migrations_folder, filename_strategy);
+
+    let new_migration = NewMigration {
+        key: key("20250115_201642"),
+        title: "create some table".into(),
+        kind: MigrationKind::Up,
+    };
+
+    let migration = migration_files
+        .create_new_migration_with_content(new_migration, "DEFINE TABLE some_table;\n");
+
+    assert_that!(migration)
+        .ok()
+        .extracting(|mig| fs::read_to_string(mig.script_path).ok())
+        .is_equal_to(Some("DEFINE TABLE some_table;\n".to_string()));
+}
+
 #[test]
 fn create_migration_file_for_new_migration_with_empty_title() {
     let temp_dir = TempDir::new().expect("failed to create temp dir");
@@ -4,6 +4,7 @@
 mod dummy_extern_uses {
     use assert_fs as _;
     use asserting as _;
+    use chrono as _;
     use database_migration as _;
     use database_migration_files as _;
     use walkdir as _;
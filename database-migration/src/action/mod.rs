@@ -1,5 +1,5 @@
 use crate::migration::{
-    ApplicableMigration, Execution, Problem, ProblematicMigration, ScriptContent,
+    ApplicableMigration, Execution, MigrationKind, Problem, ProblematicMigration, ScriptContent,
 };
 use chrono::NaiveDateTime;
 use enumset::{EnumSet, EnumSetIter, EnumSetType};
@@ -169,8 +169,10 @@ impl ListOutOfOrder for Verify {
             return Vec::new();
         }
         if let Some(&last_applied_key) = executed_migrations.keys().max_by_key(|key| **key) {
+            let baseline_key = applied_baseline_key(defined_migrations, executed_migrations);
             defined_migrations
                 .iter()
+                .filter(|mig| !mig.kind.is_baseline() && !is_squashed(mig, baseline_key))
                 .filter_map(|mig| {
                     if last_applied_key > mig.key && !executed_migrations.contains_key(&mig.key) {
                         Some(ProblematicMigration {
@@ -199,12 +201,16 @@ impl ListChangedAfterExecution for Verify {
         if self.ignore_checksums {
             return Vec::new();
         }
+        let baseline_key = applied_baseline_key(defined_migrations, executed_migrations);
         defined_migrations
             .iter()
             .filter_map(|mig| {
-                if mig.kind.is_forward() {
+                if mig.kind.is_forward() && !is_squashed(mig, baseline_key) {
                     executed_migrations.get(&mig.key).and_then(|exec| {
-                        if exec.checksum != mig.checksum {
+                        if exec.checksum != mig.checksum
+                            && executed_kind(exec, defined_migrations, executed_migrations)
+                                == mig.kind
+                        {
                             Some(ProblematicMigration {
                                 key: mig.key,
                                 kind: mig.kind,
@@ -245,9 +251,21 @@ impl MigrationsToApply for Migrate {
         defined_migrations: &[ScriptContent],
         executed_migrations: &IndexMap<NaiveDateTime, Execution>,
     ) -> IndexMap<NaiveDateTime, ApplicableMigration> {
+        let fresh_database = executed_migrations.is_empty();
+        let baseline_key = if fresh_database {
+            latest_baseline_key(defined_migrations)
+        } else {
+            applied_baseline_key(defined_migrations, executed_migrations)
+        };
         defined_migrations
             .iter()
-            .filter(|mig| mig.kind.is_forward() && !executed_migrations.contains_key(&mig.key))
+            .filter(|mig| match mig.kind {
+                MigrationKind::Baseline => fresh_database && Some(mig.key) == baseline_key,
+                MigrationKind::Up => {
+                    !executed_migrations.contains_key(&mig.key) && !is_squashed(mig, baseline_key)
+                },
+                MigrationKind::Down => false,
+            })
             .map(to_applicable_migration)
             .collect()
     }
@@ -264,20 +282,94 @@ impl MigrationsToApply for Revert {
         defined_migrations: &[ScriptContent],
         executed_migrations: &IndexMap<NaiveDateTime, Execution>,
     ) -> IndexMap<NaiveDateTime, ApplicableMigration> {
+        let baseline_key = applied_baseline_key(defined_migrations, executed_migrations);
         defined_migrations
             .iter()
-            .filter(|mig| mig.kind.is_backward() && executed_migrations.contains_key(&mig.key))
+            .filter(|mig| {
+                mig.kind.is_backward()
+                    && executed_migrations.contains_key(&mig.key)
+                    && !is_squashed(mig, baseline_key)
+            })
             .map(to_applicable_migration)
             .collect()
     }
 }
 
+/// Returns the key of the latest baseline defined in the given migrations.
+fn latest_baseline_key(defined_migrations: &[ScriptContent]) -> Option<NaiveDateTime> {
+    defined_migrations
+        .iter()
+        .filter(|mig| mig.kind.is_baseline())
+        .map(|mig| mig.key)
+        .max()
+}
+
+/// Returns the key of the latest baseline that has been applied to the
+/// database.
+///
+/// A baseline is considered applied if there is an execution with the same key
+/// and the same checksum as the baseline script. Existing databases that have
+/// been migrated with the migrations squashed into a baseline have an execution
+/// with the baseline's key, but with the checksum of the last squashed
+/// migration.
+fn applied_baseline_key(
+    defined_migrations: &[ScriptContent],
+    executed_migrations: &IndexMap<NaiveDateTime, Execution>,
+) -> Option<NaiveDateTime> {
+    defined_migrations
+        .iter()
+        .filter(|mig| {
+            mig.kind.is_baseline()
+                && executed_migrations
+                    .get(&mig.key)
+                    .is_some_and(|exec| exec.checksum == mig.checksum)
+        })
+        .map(|mig| mig.key)
+        .max()
+}
+
+/// Returns the kind of the migration script the given execution belongs to.
+///
+/// A baseline has the same key as the last migration squashed into it. An
+/// execution with such a key belongs to the script with the same checksum. If
+/// both scripts have been changed, the execution belongs to the baseline, if
+/// the database has been bootstrapped from it, i.e. no migration with a lower
+/// key has been executed.
+fn executed_kind(
+    execution: &Execution,
+    defined_migrations: &[ScriptContent],
+    executed_migrations: &IndexMap<NaiveDateTime, Execution>,
+) -> MigrationKind {
+    let mut candidates = defined_migrations
+        .iter()
+        .filter(|mig| mig.key == execution.key && mig.kind.is_forward());
+    if let Some(mig) = candidates
+        .clone()
+        .find(|mig| mig.checksum == execution.checksum)
+    {
+        return mig.kind;
+    }
+    if candidates.any(|mig| mig.kind.is_baseline())
+        && executed_migrations.keys().all(|key| *key >= execution.key)
+    {
+        MigrationKind::Baseline
+    } else {
+        MigrationKind::Up
+    }
+}
+
+/// Whether the given migration is squashed into the baseline with the given key.
+fn is_squashed(migration: &ScriptContent, baseline_key: Option<NaiveDateTime>) -> bool {
+    !migration.kind.is_baseline() && baseline_key.is_some_and(|key| migration.key <= key)
+}
+
 fn to_applicable_migration(mig: &ScriptContent) -> (NaiveDateTime, ApplicableMigration) {
     (
         mig.key,
         ApplicableMigration {
             key: mig.key,
             kind: mig.kind,
+            script_path: mig.path.clone(),
             script_content: mig.content.clone(),
             checksum: mig.checksum,
         },
@@ -7,6 +7,51 @@ use chrono::DateTime;
 use std::path::Path;
 use std::time::Duration;
 
+fn migrations_squashed_into_baseline() -> Vec<ScriptContent> {
+    vec![
+        ScriptContent {
+            key: key("20250109_115959"),
+            kind: MigrationKind::Up,
+            path: Path::new("migrations/20250109_115959_define_quote_table.up.surql").into(),
+            content: "DEFINE TABLE quote SCHEMAFULL;".into(),
+            checksum: Checksum(0x_4D65A4BF),
+        },
+        ScriptContent {
+            key: key("20250109_125900"),
+            kind: MigrationKind::Up,
+            path: Path::new("migrations/20250109_125900_define_author_field.up.surql").into(),
+            content: "DEFINE FIELD author ON quote TYPE string;".into(),
+            checksum: Checksum(0x_08C11ABD),
+        },
+        ScriptContent {
+            key: key("20250109_125900"),
+            kind: MigrationKind::Baseline,
+            path: Path::new("migrations/20250109_125900_squashed.baseline.surql").into(),
+            content: "DEFINE TABLE quote SCHEMAFULL;\nDEFINE FIELD author ON quote TYPE string;"
+                .into(),
+            checksum: Checksum(0x_5C3A0E41),
+        },
+        ScriptContent {
+            key: key("20250110_090059"),
+            kind: MigrationKind::Up,
+            path: Path::new("migrations/20250110_090059_define_text_field.up.surql").into(),
+            content: "DEFINE FIELD text ON quote TYPE string;".into(),
+            checksum: Checksum(0x_DD081E07),
+        },
+    ]
+}
+
+fn execution(key_str: &str, applied_rank: i64, checksum: Checksum) -> Execution {
+    Execution {
+        key: key(key_str),
+        applied_rank,
+        applied_by: "some.user".into(),
+        checksum,
+        applied_at: DateTime::default(),
+        execution_time: Duration::default(),
+    }
+}
+
 mod checks {
     #![allow(clippy::iter_on_single_items)]
 
@@ -734,6 +779,117 @@ mod verify {
             },
         }]);
     }
+
+    #[test]
+    fn list_out_of_order_ignores_baseline_and_squashed_migrations_on_database_bootstrapped_from_baseline()
+     {
+        let defined = migrations_squashed_into_baseline();
+
+        let executed = executed_migrations([
+            execution("20250109_125900", 1, Checksum(0x_5C3A0E41)),
+            execution("20250110_090059", 2, Checksum(0x_DD081E07)),
+        ]);
+
+        let verify = Verify::default();
+        let out_of_order = verify.list_out_of_order(&defined, &executed);
+
+        assert_that!(out_of_order).is_empty();
+    }
+
+    #[test]
+    fn list_out_of_order_ignores_baseline_on_database_migrated_before_squashing() {
+        let defined = migrations_squashed_into_baseline();
+
+        let executed = executed_migrations([
+            execution("20250109_115959", 1, Checksum(0x_4D65A4BF)),
+            execution("20250109_125900", 2, Checksum(0x_08C11ABD)),
+            execution("20250110_090059", 3, Checksum(0x_DD081E07)),
+        ]);
+
+        let verify = Verify::default();
+        let out_of_order = verify.list_out_of_order(&defined, &executed);
+
+        assert_that!(out_of_order).is_empty();
+    }
+
+    #[test]
+    fn list_changed_migrations_ignores_squashed_migrations_on_database_bootstrapped_from_baseline()
+    {
+        let defined = migrations_squashed_into_baseline();
+
+        let executed = executed_migrations([
+            execution("20250109_125900", 1, Checksum(0x_5C3A0E41)),
+            execution("20250110_090059", 2, Checksum(0x_DD081E07)),
+        ]);
+
+        let verify = Verify::default();
+        let changed = verify.list_changed_after_execution(&defined, &executed);
+
+        assert_that!(changed).is_empty();
+    }
+
+    #[test]
+    fn list_changed_migrations_ignores_baseline_on_database_migrated_before_squashing() {
+        let defined = migrations_squashed_into_baseline();
+
+        let executed = executed_migrations([
+            execution("20250109_115959", 1, Checksum(0x_4D65A4BF)),
+            execution("20250109_125900", 2, Checksum(0x_08C11ABD)),
+        ]);
+
+        let verify = Verify::default();
+        let changed = verify.list_changed_after_execution(&defined, &executed);
+
+        assert_that!(changed).is_empty();
+    }
+
+    #[test]
+    fn list_changed_migrations_reports_baseline_changed_after_bootstrapping_database_from_it() {
+        let defined = migrations_squashed_into_baseline();
+
+        let executed = executed_migrations([
+            execution("20250109_125900", 1, Checksum(0x_6C3A0E41)),
+            execution("20250110_090059", 2, Checksum(0x_DD081E07)),
+        ]);
+
+        let verify = Verify::default();
+        let changed = verify.list_changed_after_execution(&defined, &executed);
+
+        assert_that!(changed).contains_exactly(vec![ProblematicMigration {
+            key: key("20250109_125900"),
+            kind: MigrationKind::Baseline,
+            script_path: Path::new("migrations/20250109_125900_squashed.baseline.surql").into(),
+            problem: Problem::ChecksumMismatch {
+                definition_checksum: Checksum(0x_5C3A0E41),
+                execution_checksum: Checksum(0x_6C3A0E41),
+            },
+        }]);
+    }
+
+    #[test]
+    fn list_changed_migrations_reports_squashed_migration_changed_on_database_migrated_before_squashing()
+     {
+        let defined = migrations_squashed_into_baseline();
+
+        let executed = executed_migrations([
+            execution("20250109_115959", 1, Checksum(0x_4D65A4BF)),
+            execution("20250109_125900", 2, Checksum(0x_18C11ABD)),
+        ]);
+
+        let verify = Verify::default();
+        let changed = verify.list_changed_after_execution(&defined, &executed);
+
+        assert_that!(changed).contains_exactly(vec![ProblematicMigration {
+            key: key("20250109_125900"),
+            kind: MigrationKind::Up,
+            script_path: Path::new("migrations/20250109_125900_define_author_field.up.surql")
+                .into(),
+            problem: Problem::ChecksumMismatch {
+                definition_checksum: Checksum(0x_08C11ABD),
+                execution_checksum: Checksum(0x_18C11ABD),
+            },
+        }]);
+    }
 }
 
 mod migrate {
@@ -758,6 +914,8 @@ mod migrate {
             ApplicableMigration {
                 key: key("20250109_125900"),
                 kind: MigrationKind::Up,
+                script_path: Path::new("migrations/20250109_125900_create_name_set_one.surql")
+                    .into(),
                 script_content: r#"LET $data = ["J. Jonah Jameson", "James Earl Jones"];"#.into(),
                 checksum: Checksum(0x_08C11ABD),
             },
@@ -799,6 +957,8 @@ mod migrate {
             ApplicableMigration {
                 key: key("20250110_090059"),
                 kind: MigrationKind::Up,
+                script_path: Path::new("migrations/20250110_090059_create_name_set_two.surql")
+                    .into(),
                 script_content: r#"LET $data = ["Alice Sulton", "Tamara Jackson"];"#.into(),
                 checksum: Checksum(0x_DD081E07),
             },
@@ -840,17 +1000,97 @@ mod migrate {
             ApplicableMigration {
                 key: key("20250109_125900"),
                 kind: MigrationKind::Baseline,
+                script_path: Path::new("migrations/20250109_125900_create_name_set_one.surql")
+                    .into(),
                 script_content: r#"LET $data = ["J. Jonah Jameson", "James Earl Jones"];"#.into(),
                 checksum: Checksum(0x_08C11ABD),
             },
             ApplicableMigration {
                 key: key("20250110_090059"),
                 kind: MigrationKind::Up,
+                script_path: Path::new("migrations/20250110_090059_create_name_set_three.surql")
+                    .into(),
                 script_content: r#"LET $data = ["Simon Says", "Lucy May"];"#.into(),
                 checksum: Checksum(0x_AA0137FA),
             },
         ]));
     }
+
+    #[test]
+    fn list_migrations_to_apply_on_fresh_database_applies_latest_baseline_and_newer_migrations() {
+        let defined = migrations_squashed_into_baseline();
+
+        let executed = executed_migrations([]);
+
+        let migrate = Migrate::default();
+        let applicable = migrate.list_migrations_to_apply(&defined, &executed);
+
+        assert_that!(applicable).contains_exactly_in_any_order(applicable_migrations([
+            ApplicableMigration {
+                key: key("20250109_125900"),
+                kind: MigrationKind::Baseline,
+                script_path: Path::new("migrations/20250109_125900_squashed.baseline.surql").into(),
+                script_content:
+                    "DEFINE TABLE quote SCHEMAFULL;\nDEFINE FIELD author ON quote TYPE string;"
+                        .into(),
+                checksum: Checksum(0x_5C3A0E41),
+            },
+            ApplicableMigration {
+                key: key("20250110_090059"),
+                kind: MigrationKind::Up,
+                script_path: Path::new("migrations/20250110_090059_define_text_field.up.surql")
+                    .into(),
+                script_content: "DEFINE FIELD text ON quote TYPE string;".into(),
+                checksum: Checksum(0x_DD081E07),
+            },
+        ]));
+    }
+
+    #[test]
+    fn list_migrations_to_apply_on_database_migrated_before_squashing_ignores_baseline() {
+        let defined = migrations_squashed_into_baseline();
+
+        let executed = executed_migrations([
+            execution("20250109_115959", 1, Checksum(0x_4D65A4BF)),
+            execution("20250109_125900", 2, Checksum(0x_08C11ABD)),
+        ]);
+
+        let migrate = Migrate::default();
+        let applicable = migrate.list_migrations_to_apply(&defined, &executed);
+
+        assert_that!(applicable).contains_exactly_in_any_order(applicable_migrations([
+            ApplicableMigration {
+                key: key("20250110_090059"),
+                kind: MigrationKind::Up,
+                script_path: Path::new("migrations/20250110_090059_define_text_field.up.surql")
+                    .into(),
+                script_content: "DEFINE FIELD text ON quote TYPE string;".into(),
+                checksum: Checksum(0x_DD081E07),
+            },
+        ]));
+    }
+
+    #[test]
+    fn list_migrations_to_apply_on_database_bootstrapped_from_baseline_skips_squashed_migrations() {
+        let defined = migrations_squashed_into_baseline();
+
+        let executed =
+            executed_migrations([execution("20250109_125900", 1, Checksum(0x_5C3A0E41))]);
+
+        let migrate = Migrate::default();
+        let applicable = migrate.list_migrations_to_apply(&defined, &executed);
+
+        assert_that!(applicable).contains_exactly_in_any_order(applicable_migrations([
+            ApplicableMigration {
+                key: key("20250110_090059"),
+                kind: MigrationKind::Up,
+                script_path: Path::new("migrations/20250110_090059_define_text_field.up.surql")
+                    .into(),
+                script_content: "DEFINE FIELD text ON quote TYPE string;".into(),
+                checksum: Checksum(0x_DD081E07),
+            },
+        ]));
+    }
 }
 
 mod revert {
@@ -882,6 +1122,8 @@ mod revert {
             ApplicableMigration {
                 key: key("20250109_125900"),
                 kind: MigrationKind::Down,
+                script_path: Path::new("migrations/20250109_125900_create_name_set_one.surql")
+                    .into(),
                 script_content: r#"LET $data = ["J. Jonah Jameson", "James Earl Jones"];"#.into(),
                 checksum: Checksum(0x_08C11ABD),
             },
@@ -923,6 +1165,8 @@ mod revert {
             ApplicableMigration {
                 key: key("20250109_125900"),
                 kind: MigrationKind::Down,
+                script_path: Path::new("migrations/20250109_125900_create_name_set_one.surql")
+                    .into(),
                 script_content: r#"LET $data = ["J. Jonah Jameson", "James Earl Jones"];"#.into(),
                 checksum: Checksum(0x_08C11ABD),
             },
@@ -989,6 +1233,8 @@ mod revert {
             ApplicableMigration {
                 key: key("20250109_130000"),
                 kind: MigrationKind::Down,
+                script_path: Path::new("migrations/20250109_130000_create_name_set_two.surql")
+                    .into(),
                 script_content: r#"LET $data = ["Alice Sulton", "Tamara Jackson"];"#.into(),
                 checksum: Checksum(0x_DD081E07),
             },
@@ -0,0 +1,95 @@
+use crate::definition::SCRIPT_FILE_EXTENSION;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+
+/// A point in the lifecycle of a migrate or revert run at which a callback
+/// script is executed.
+///
+/// Callback scripts are optional. They are located in the migrations folder
+/// and are named after the callback, e.g. `before_migrate.surql`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Callback {
+    /// Before any migration is applied.
+    BeforeMigrate,
+    /// Before each migration is applied.
+    BeforeEachMigrate,
+    /// After each migration has been applied.
+    AfterEachMigrate,
+    /// After all migrations have been applied.
+    AfterMigrate,
+    /// Before any migration is reverted.
+    BeforeRevert,
+    /// Before each migration is reverted.
+    BeforeEachRevert,
+    /// After each migration has been reverted.
+    AfterEachRevert,
+    /// After all migrations have been reverted.
+    AfterRevert,
+}
+
+impl Callback {
+    pub const fn all() -> [Self; 8] {
+        [
+            Self::BeforeMigrate,
+            Self::BeforeEachMigrate,
+            Self::AfterEachMigrate,
+            Self::AfterMigrate,
+            Self::BeforeRevert,
+            Self::BeforeEachRevert,
+            Self::AfterEachRevert,
+            Self::AfterRevert,
+        ]
+    }
+
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::BeforeMigrate => "before_migrate",
+            Self::BeforeEachMigrate => "before_each_migrate",
+            Self::AfterEachMigrate => "after_each_migrate",
+            Self::AfterMigrate => "after_migrate",
+            Self::BeforeRevert => "before_revert",
+            Self::BeforeEachRevert => "before_each_revert",
+            Self::AfterEachRevert => "after_each_revert",
+            Self::AfterRevert => "after_revert",
+        }
+    }
+
+    /// Returns the filename of the script for this callback.
+    pub fn filename(&self) -> String {
+        format!("{}{SCRIPT_FILE_EXTENSION}", self.as_str())
+    }
+
+    /// Returns the callback for the given filename or `None` if the filename
+    /// is not the filename of a callback script.
+    pub fn from_filename(filename: &str) -> Option<Self> {
+        let name = filename.strip_suffix(SCRIPT_FILE_EXTENSION)?;
+        Self::all()
+            .into_iter()
+            .find(|callback| callback.as_str() == name)
+    }
+
+    /// Returns the callback for the script at the given path or `None` if
+    /// the path does not point to a callback script.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        path.file_name()
+            .and_then(|filename| filename.to_str())
+            .and_then(Self::from_filename)
+    }
+}
+
+impl Display for Callback {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// The content of a callback script read from the migrations folder.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallbackScript {
+    pub callback: Callback,
+    pub script_path: PathBuf,
+    pub content: String,
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,44 @@
+use super::*;
+use asserting::prelude::*;
+
+#[test]
+fn filename_of_before_migrate_callback() {
+    assert_that!(Callback::BeforeMigrate.filename()).is_equal_to("before_migrate.surql");
+}
+
+#[test]
+fn filename_of_after_each_revert_callback() {
+    assert_that!(Callback::AfterEachRevert.filename()).is_equal_to("after_each_revert.surql");
+}
+
+#[test]
+fn callback_from_its_filename() {
+    for callback in Callback::all() {
+        assert_that!(Callback::from_filename(&callback.filename())).is_equal_to(Some(callback));
+    }
+}
+
+#[test]
+fn callback_from_filename_without_script_extension() {
+    assert_that!(Callback::from_filename("after_migrate.sql")).is_none();
+}
+
+#[test]
+fn callback_from_filename_of_a_migration() {
+    assert_that!(Callback::from_filename(
+        "20250103_140520_before_migrate.surql"
+    ))
+    .is_none();
+}
+
+#[test]
+fn callback_from_path() {
+    let path = Path::new("fixtures/callbacks/before_revert.surql");
+
+    assert_that!(Callback::from_path(path)).is_equal_to(Some(Callback::BeforeRevert));
+}
+
+#[test]
+fn display_callback() {
+    assert_that!(Callback::AfterEachMigrate.to_string()).is_equal_to("after_each_migrate");
+}
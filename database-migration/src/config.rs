@@ -1,21 +1,38 @@
 use crate::definition::ExcludedFiles;
 use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fmt::{Debug, Formatter};
 use std::path::Path;
+use std::time::Duration;
 
 pub const DEFAULT_MIGRATIONS_FOLDER: &str = "migrations";
 pub const DEFAULT_MIGRATIONS_TABLE: &str = "migrations";
 pub const DEFAULT_EXCLUDED_FILES: &str = ".*|README*|TODO*";
+pub const DEFAULT_TEMPLATE_FOLDER: &str = "templates";
+pub const DEFAULT_SCHEMA_FOLDER: &str = "schema";
 
 pub const MIGRATION_KEY_FORMAT_STR: &str = "%Y%m%d_%H%M%S";
 
+/// Placeholder printed instead of a password or a token in the `Debug` output of
+/// configuration structs.
+pub const REDACTED_PASSWORD: &str = "<redacted>";
+
 #[must_use]
 #[derive(Debug, Clone, PartialEq)]
 pub struct RunnerConfig<'a> {
     pub migrations_folder: Cow<'a, Path>,
+    /// Folder holding the migrations applied at root level. No root level
+    /// migrations are applied if not set.
+    pub root_migrations_folder: Option<Cow<'a, Path>>,
+    /// Folder holding the migrations applied at namespace level. No namespace
+    /// level migrations are applied if not set.
+    pub namespace_migrations_folder: Option<Cow<'a, Path>>,
     pub excluded_files: ExcludedFiles,
     pub migrations_table: Cow<'a, str>,
     pub ignore_checksum: bool,
     pub ignore_order: bool,
+    pub template_folder: Cow<'a, Path>,
+    pub schema_folder: Cow<'a, Path>,
 }
 
 impl Default for RunnerConfig<'_> {
@@ -24,10 +41,14 @@ impl Default for RunnerConfig<'_> {
 
         Self {
             migrations_folder: Path::new(DEFAULT_MIGRATIONS_FOLDER).into(),
+            root_migrations_folder: None,
+            namespace_migrations_folder: None,
             excluded_files,
             migrations_table: DEFAULT_MIGRATIONS_TABLE.into(),
             ignore_checksum: false,
             ignore_order: false,
+            template_folder: Path::new(DEFAULT_TEMPLATE_FOLDER).into(),
+            schema_folder: Path::new(DEFAULT_SCHEMA_FOLDER).into(),
         }
     }
 }
@@ -38,6 +59,22 @@ impl<'a> RunnerConfig<'a> {
         self
     }
 
+    pub fn with_root_migrations_folder(
+        mut self,
+        root_migrations_folder: impl Into<Cow<'a, Path>>,
+    ) -> Self {
+        self.root_migrations_folder = Some(root_migrations_folder.into());
+        self
+    }
+
+    pub fn with_namespace_migrations_folder(
+        mut self,
+        namespace_migrations_folder: impl Into<Cow<'a, Path>>,
+    ) -> Self {
+        self.namespace_migrations_folder = Some(namespace_migrations_folder.into());
+        self
+    }
+
     pub fn with_migrations_table(mut self, migrations_table: impl Into<Cow<'a, str>>) -> Self {
         self.migrations_table = migrations_table.into();
         self
@@ -52,23 +89,58 @@ impl<'a> RunnerConfig<'a> {
         self.ignore_order = ignore_order;
         self
     }
+
+    pub fn with_template_folder(mut self, template_folder: impl Into<Cow<'a, Path>>) -> Self {
+        self.template_folder = template_folder.into();
+        self
+    }
+
+    pub fn with_schema_folder(mut self, schema_folder: impl Into<Cow<'a, Path>>) -> Self {
+        self.schema_folder = schema_folder.into();
+        self
+    }
 }
 
+/// The method used to authenticate to the database instance.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DbAuthLevel {
+    /// Sign in as a root user with username and password.
     Root,
+    /// Sign in as a namespace user with username and password.
     Namespace,
+    /// Sign in as a database user with username and password.
     Database,
+    /// Sign in via a record access method defined with `DEFINE ACCESS ...
+    /// TYPE RECORD`, passing the access variables to its `SIGNIN` clause.
+    Record,
+    /// Authenticate with an existing token, e.g. a JWT issued for an access
+    /// method defined with `DEFINE ACCESS ... TYPE JWT`.
+    Token,
+}
+
+/// The capabilities of an embedded database engine, like functions,
+/// scripting and network access, that are allowed to be used by queries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DbCapabilities {
+    /// The default capabilities of the database engine.
+    Default,
+    /// Allow all capabilities.
+    AllowAll,
+    /// Deny all capabilities.
+    DenyAll,
 }
 
 #[must_use]
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq)]
 pub struct DbClientConfig<'a> {
     /// Address of the database instance.
     ///
     /// Examples:
     /// - `"ws://localhost:8000"`
     /// - `"wss://cloud.surrealdb.com"`
+    /// - `"mem://"` (embedded, requires the crate feature `kv-mem`)
+    /// - `"surrealkv://data/mydb"` (embedded, requires the crate feature
+    ///   `kv-surrealkv`)
     ///
     /// Default: `"ws://localhost:8000"`
     pub address: Cow<'a, str>,
@@ -83,13 +155,16 @@ pub struct DbClientConfig<'a> {
     /// Default: `"test"`
     pub database: Cow<'a, str>,
 
-    /// The kind of the system user used for authentication.
+    /// The method used for authentication.
     ///
     /// Default: `Root`
     pub auth_level: DbAuthLevel,
 
     /// Username used to authenticate to the database instance.
     ///
+    /// With the auth levels `Record` and `Token` the username is only
+    /// recorded as the user who applied or reverted a migration.
+    ///
     /// Default: `"root"`
     pub username: Cow<'a, str>,
 
@@ -98,6 +173,44 @@ pub struct DbClientConfig<'a> {
     /// Default: `"root"`
     pub password: Cow<'a, str>,
 
+    /// Name of the record access method used with the auth level `Record`.
+    ///
+    /// Default: `""`
+    pub access: Cow<'a, str>,
+
+    /// Variables passed to the `SIGNIN` clause of the record access method
+    /// used with the auth level `Record`, e.g. `email` and `password`.
+    ///
+    /// Default: no variables
+    pub access_variables: BTreeMap<String, String>,
+
+    /// Token used to authenticate with the auth level `Token`.
+    ///
+    /// Default: `""`
+    pub token: Cow<'a, str>,
+
+    /// Maximum time for one attempt to connect and sign in to the database
+    /// instance.
+    ///
+    /// Default: 10 seconds
+    pub connect_timeout: Duration,
+
+    /// Number of times connecting is retried if the database instance is not
+    /// reachable.
+    ///
+    /// Example:
+    /// - `0` (= no retries)
+    /// - `10`
+    ///
+    /// Default: `0`
+    pub connect_retries: u32,
+
+    /// Time to wait before the first retry to connect. The interval doubles
+    /// after each retry up to a maximum of one minute.
+    ///
+    /// Default: 1 second
+    pub connect_retry_interval: Duration,
+
     /// Capacity of the channels to the database.
     ///
     /// Example:
@@ -106,6 +219,110 @@ pub struct DbClientConfig<'a> {
     ///
     /// Default: `20`
     pub capacity: usize,
+
+    /// Maximum time a query may take, or `None` for the default of the
+    /// database engine.
+    ///
+    /// Only applies to embedded database engines. A database server uses its
+    /// own settings.
+    ///
+    /// Default: `None`
+    pub query_timeout: Option<Duration>,
+
+    /// Maximum time a transaction may take, or `None` for the default of the
+    /// database engine.
+    ///
+    /// Only applies to embedded database engines. A database server uses its
+    /// own settings.
+    ///
+    /// Default: `None`
+    pub transaction_timeout: Option<Duration>,
+
+    /// Whether the database engine runs in strict mode, in which namespaces,
+    /// databases and tables must be defined before they can be used.
+    ///
+    /// Only applies to embedded database engines. A database server uses its
+    /// own settings.
+    ///
+    /// Default: `false`
+    pub strict: bool,
+
+    /// The capabilities allowed to be used by queries.
+    ///
+    /// Only applies to embedded database engines. A database server uses its
+    /// own settings.
+    ///
+    /// Default: `Default`
+    pub capabilities: DbCapabilities,
+
+    /// PEM file with the certificates of the certificate authorities (CA)
+    /// trusted to sign the certificate of the database server. If not set,
+    /// the well known public CAs are trusted.
+    ///
+    /// Only applies to connections via `wss` or `https`.
+    ///
+    /// Default: `None`
+    pub tls_ca_file: Option<Cow<'a, Path>>,
+
+    /// PEM file with the client certificate (chain) presented to the database
+    /// server. Must be set together with `tls_client_key_file`.
+    ///
+    /// Default: `None`
+    pub tls_client_cert_file: Option<Cow<'a, Path>>,
+
+    /// PEM file with the private key of the client certificate. Must be set
+    /// together with `tls_client_cert_file`.
+    ///
+    /// Default: `None`
+    pub tls_client_key_file: Option<Cow<'a, Path>>,
+
+    /// Name the certificate of the database server is verified against,
+    /// instead of the host name of the address, e.g. if the database server is
+    /// reached via an IP address or a tunnel.
+    ///
+    /// Default: `None`
+    pub tls_server_name: Option<Cow<'a, str>>,
+
+    /// Whether to accept any certificate presented by the database server,
+    /// including self-signed and expired ones.
+    ///
+    /// This disables the protection against man-in-the-middle attacks. Use it
+    /// for development only!
+    ///
+    /// Default: `false`
+    pub tls_accept_invalid_certs: bool,
+}
+
+impl Debug for DbClientConfig<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DbClientConfig")
+            .field("address", &self.address)
+            .field("namespace", &self.namespace)
+            .field("database", &self.database)
+            .field("auth_level", &self.auth_level)
+            .field("username", &self.username)
+            .field("password", &REDACTED_PASSWORD)
+            .field("access", &self.access)
+            .field(
+                "access_variables",
+                &self.access_variables.keys().collect::<Vec<_>>(),
+            )
+            .field("token", &REDACTED_PASSWORD)
+            .field("connect_timeout", &self.connect_timeout)
+            .field("connect_retries", &self.connect_retries)
+            .field("connect_retry_interval", &self.connect_retry_interval)
+            .field("capacity", &self.capacity)
+            .field("query_timeout", &self.query_timeout)
+            .field("transaction_timeout", &self.transaction_timeout)
+            .field("strict", &self.strict)
+            .field("capabilities", &self.capabilities)
+            .field("tls_ca_file", &self.tls_ca_file)
+            .field("tls_client_cert_file", &self.tls_client_cert_file)
+            .field("tls_client_key_file", &self.tls_client_key_file)
+            .field("tls_server_name", &self.tls_server_name)
+            .field("tls_accept_invalid_certs", &self.tls_accept_invalid_certs)
+            .finish()
+    }
 }
 
 impl Default for DbClientConfig<'_> {
@@ -117,7 +334,22 @@ impl Default for DbClientConfig<'_> {
             auth_level: DbAuthLevel::Root,
             username: "root".into(),
             password: "root".into(),
+            access: "".into(),
+            access_variables: BTreeMap::new(),
+            token: "".into(),
+            connect_timeout: Duration::from_secs(10),
+            connect_retries: 0,
+            connect_retry_interval: Duration::from_secs(1),
             capacity: 20,
+            query_timeout: None,
+            transaction_timeout: None,
+            strict: false,
+            capabilities: DbCapabilities::Default,
+            tls_ca_file: None,
+            tls_client_cert_file: None,
+            tls_client_key_file: None,
+            tls_server_name: None,
+            tls_accept_invalid_certs: false,
         }
     }
 }
@@ -153,8 +385,93 @@ impl<'a> DbClientConfig<'a> {
         self
     }
 
+    pub fn with_access(mut self, access: impl Into<Cow<'a, str>>) -> Self {
+        self.access = access.into();
+        self
+    }
+
+    pub fn with_access_variable(
+        mut self,
+        name: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Self {
+        self.access_variables.insert(name.into(), value.into());
+        self
+    }
+
+    pub fn with_token(mut self, token: impl Into<Cow<'a, str>>) -> Self {
+        self.token = token.into();
+        self
+    }
+
+    pub const fn with_connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connect_timeout = connect_timeout;
+        self
+    }
+
+    pub const fn with_connect_retries(mut self, connect_retries: u32) -> Self {
+        self.connect_retries = connect_retries;
+        self
+    }
+
+    pub const fn with_connect_retry_interval(mut self, connect_retry_interval: Duration) -> Self {
+        self.connect_retry_interval = connect_retry_interval;
+        self
+    }
+
     pub const fn with_capacity(mut self, capacity: usize) -> Self {
         self.capacity = capacity;
         self
     }
+
+    pub const fn with_query_timeout(mut self, query_timeout: Option<Duration>) -> Self {
+        self.query_timeout = query_timeout;
+        self
+    }
+
+    pub const fn with_transaction_timeout(mut self, transaction_timeout: Option<Duration>) -> Self {
+        self.transaction_timeout = transaction_timeout;
+        self
+    }
+
+    pub const fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    pub const fn with_capabilities(mut self, capabilities: DbCapabilities) -> Self {
+        self.capabilities = capabilities;
+        self
+    }
+
+    pub fn with_tls_ca_file(mut self, tls_ca_file: impl Into<Cow<'a, Path>>) -> Self {
+        self.tls_ca_file = Some(tls_ca_file.into());
+        self
+    }
+
+    pub fn with_tls_client_cert_file(
+        mut self,
+        tls_client_cert_file: impl Into<Cow<'a, Path>>,
+    ) -> Self {
+        self.tls_client_cert_file = Some(tls_client_cert_file.into());
+        self
+    }
+
+    pub fn with_tls_client_key_file(
+        mut self,
+        tls_client_key_file: impl Into<Cow<'a, Path>>,
+    ) -> Self {
+        self.tls_client_key_file = Some(tls_client_key_file.into());
+        self
+    }
+
+    pub fn with_tls_server_name(mut self, tls_server_name: impl Into<Cow<'a, str>>) -> Self {
+        self.tls_server_name = Some(tls_server_name.into());
+        self
+    }
+
+    pub const fn with_tls_accept_invalid_certs(mut self, tls_accept_invalid_certs: bool) -> Self {
+        self.tls_accept_invalid_certs = tls_accept_invalid_certs;
+        self
+    }
 }
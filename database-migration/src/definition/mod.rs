@@ -18,6 +18,7 @@ pub trait ParseMigration {
 pub const SCRIPT_FILE_EXTENSION: &str = ".surql";
 pub const UP_SCRIPT_FILE_EXTENSION: &str = ".up.surql";
 pub const DOWN_SCRIPT_FILE_EXTENSION: &str = ".down.surql";
+pub const BASELINE_SCRIPT_FILE_EXTENSION: &str = ".baseline.surql";
 
 fn parse_migration(path: &Path, filename: &str) -> Result<Migration, DefinitionError> {
     if !filename.ends_with(SCRIPT_FILE_EXTENSION) {
@@ -25,13 +26,22 @@ fn parse_migration(path: &Path, filename: &str) -> Result<Migration, DefinitionE
     }
     let up = filename.ends_with(UP_SCRIPT_FILE_EXTENSION);
     let down = filename.ends_with(DOWN_SCRIPT_FILE_EXTENSION);
-    let (kind, ext_len) = match (up, down) {
-        (false, false) => (MigrationKind::Up, SCRIPT_FILE_EXTENSION.len()),
-        (true, false) => (MigrationKind::Up, UP_SCRIPT_FILE_EXTENSION.len()),
-        (false, true) => (MigrationKind::Down, DOWN_SCRIPT_FILE_EXTENSION.len()),
-        (true, true) => return Err(DefinitionError::AmbiguousDirection),
+    let baseline = filename.ends_with(BASELINE_SCRIPT_FILE_EXTENSION);
+    let (kind, ext_len) = match (up, down, baseline) {
+        (false, false, false) => (MigrationKind::Up, SCRIPT_FILE_EXTENSION.len()),
+        (true, false, false) => (MigrationKind::Up, UP_SCRIPT_FILE_EXTENSION.len()),
+        (false, true, false) => (MigrationKind::Down, DOWN_SCRIPT_FILE_EXTENSION.len()),
+        (false, false, true) => (
+            MigrationKind::Baseline,
+            BASELINE_SCRIPT_FILE_EXTENSION.len(),
+        ),
+        _ => return Err(DefinitionError::AmbiguousDirection),
     };
-    if filename.contains(".up.") && filename.contains(".down.") {
+    let direction_markers = [".up.", ".down.", ".baseline."]
+        .into_iter()
+        .filter(|marker| filename.contains(marker))
+        .count();
+    if direction_markers > 1 {
         return Err(DefinitionError::AmbiguousDirection);
     }
     let len = filename.len();
@@ -130,7 +140,7 @@ impl GetFilename for MigrationFilenameStrategy {
             (MigrationKind::Up, true) => UP_SCRIPT_FILE_EXTENSION,
             (MigrationKind::Up, false) => SCRIPT_FILE_EXTENSION,
             (MigrationKind::Down, _) => DOWN_SCRIPT_FILE_EXTENSION,
-            (MigrationKind::Baseline, _) => panic!("baselines do not have migration scripts"),
+            (MigrationKind::Baseline, _) => BASELINE_SCRIPT_FILE_EXTENSION,
         };
         if title.is_empty() {
             format!("{key}{extension}")
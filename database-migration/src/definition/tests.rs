@@ -2,7 +2,7 @@
 
 use super::*;
 use crate::migration::MigrationKind;
-use crate::proptest_support::{any_direction, any_key, any_title};
+use crate::proptest_support::{any_key, any_migration_kind, any_title};
 use crate::test_dsl::key;
 use asserting::prelude::*;
 use proptest::prelude::*;
@@ -151,6 +151,20 @@ mod path {
         });
     }
 
+    #[test]
+    fn parse_migration_from_file_path_with_baseline_extension() {
+        let path = Path::new("migrations/20250103_140830_squashed_schema.baseline.surql");
+
+        let migration = path.parse_migration();
+
+        assert_that!(migration).ok().is_equal_to(Migration {
+            key: key("20250103_140830"),
+            title: "squashed schema".into(),
+            kind: MigrationKind::Baseline,
+            script_path: path.into(),
+        });
+    }
+
     #[test]
     fn parse_migration_from_filename_only() {
         let path = Path::new("20250103_140830_define_some_table.surql");
@@ -214,6 +228,17 @@ mod path {
             .is_equal_to(DefinitionError::AmbiguousDirection);
     }
 
+    #[test]
+    fn parse_migration_from_file_path_with_up_and_baseline() {
+        let path = Path::new("migrations/20250103_140830_define_some_table.up.baseline.surql");
+
+        let migration = path.parse_migration();
+
+        assert_that!(migration)
+            .err()
+            .is_equal_to(DefinitionError::AmbiguousDirection);
+    }
+
     #[test]
     fn parse_migration_from_file_path_without_a_filename() {
         let path = Path::new("migrations");
@@ -273,7 +298,6 @@ mod migration_filename_strategy {
     }
 
     #[test]
-    #[should_panic(expected = "baselines do not have migration scripts")]
     fn get_filename_with_default_strategy_for_baseline_migration() {
         let filename_strategy = MigrationFilenameStrategy::default().with_up_postfix(false);
 
@@ -283,7 +307,10 @@ mod migration_filename_strategy {
             kind: MigrationKind::Baseline,
         };
 
-        _ = filename_strategy.get_filename(&migration);
+        let filename = filename_strategy.get_filename(&migration);
+
+        assert_that!(filename)
+            .is_equal_to("20250114_092042_create_some_table.baseline.surql".to_string());
     }
 
     #[test]
@@ -323,7 +350,7 @@ proptest! {
     fn any_filename_created_by_the_strategy_can_be_parsed_as_migration(
         key in any_key(),
         title in any_title(),
-        direction in any_direction(),
+        direction in any_migration_kind(),
         up_postfix in any::<bool>(),
     ) {
         let filename_strategy = MigrationFilenameStrategy {
@@ -1,5 +1,8 @@
 use crate::migration::ProblematicMigration;
-use indexmap::IndexMap;
+use crate::script::ScriptError;
+use std::error::Error as StdError;
+use std::fmt::{Display, Formatter};
+use std::sync::Arc;
 
 #[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
 pub enum Error {
@@ -11,10 +14,10 @@ pub enum Error {
     CreatingMigrationsFolder(String),
     #[error("failed to create script file: {0}")]
     CreatingScriptFile(String),
-    #[error("database query failed: {0}")]
-    DbQuery(String),
-    #[error("db script execution failed: {0:?}")]
-    DbScript(IndexMap<usize, String>),
+    #[error("database operation failed: {0}")]
+    Db(#[source] DatabaseError),
+    #[error("db script execution failed: {0}")]
+    DbScript(ScriptError),
     #[error(transparent)]
     Definition(#[from] DefinitionError),
     #[error("failed to insert the migration execution for key={0} into the migrations table")]
@@ -23,14 +26,130 @@ pub enum Error {
     ExecutionNotDeleted(String),
     #[error("failed to query table definitions: {0}")]
     FetchingTableDefinitions(String),
+    #[error("the database is not migrated up to migration {0}")]
+    NotMigratedUpTo(String),
     #[error("migrations out of order: {0:?}")]
     OutOfOrder(Vec<ProblematicMigration>),
     #[error("failed reading migration files: {0}")]
     ReadingMigrationFile(String),
+    #[error("failed reading schema definition: {0}")]
+    ReadingSchemaDefinition(String),
+    #[error("failed reading migration template: {0}")]
+    ReadingTemplate(String),
     #[error("failed scanning migration directory: {0}")]
     ScanningMigrationDirectory(String),
+    #[error("the scratch database for the desired schema is not empty: {0}")]
+    ScratchDatabaseNotEmpty(String),
+    #[error("migration template not found: {0}")]
+    TemplateNotFound(String),
 }
 
+impl Error {
+    /// Returns the category of this error.
+    pub const fn category(&self) -> ErrorCategory {
+        match self {
+            Self::Configuration(_) | Self::TemplateNotFound(_) => ErrorCategory::Configuration,
+            Self::CreatingMigrationsFolder(_)
+            | Self::CreatingScriptFile(_)
+            | Self::Definition(_)
+            | Self::ReadingMigrationFile(_)
+            | Self::ReadingSchemaDefinition(_)
+            | Self::ReadingTemplate(_)
+            | Self::ScanningMigrationDirectory(_) => ErrorCategory::Filesystem,
+            Self::Db(db_error) => db_error.category(),
+            Self::DbScript(_) => ErrorCategory::Script,
+            Self::FetchingTableDefinitions(_) => ErrorCategory::Query,
+            Self::ChangedAfterExecution(_)
+            | Self::ExecutionNotDeleted(_)
+            | Self::ExecutionNotInserted(_)
+            | Self::NotMigratedUpTo(_)
+            | Self::OutOfOrder(_)
+            | Self::ScratchDatabaseNotEmpty(_) => ErrorCategory::Migration,
+        }
+    }
+}
+
+/// The category of an error.
+///
+/// Categories allow to react on groups of errors without inspecting the
+/// error messages, e.g. to retry an operation after a connection loss.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorCategory {
+    /// Signing in to the database failed or the session has expired.
+    Authentication,
+    /// The configuration is invalid or incomplete.
+    Configuration,
+    /// The database server is not reachable or the connection has been lost.
+    Connection,
+    /// A statement violated a constraint, e.g. a unique index or a field type.
+    Constraint,
+    /// Reading or writing files failed.
+    Filesystem,
+    /// The state of the migrations does not allow the requested operation.
+    Migration,
+    /// The database user is not allowed to perform an operation.
+    Permission,
+    /// Any other failure reported by the database.
+    Query,
+    /// A statement of a migration script failed.
+    Script,
+}
+
+/// An error reported by the database.
+///
+/// The original error is preserved as the source of this error.
+#[derive(Debug, Clone)]
+pub struct DatabaseError {
+    category: ErrorCategory,
+    message: String,
+    source: Option<Arc<dyn StdError + Send + Sync>>,
+}
+
+impl DatabaseError {
+    pub fn new(category: ErrorCategory, message: impl Into<String>) -> Self {
+        Self {
+            category,
+            message: message.into(),
+            source: None,
+        }
+    }
+
+    #[must_use]
+    pub fn with_source(mut self, source: impl StdError + Send + Sync + 'static) -> Self {
+        self.source = Some(Arc::new(source));
+        self
+    }
+
+    pub const fn category(&self) -> ErrorCategory {
+        self.category
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for DatabaseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl StdError for DatabaseError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        let source: &(dyn StdError + 'static) = self.source.as_deref()?;
+        Some(source)
+    }
+}
+
+impl PartialEq for DatabaseError {
+    fn eq(&self, other: &Self) -> bool {
+        self.category == other.category && self.message == other.message
+    }
+}
+
+impl Eq for DatabaseError {}
+
 #[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
 pub enum DefinitionError {
     #[error("direction is ambiguous")]
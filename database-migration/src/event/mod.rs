@@ -0,0 +1,125 @@
+use crate::error::Error;
+use crate::migration::{Execution, Migration, ProblematicMigration, Reversion};
+use std::fmt::{Display, Formatter};
+
+/// The operation performed by a run of the migration runner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Operation {
+    Migrate,
+    Revert,
+    Verify,
+}
+
+impl Operation {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Migrate => "migrate",
+            Self::Revert => "revert",
+            Self::Verify => "verify",
+        }
+    }
+}
+
+impl Display for Operation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// The namespace and database an operation is performed on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Target<'a> {
+    pub namespace: &'a str,
+    pub database: &'a str,
+}
+
+impl Display for Target<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.namespace, self.database)
+    }
+}
+
+/// An event emitted by the migration runner while performing an operation.
+///
+/// Each event carries the target the operation is performed on, so that the
+/// events of several databases migrated concurrently can be told apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MigrationEvent<'a> {
+    /// A run of the given operation has been started.
+    RunStarted {
+        target: Target<'a>,
+        operation: Operation,
+    },
+    /// The given migration is about to be applied or reverted.
+    MigrationStarted {
+        target: Target<'a>,
+        migration: &'a Migration,
+    },
+    /// The given migration has been applied successfully.
+    MigrationApplied {
+        target: Target<'a>,
+        migration: &'a Migration,
+        execution: &'a Execution,
+    },
+    /// Applying or reverting the given migration failed.
+    MigrationFailed {
+        target: Target<'a>,
+        migration: &'a Migration,
+        error: &'a Error,
+    },
+    /// The given backward migration has been applied successfully.
+    MigrationReverted {
+        target: Target<'a>,
+        migration: &'a Migration,
+        reversion: &'a Reversion,
+    },
+    /// A problem with a migration has been found while verifying the
+    /// migrations.
+    ProblemFound {
+        target: Target<'a>,
+        problem: &'a ProblematicMigration,
+    },
+    /// A run of the given operation has been finished. If the run failed,
+    /// the error is given.
+    RunFinished {
+        target: Target<'a>,
+        operation: Operation,
+        error: Option<&'a Error>,
+    },
+}
+
+impl<'a> MigrationEvent<'a> {
+    /// Returns the target of the operation this event belongs to.
+    pub const fn target(&self) -> Target<'a> {
+        match self {
+            Self::RunStarted { target, .. }
+            | Self::MigrationStarted { target, .. }
+            | Self::MigrationApplied { target, .. }
+            | Self::MigrationFailed { target, .. }
+            | Self::MigrationReverted { target, .. }
+            | Self::ProblemFound { target, .. }
+            | Self::RunFinished { target, .. } => *target,
+        }
+    }
+}
+
+/// A listener that gets notified about the progress of the migration runner.
+///
+/// Listeners are registered with the migration runner and are notified in
+/// the order they have been registered. Any function or closure that takes a
+/// [`MigrationEvent`] as argument can be used as listener.
+pub trait MigrationListener: Send + Sync {
+    fn on_event(&self, event: &MigrationEvent<'_>);
+}
+
+impl<F> MigrationListener for F
+where
+    F: Fn(&MigrationEvent<'_>) + Send + Sync,
+{
+    fn on_event(&self, event: &MigrationEvent<'_>) {
+        self(event);
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,61 @@
+use super::*;
+use asserting::prelude::*;
+use std::sync::Mutex;
+
+#[test]
+fn display_operation() {
+    assert_that!(Operation::Migrate.to_string()).is_equal_to("migrate");
+    assert_that!(Operation::Revert.to_string()).is_equal_to("revert");
+    assert_that!(Operation::Verify.to_string()).is_equal_to("verify");
+}
+
+const TARGET: Target<'static> = Target {
+    namespace: "tenants",
+    database: "shop",
+};
+
+#[test]
+fn display_target() {
+    assert_that!(TARGET.to_string()).is_equal_to("tenants/shop");
+}
+
+#[test]
+fn target_of_migration_event() {
+    let event = MigrationEvent::RunFinished {
+        target: TARGET,
+        operation: Operation::Migrate,
+        error: None,
+    };
+
+    assert_that!(event.target()).is_equal_to(TARGET);
+}
+
+#[test]
+fn closure_as_migration_listener() {
+    let operations = Mutex::new(Vec::new());
+    let listener = |event: &MigrationEvent<'_>| {
+        if let MigrationEvent::RunStarted { operation, .. } = event {
+            operations
+                .lock()
+                .unwrap_or_else(|err| panic!("lock poisoned: {err}"))
+                .push(*operation);
+        }
+    };
+
+    listener.on_event(&MigrationEvent::RunStarted {
+        target: TARGET,
+        operation: Operation::Revert,
+    });
+    listener.on_event(&MigrationEvent::RunFinished {
+        target: TARGET,
+        operation: Operation::Revert,
+        error: None,
+    });
+
+    assert_that!(
+        operations
+            .into_inner()
+            .unwrap_or_else(|err| panic!("lock poisoned: {err}"))
+    )
+    .is_equal_to(vec![Operation::Revert]);
+}
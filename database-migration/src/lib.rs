@@ -1,13 +1,18 @@
 #![doc(html_root_url = "https://docs.rs/database-migration/0.2.0")]
 
 pub mod action;
+pub mod callback;
 pub mod checksum;
 pub mod config;
 pub mod definition;
 pub mod error;
+pub mod event;
 pub mod migration;
 pub mod repository;
 pub mod result;
+pub mod scope;
+pub mod script;
+pub mod targets;
 
 #[cfg(any(test, feature = "proptest-support"))]
 pub mod proptest_support;
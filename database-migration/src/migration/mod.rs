@@ -1,11 +1,12 @@
 use crate::checksum::Checksum;
 use chrono::{DateTime, NaiveDateTime, Utc};
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
 use std::path::PathBuf;
 use std::time::Duration;
 
-#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum MigrationKind {
     Baseline,
     Up,
@@ -21,6 +22,10 @@ impl MigrationKind {
         }
     }
 
+    pub const fn is_baseline(&self) -> bool {
+        matches!(self, Self::Baseline)
+    }
+
     pub fn is_backward(&self) -> bool {
         *self == Self::Down
     }
@@ -68,6 +73,7 @@ pub struct ScriptContent {
 pub struct ApplicableMigration {
     pub key: NaiveDateTime,
     pub kind: MigrationKind,
+    pub script_path: PathBuf,
     pub script_content: String,
     pub checksum: Checksum,
 }
@@ -90,6 +96,61 @@ pub struct Reversion {
     pub execution_time: Duration,
 }
 
+/// A failed attempt to apply a migration.
+///
+/// The errors are the error messages keyed by the number of the failed
+/// statement within the migration script.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FailedAttempt {
+    pub key: NaiveDateTime,
+    pub title: String,
+    pub kind: MigrationKind,
+    pub script_path: PathBuf,
+    pub checksum: Checksum,
+    pub attempted_by: String,
+    pub attempted_at: DateTime<Utc>,
+    pub errors: IndexMap<usize, String>,
+}
+
+/// The action performed on a migration as recorded in the migration history.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HistoryAction {
+    Applied,
+    Reverted,
+}
+
+impl HistoryAction {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Applied => "applied",
+            Self::Reverted => "reverted",
+        }
+    }
+}
+
+impl Display for HistoryAction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// An event in the timeline of migrations applied to and reverted on a
+/// database.
+///
+/// For reverted migrations the kind is [`MigrationKind::Down`] and the
+/// checksum is the one of the reverted execution.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistoryEvent {
+    pub key: NaiveDateTime,
+    pub title: String,
+    pub kind: MigrationKind,
+    pub action: HistoryAction,
+    pub checksum: Checksum,
+    pub performed_by: String,
+    pub performed_at: DateTime<Utc>,
+    pub execution_time: Duration,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ProblematicMigration {
     pub key: NaiveDateTime,
@@ -107,6 +168,24 @@ pub enum Problem {
     OutOfOrder {
         last_applied_key: NaiveDateTime,
     },
+    Failed {
+        attempted_at: DateTime<Utc>,
+        errors: IndexMap<usize, String>,
+    },
+}
+
+impl From<FailedAttempt> for ProblematicMigration {
+    fn from(attempt: FailedAttempt) -> Self {
+        Self {
+            key: attempt.key,
+            kind: attempt.kind,
+            script_path: attempt.script_path,
+            problem: Problem::Failed {
+                attempted_at: attempt.attempted_at,
+                errors: attempt.errors,
+            },
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
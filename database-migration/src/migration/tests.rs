@@ -29,3 +29,44 @@ mod migration_kind {
         assert_that!(MigrationKind::Down.is_backward()).is_true();
     }
 }
+
+mod history_action {
+    use super::*;
+
+    #[test]
+    fn display_history_action() {
+        assert_that!(HistoryAction::Applied.to_string()).is_equal_to("applied".to_string());
+        assert_that!(HistoryAction::Reverted.to_string()).is_equal_to("reverted".to_string());
+    }
+}
+
+mod failed_attempt {
+    use super::*;
+    use crate::test_dsl::{datetime, key};
+
+    #[test]
+    fn failed_attempt_into_problematic_migration() {
+        let attempt = FailedAttempt {
+            key: key("20250103_140520"),
+            title: "define quote table".into(),
+            kind: MigrationKind::Up,
+            script_path: "migrations/20250103_140520_define_quote_table.up.surql".into(),
+            checksum: Checksum(1_234_567),
+            attempted_by: "some.user".into(),
+            attempted_at: datetime("2025-01-20T09:10:19Z"),
+            errors: IndexMap::from_iter([(1, "some error".to_string())]),
+        };
+
+        let problematic_migration = ProblematicMigration::from(attempt);
+
+        assert_that!(problematic_migration).is_equal_to(ProblematicMigration {
+            key: key("20250103_140520"),
+            kind: MigrationKind::Up,
+            script_path: "migrations/20250103_140520_define_quote_table.up.surql".into(),
+            problem: Problem::Failed {
+                attempted_at: datetime("2025-01-20T09:10:19Z"),
+                errors: IndexMap::from_iter([(1, "some error".to_string())]),
+            },
+        });
+    }
+}
@@ -1,3 +1,4 @@
+use crate::callback::{Callback, CallbackScript};
 use crate::error::Error;
 use crate::migration::{Migration, NewMigration, ScriptContent};
 
@@ -21,6 +22,20 @@ pub trait ReadScriptContent {
     }
 }
 
+pub trait ReadCallbackScript {
+    /// Reads the script for the given callback or returns `None` if no script
+    /// is defined for the callback.
+    fn read_callback_script(&self, callback: Callback) -> Result<Option<CallbackScript>, Error>;
+}
+
 pub trait CreateNewMigration {
-    fn create_new_migration(&self, new_migration: NewMigration) -> Result<Migration, Error>;
+    fn create_new_migration(&self, new_migration: NewMigration) -> Result<Migration, Error> {
+        self.create_new_migration_with_content(new_migration, "")
+    }
+
+    fn create_new_migration_with_content(
+        &self,
+        new_migration: NewMigration,
+        content: &str,
+    ) -> Result<Migration, Error>;
 }
@@ -1,4 +1,4 @@
-use crate::migration::ProblematicMigration;
+use crate::migration::{Migration, ProblematicMigration};
 use chrono::NaiveDateTime;
 
 /// Result of a migration action.
@@ -35,3 +35,21 @@ pub enum Verified {
     /// No migrations found in the migrations folder.
     NoMigrationsFound,
 }
+
+/// Result of a diff action.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Diffed {
+    /// The schemas of both databases are equal. No migration has been created.
+    NoDifferences,
+    /// Created a new forward migration and the related backward migration.
+    Created { up: Migration, down: Migration },
+}
+
+/// Result of a repair action.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Repaired {
+    /// No failed migration attempts found. Nothing has been repaired.
+    Nothing,
+    /// Removed the failed attempts of the migrations with the given keys.
+    Cleared(Vec<NaiveDateTime>),
+}
@@ -0,0 +1,88 @@
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter};
+
+/// The name of the database that tracks the migrations of the root and the
+/// namespace scope.
+///
+/// `SurrealDB` stores tables in a database only. The migrations of the root
+/// scope are tracked in this database within the namespace of the same name.
+/// The migrations of the namespace scope are tracked in this database within
+/// the migrated namespace.
+pub const TRACKING_DATABASE: &str = "surrealdb_migrate";
+
+/// The level of a `SurrealDB` instance at which a set of migrations is applied.
+///
+/// Migrations of different scopes are kept in separate folders and are
+/// tracked in separate migrations tables. They are applied in the order root,
+/// namespace, database and reverted in the opposite order.
+///
+/// The migrations of each scope are tracked at the level they apply to, so
+/// that migrating further databases does not apply them again. See
+/// [`MigrationScope::tracking_location`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MigrationScope {
+    /// Root level, e.g. `DEFINE NAMESPACE` or `DEFINE USER ... ON ROOT`.
+    Root,
+    /// Namespace level, e.g. `DEFINE DATABASE` or `DEFINE USER ... ON NAMESPACE`.
+    Namespace,
+    /// Database level, e.g. `DEFINE TABLE` or `DEFINE FIELD`.
+    Database,
+}
+
+impl MigrationScope {
+    /// Returns all scopes in the order in which their migrations are applied.
+    pub const fn all() -> [Self; 3] {
+        [Self::Root, Self::Namespace, Self::Database]
+    }
+
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Root => "root",
+            Self::Namespace => "namespace",
+            Self::Database => "database",
+        }
+    }
+
+    /// Returns the name of the table that tracks the migrations of this scope.
+    ///
+    /// The migrations of the database scope are tracked in the given
+    /// migrations table. For the other scopes the name of the scope is
+    /// appended to the name of the migrations table, e.g. `migrations_root`.
+    pub fn migrations_table<'a>(&self, migrations_table: &'a str) -> Cow<'a, str> {
+        match self {
+            Self::Database => Cow::Borrowed(migrations_table),
+            Self::Root | Self::Namespace => {
+                Cow::Owned(format!("{migrations_table}_{}", self.as_str()))
+            },
+        }
+    }
+
+    /// Returns the namespace and the database in which the migrations of this
+    /// scope are tracked when migrating the given namespace and database.
+    ///
+    /// The migrations of the database scope are tracked in the migrated
+    /// database itself. The migrations of the other scopes are tracked in the
+    /// [`TRACKING_DATABASE`], which is located in the migrated namespace for
+    /// the namespace scope and in the namespace [`TRACKING_DATABASE`] for the
+    /// root scope.
+    pub const fn tracking_location<'a>(
+        &self,
+        namespace: &'a str,
+        database: &'a str,
+    ) -> (&'a str, &'a str) {
+        match self {
+            Self::Root => (TRACKING_DATABASE, TRACKING_DATABASE),
+            Self::Namespace => (namespace, TRACKING_DATABASE),
+            Self::Database => (namespace, database),
+        }
+    }
+}
+
+impl Display for MigrationScope {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,56 @@
+use super::*;
+use asserting::prelude::*;
+
+#[test]
+fn scopes_are_ordered_from_root_to_database() {
+    let mut scopes = vec![
+        MigrationScope::Database,
+        MigrationScope::Root,
+        MigrationScope::Namespace,
+    ];
+
+    scopes.sort();
+
+    assert_that!(scopes).is_equal_to(MigrationScope::all().to_vec());
+}
+
+#[test]
+fn migrations_table_of_root_scope() {
+    assert_that!(MigrationScope::Root.migrations_table("migrations"))
+        .is_equal_to(Cow::Borrowed("migrations_root"));
+}
+
+#[test]
+fn migrations_table_of_namespace_scope() {
+    assert_that!(MigrationScope::Namespace.migrations_table("executions"))
+        .is_equal_to(Cow::Borrowed("executions_namespace"));
+}
+
+#[test]
+fn migrations_table_of_database_scope() {
+    assert_that!(MigrationScope::Database.migrations_table("migrations"))
+        .is_equal_to(Cow::Borrowed("migrations"));
+}
+
+#[test]
+fn root_scope_is_tracked_in_the_tracking_database_of_the_tracking_namespace() {
+    assert_that!(MigrationScope::Root.tracking_location("tenants", "shop"))
+        .is_equal_to(("surrealdb_migrate", "surrealdb_migrate"));
+}
+
+#[test]
+fn namespace_scope_is_tracked_in_the_tracking_database_of_the_migrated_namespace() {
+    assert_that!(MigrationScope::Namespace.tracking_location("tenants", "shop"))
+        .is_equal_to(("tenants", "surrealdb_migrate"));
+}
+
+#[test]
+fn database_scope_is_tracked_in_the_migrated_database() {
+    assert_that!(MigrationScope::Database.tracking_location("tenants", "shop"))
+        .is_equal_to(("tenants", "shop"));
+}
+
+#[test]
+fn display_migration_scope() {
+    assert_that!(MigrationScope::Namespace.to_string()).is_equal_to("namespace");
+}
@@ -0,0 +1,237 @@
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+
+/// A position within a migration script.
+///
+/// Lines and columns are counted starting with 1. Columns are counted in
+/// characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// A single statement of a migration script together with its location in
+/// the script.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Statement {
+    /// The text of the statement as written in the script without the
+    /// terminating semicolon.
+    pub text: String,
+    /// Position of the first character of the statement.
+    pub start: Position,
+    /// Position of the last character of the statement.
+    pub end: Position,
+}
+
+/// Splits the content of a migration script into its statements.
+///
+/// Statements are separated by semicolons. Semicolons within strings,
+/// identifiers, comments and blocks (`{ ... }`) do not terminate a statement.
+/// Comments and whitespace between statements are not part of any statement.
+pub fn split_statements(script: &str) -> Vec<Statement> {
+    StatementSplitter::new(script).split()
+}
+
+/// An error reported by the database for one statement of a migration script.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatementError {
+    /// Number of the failed statement within the script, starting with 1.
+    pub number: usize,
+    /// The failed statement or `None` if the error could not be related to a
+    /// statement of the script.
+    pub statement: Option<Statement>,
+    /// The error message as reported by the database.
+    pub message: String,
+}
+
+/// The errors that occurred while executing a migration script.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScriptError {
+    pub script_path: PathBuf,
+    pub errors: Vec<StatementError>,
+}
+
+impl ScriptError {
+    /// Creates a new `ScriptError` for the given script.
+    ///
+    /// The errors are given as pairs of the zero based index of the failed
+    /// statement and the error message. The statements are located by
+    /// splitting the script content.
+    pub fn new(
+        script_path: impl Into<PathBuf>,
+        script_content: &str,
+        errors: impl IntoIterator<Item = (usize, String)>,
+    ) -> Self {
+        let mut statements = split_statements(script_content)
+            .into_iter()
+            .map(Some)
+            .collect::<Vec<_>>();
+        let mut errors = errors
+            .into_iter()
+            .map(|(index, message)| StatementError {
+                number: index + 1,
+                statement: statements.get_mut(index).and_then(Option::take),
+                message,
+            })
+            .collect::<Vec<_>>();
+        errors.sort_by_key(|error| error.number);
+        Self {
+            script_path: script_path.into(),
+            errors,
+        }
+    }
+}
+
+impl Display for ScriptError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.script_path.display())?;
+        for error in &self.errors {
+            write!(f, "\n  statement {}", error.number)?;
+            if let Some(statement) = &error.statement {
+                write!(f, " at {}-{}", statement.start, statement.end)?;
+            }
+            write!(f, ": {}", error.message)?;
+        }
+        Ok(())
+    }
+}
+
+struct StatementSplitter<'a> {
+    script: &'a str,
+    chars: std::iter::Peekable<std::str::CharIndices<'a>>,
+    position: Position,
+}
+
+impl<'a> StatementSplitter<'a> {
+    fn new(script: &'a str) -> Self {
+        Self {
+            script,
+            chars: script.char_indices().peekable(),
+            position: Position { line: 1, column: 1 },
+        }
+    }
+
+    fn next_char(&mut self) -> Option<(usize, char, Position)> {
+        let (offset, chr) = self.chars.next()?;
+        let position = self.position;
+        if chr == '\n' {
+            self.position.line += 1;
+            self.position.column = 1;
+        } else {
+            self.position.column += 1;
+        }
+        Some((offset, chr, position))
+    }
+
+    fn peek_char(&mut self) -> Option<char> {
+        self.chars.peek().map(|(_, chr)| *chr)
+    }
+
+    fn skip_line_comment(&mut self) {
+        while let Some(chr) = self.peek_char() {
+            if chr == '\n' {
+                break;
+            }
+            self.next_char();
+        }
+    }
+
+    fn skip_block_comment(&mut self) {
+        while let Some((_, chr, _)) = self.next_char() {
+            if chr == '*' && self.peek_char() == Some('/') {
+                self.next_char();
+                break;
+            }
+        }
+    }
+
+    fn skip_quoted(&mut self, closing: char) {
+        while let Some((_, chr, _)) = self.next_char() {
+            if chr == '\\' {
+                self.next_char();
+            } else if chr == closing {
+                break;
+            }
+        }
+    }
+
+    /// Skips a comment if the given character starts one and returns whether
+    /// a comment has been skipped.
+    fn skip_comment(&mut self, chr: char) -> bool {
+        match (chr, self.peek_char()) {
+            ('#', _) | ('-', Some('-')) | ('/', Some('/')) => {
+                self.skip_line_comment();
+                true
+            },
+            ('/', Some('*')) => {
+                self.next_char();
+                self.skip_block_comment();
+                true
+            },
+            _ => false,
+        }
+    }
+
+    fn split(mut self) -> Vec<Statement> {
+        let mut statements = Vec::new();
+        let mut current: Option<(usize, Position)> = None;
+        let mut last: Option<(usize, Position)> = None;
+        let mut depth = 0_usize;
+
+        while let Some((offset, chr, position)) = self.next_char() {
+            if self.skip_comment(chr) {
+                continue;
+            }
+            if chr == ';' && depth == 0 {
+                if let (Some(start), Some(end)) = (current.take(), last.take()) {
+                    statements.push(self.statement(start, end));
+                }
+                continue;
+            }
+            if chr.is_whitespace() {
+                continue;
+            }
+            match chr {
+                '\'' | '"' | '`' => self.skip_quoted(chr),
+                '⟨' => self.skip_quoted('⟩'),
+                '{' | '(' | '[' => depth += 1,
+                '}' | ')' | ']' => depth = depth.saturating_sub(1),
+                _ => {},
+            }
+            if current.is_none() {
+                current = Some((offset, position));
+            }
+            let end_offset = self
+                .chars
+                .peek()
+                .map_or(self.script.len(), |(next, _)| *next);
+            let end_position = Position {
+                line: self.position.line,
+                column: self.position.column - 1,
+            };
+            last = Some((end_offset, end_position));
+        }
+        if let (Some(start), Some(end)) = (current, last) {
+            statements.push(self.statement(start, end));
+        }
+        statements
+    }
+
+    fn statement(&self, start: (usize, Position), end: (usize, Position)) -> Statement {
+        Statement {
+            text: self.script[start.0..end.0].to_string(),
+            start: start.1,
+            end: end.1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
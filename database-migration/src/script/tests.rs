@@ -0,0 +1,182 @@
+use super::*;
+use asserting::prelude::*;
+
+fn pos(line: usize, column: usize) -> Position {
+    Position { line, column }
+}
+
+mod split_statements {
+    use super::*;
+
+    #[test]
+    fn split_empty_script() {
+        let statements = split_statements("");
+
+        assert_that!(statements).is_empty();
+    }
+
+    #[test]
+    fn split_script_with_comments_only() {
+        let statements = split_statements("-- first comment\n# second comment\n/* third */\n");
+
+        assert_that!(statements).is_empty();
+    }
+
+    #[test]
+    fn split_script_with_one_statement_per_line() {
+        let statements = split_statements(
+            "DEFINE TABLE quote SCHEMAFULL;\nDEFINE FIELD text ON quote TYPE string;\n",
+        );
+
+        assert_that!(statements).is_equal_to(vec![
+            Statement {
+                text: "DEFINE TABLE quote SCHEMAFULL".into(),
+                start: pos(1, 1),
+                end: pos(1, 29),
+            },
+            Statement {
+                text: "DEFINE FIELD text ON quote TYPE string".into(),
+                start: pos(2, 1),
+                end: pos(2, 38),
+            },
+        ]);
+    }
+
+    #[test]
+    fn split_statement_spanning_several_lines() {
+        let statements = split_statements(
+            "\n  DEFINE TABLE quote SCHEMALESS\n    PERMISSIONS FOR select FULL;\n",
+        );
+
+        assert_that!(statements).is_equal_to(vec![Statement {
+            text: "DEFINE TABLE quote SCHEMALESS\n    PERMISSIONS FOR select FULL".into(),
+            start: pos(2, 3),
+            end: pos(3, 31),
+        }]);
+    }
+
+    #[test]
+    fn split_last_statement_without_semicolon() {
+        let statements = split_statements("RETURN 1; RETURN 2");
+
+        assert_that!(statements).is_equal_to(vec![
+            Statement {
+                text: "RETURN 1".into(),
+                start: pos(1, 1),
+                end: pos(1, 8),
+            },
+            Statement {
+                text: "RETURN 2".into(),
+                start: pos(1, 11),
+                end: pos(1, 18),
+            },
+        ]);
+    }
+
+    #[test]
+    fn semicolons_in_strings_and_identifiers_do_not_terminate_a_statement() {
+        let statements = split_statements(
+            r#"CREATE quote SET text = "a; b", author = 'c; \'d'; CREATE `we;ird`:⟨x;y⟩;"#,
+        );
+
+        assert_that!(
+            statements
+                .iter()
+                .map(|stmt| stmt.text.as_str())
+                .collect::<Vec<_>>()
+        )
+        .is_equal_to(vec![
+            r#"CREATE quote SET text = "a; b", author = 'c; \'d'"#,
+            "CREATE `we;ird`:⟨x;y⟩",
+        ]);
+    }
+
+    #[test]
+    fn semicolons_in_blocks_do_not_terminate_a_statement() {
+        let statements = split_statements(
+            "DEFINE FUNCTION fn::greet($name: string) {\n    LET $greeting = 'Hello';\n    RETURN $greeting + $name;\n};\nRETURN fn::greet('Tom');",
+        );
+
+        assert_that!(&statements).has_length(2);
+        assert_that!(statements[0].start).is_equal_to(pos(1, 1));
+        assert_that!(statements[0].end).is_equal_to(pos(4, 1));
+        assert_that!(statements[1].start).is_equal_to(pos(5, 1));
+    }
+
+    #[test]
+    fn comments_between_and_after_statements_are_not_part_of_a_statement() {
+        let statements = split_statements(
+            "-- define the table\nDEFINE TABLE quote; // trailing comment; still a comment\n/* a; b */ REMOVE TABLE quote; # done;",
+        );
+
+        assert_that!(statements).is_equal_to(vec![
+            Statement {
+                text: "DEFINE TABLE quote".into(),
+                start: pos(2, 1),
+                end: pos(2, 18),
+            },
+            Statement {
+                text: "REMOVE TABLE quote".into(),
+                start: pos(3, 12),
+                end: pos(3, 29),
+            },
+        ]);
+    }
+}
+
+mod script_error {
+    use super::*;
+
+    #[test]
+    fn new_script_error_locates_the_failed_statements() {
+        let script_error = ScriptError::new(
+            "migrations/20250103_140520_define_quote_table.surql",
+            "DEFINE TABLE quote;\nTHROW 'boom';\n",
+            [(1, "An error occurred: boom".to_string())],
+        );
+
+        assert_that!(script_error).is_equal_to(ScriptError {
+            script_path: "migrations/20250103_140520_define_quote_table.surql".into(),
+            errors: vec![StatementError {
+                number: 2,
+                statement: Some(Statement {
+                    text: "THROW 'boom'".into(),
+                    start: pos(2, 1),
+                    end: pos(2, 12),
+                }),
+                message: "An error occurred: boom".into(),
+            }],
+        });
+    }
+
+    #[test]
+    fn new_script_error_for_statement_outside_of_the_script() {
+        let script_error = ScriptError::new(
+            "migrations/20250103_140520_define_quote_table.surql",
+            "DEFINE TABLE quote;",
+            [(1, "transaction conflict".to_string())],
+        );
+
+        assert_that!(script_error.errors).is_equal_to(vec![StatementError {
+            number: 2,
+            statement: None,
+            message: "transaction conflict".into(),
+        }]);
+    }
+
+    #[test]
+    fn display_script_error() {
+        let script_error = ScriptError::new(
+            "migrations/20250103_140520_define_quote_table.surql",
+            "DEFINE TABLE quote;\nTHROW 'boom';\n",
+            [
+                (2, "transaction conflict".to_string()),
+                (1, "An error occurred: boom".to_string()),
+            ],
+        );
+
+        assert_that!(script_error.to_string()).is_equal_to(
+            "migrations/20250103_140520_define_quote_table.surql\n  statement 2 at 2:1-2:12: An error occurred: boom\n  statement 3: transaction conflict",
+        );
+    }
+}
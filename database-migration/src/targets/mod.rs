@@ -0,0 +1,187 @@
+use crate::error::Error;
+use crate::scope::TRACKING_DATABASE;
+use regex::Regex;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+/// Default number of target databases that are migrated concurrently.
+pub const DEFAULT_TARGET_CONCURRENCY: usize = 4;
+
+/// Selects the databases within a namespace that a run is applied to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TargetDatabases {
+    /// The databases with the given names.
+    Names(Vec<String>),
+    /// All databases in the namespace whose names match the given pattern.
+    Matching(DatabasePattern),
+    /// All databases in the namespace.
+    All,
+}
+
+impl TargetDatabases {
+    /// Returns whether the databases in the namespace must be enumerated to
+    /// determine the target databases.
+    pub const fn needs_enumeration(&self) -> bool {
+        !matches!(self, Self::Names(_))
+    }
+
+    /// Selects the target databases from the given databases of a namespace.
+    ///
+    /// The [`TRACKING_DATABASE`] is never selected from the given databases,
+    /// as it only holds the bookkeeping of the namespace level migrations.
+    ///
+    /// The selected databases are returned sorted by name without duplicates.
+    pub fn select(&self, databases: impl IntoIterator<Item = String>) -> Vec<String> {
+        let databases = databases
+            .into_iter()
+            .filter(|database| database != TRACKING_DATABASE);
+        let mut selected = match self {
+            Self::Names(names) => names.clone(),
+            Self::Matching(pattern) => databases
+                .filter(|database| pattern.is_match(database))
+                .collect(),
+            Self::All => databases.collect(),
+        };
+        selected.sort_unstable();
+        selected.dedup();
+        selected
+    }
+}
+
+/// A pattern for database names.
+///
+/// The wildcard `*` matches any number of characters and `?` matches exactly
+/// one character. All other characters match themselves.
+#[derive(Debug, Clone)]
+pub struct DatabasePattern {
+    pattern: String,
+    regex: Regex,
+}
+
+impl DatabasePattern {
+    pub fn is_match(&self, database: &str) -> bool {
+        self.regex.is_match(database)
+    }
+}
+
+impl PartialEq for DatabasePattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.pattern == other.pattern
+    }
+}
+
+impl Eq for DatabasePattern {}
+
+impl Display for DatabasePattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.pattern)
+    }
+}
+
+impl FromStr for DatabasePattern {
+    type Err = Error;
+
+    fn from_str(pattern_str: &str) -> Result<Self, Self::Err> {
+        if pattern_str.is_empty() {
+            return Err(Error::Configuration(
+                "the database pattern must not be empty".into(),
+            ));
+        }
+        let mut regex_pattern = String::from("^");
+        for chr in pattern_str.chars() {
+            match chr {
+                '*' => regex_pattern.push_str(".*"),
+                '?' => regex_pattern.push('.'),
+                _ => regex_pattern.push_str(&regex::escape(chr.encode_utf8(&mut [0; 4]))),
+            }
+        }
+        regex_pattern.push('$');
+        Regex::new(&regex_pattern)
+            .map_err(|err| {
+                Error::Configuration(format!("invalid database pattern {pattern_str:?}: {err}"))
+            })
+            .map(|regex| Self {
+                pattern: pattern_str.into(),
+                regex,
+            })
+    }
+}
+
+/// What to do with the remaining target databases when migrating one of them
+/// fails.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FailurePolicy {
+    /// Do not start migrating any further target database. Migrations already
+    /// in progress are completed.
+    #[default]
+    Stop,
+    /// Migrate all remaining target databases.
+    Continue,
+}
+
+/// Configuration for running migrations on several databases of one
+/// namespace.
+#[must_use]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TargetsConfig {
+    pub databases: TargetDatabases,
+    /// Maximum number of target databases migrated concurrently.
+    pub concurrency: usize,
+    pub failure_policy: FailurePolicy,
+}
+
+impl TargetsConfig {
+    pub const fn new(databases: TargetDatabases) -> Self {
+        Self {
+            databases,
+            concurrency: DEFAULT_TARGET_CONCURRENCY,
+            failure_policy: FailurePolicy::Stop,
+        }
+    }
+
+    pub const fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    pub const fn with_failure_policy(mut self, failure_policy: FailurePolicy) -> Self {
+        self.failure_policy = failure_policy;
+        self
+    }
+}
+
+/// The outcome of a run on one target database.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TargetOutcome<T> {
+    /// The run on the target database succeeded.
+    Succeeded(T),
+    /// The run on the target database failed with the given error.
+    Failed(Error),
+    /// The target database has been skipped as the run on another target
+    /// database failed.
+    Skipped,
+}
+
+/// The result of a run on one target database.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TargetResult<T> {
+    pub database: String,
+    pub outcome: TargetOutcome<T>,
+}
+
+impl<T> TargetResult<T> {
+    pub const fn is_succeeded(&self) -> bool {
+        matches!(self.outcome, TargetOutcome::Succeeded(_))
+    }
+
+    pub const fn is_failed(&self) -> bool {
+        matches!(self.outcome, TargetOutcome::Failed(_))
+    }
+
+    pub const fn is_skipped(&self) -> bool {
+        matches!(self.outcome, TargetOutcome::Skipped)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,144 @@
+use super::*;
+use asserting::prelude::*;
+
+fn databases(names: &[&str]) -> Vec<String> {
+    names.iter().map(ToString::to_string).collect()
+}
+
+mod database_pattern {
+    use super::*;
+
+    #[test]
+    fn pattern_with_asterisk_matches_any_number_of_characters() {
+        let pattern: DatabasePattern = "tenant_*"
+            .parse()
+            .unwrap_or_else(|err| panic!("failed to parse pattern: {err}"));
+
+        assert_that!(pattern.is_match("tenant_")).is_true();
+        assert_that!(pattern.is_match("tenant_acme")).is_true();
+        assert_that!(pattern.is_match("customer_acme")).is_false();
+        assert_that!(pattern.is_match("my_tenant_acme")).is_false();
+    }
+
+    #[test]
+    fn pattern_with_question_mark_matches_exactly_one_character() {
+        let pattern: DatabasePattern = "shop-??"
+            .parse()
+            .unwrap_or_else(|err| panic!("failed to parse pattern: {err}"));
+
+        assert_that!(pattern.is_match("shop-de")).is_true();
+        assert_that!(pattern.is_match("shop-d")).is_false();
+        assert_that!(pattern.is_match("shop-deu")).is_false();
+    }
+
+    #[test]
+    fn regex_characters_in_pattern_match_themselves() {
+        let pattern: DatabasePattern = "tenant.(1)"
+            .parse()
+            .unwrap_or_else(|err| panic!("failed to parse pattern: {err}"));
+
+        assert_that!(pattern.is_match("tenant.(1)")).is_true();
+        assert_that!(pattern.is_match("tenantX(1)")).is_false();
+    }
+
+    #[test]
+    fn empty_pattern_is_invalid() {
+        let pattern = "".parse::<DatabasePattern>();
+
+        assert_that!(pattern).has_error(Error::Configuration(
+            "the database pattern must not be empty".into(),
+        ));
+    }
+
+    #[test]
+    fn display_database_pattern() {
+        let pattern: DatabasePattern = "tenant_*"
+            .parse()
+            .unwrap_or_else(|err| panic!("failed to parse pattern: {err}"));
+
+        assert_that!(pattern.to_string()).is_equal_to("tenant_*");
+    }
+}
+
+mod target_databases {
+    use super::*;
+
+    #[test]
+    fn select_databases_by_name() {
+        let targets = TargetDatabases::Names(databases(&["tenant_b", "tenant_a", "tenant_b"]));
+
+        let selected = targets.select(databases(&["tenant_a", "tenant_c"]));
+
+        assert_that!(selected).is_equal_to(databases(&["tenant_a", "tenant_b"]));
+        assert_that!(targets.needs_enumeration()).is_false();
+    }
+
+    #[test]
+    fn select_databases_matching_a_pattern() {
+        let pattern = "tenant_*"
+            .parse()
+            .unwrap_or_else(|err| panic!("failed to parse pattern: {err}"));
+        let targets = TargetDatabases::Matching(pattern);
+
+        let selected = targets.select(databases(&["tenant_b", "admin", "tenant_a"]));
+
+        assert_that!(selected).is_equal_to(databases(&["tenant_a", "tenant_b"]));
+        assert_that!(targets.needs_enumeration()).is_true();
+    }
+
+    #[test]
+    fn select_all_databases() {
+        let targets = TargetDatabases::All;
+
+        let selected = targets.select(databases(&["tenant_b", "admin", "tenant_a"]));
+
+        assert_that!(selected).is_equal_to(databases(&["admin", "tenant_a", "tenant_b"]));
+        assert_that!(targets.needs_enumeration()).is_true();
+    }
+
+    #[test]
+    fn select_all_databases_does_not_select_the_tracking_database() {
+        let targets = TargetDatabases::All;
+
+        let selected = targets.select(databases(&["tenant_a", "surrealdb_migrate"]));
+
+        assert_that!(selected).is_equal_to(databases(&["tenant_a"]));
+    }
+
+    #[test]
+    fn select_databases_matching_a_pattern_does_not_select_the_tracking_database() {
+        let pattern = "*"
+            .parse()
+            .unwrap_or_else(|err| panic!("failed to parse pattern: {err}"));
+        let targets = TargetDatabases::Matching(pattern);
+
+        let selected = targets.select(databases(&["surrealdb_migrate", "tenant_a"]));
+
+        assert_that!(selected).is_equal_to(databases(&["tenant_a"]));
+    }
+}
+
+mod targets_config {
+    use super::*;
+
+    #[test]
+    fn new_targets_config_has_default_concurrency_and_stops_on_failure() {
+        let config = TargetsConfig::new(TargetDatabases::All);
+
+        assert_that!(config).is_equal_to(TargetsConfig {
+            databases: TargetDatabases::All,
+            concurrency: DEFAULT_TARGET_CONCURRENCY,
+            failure_policy: FailurePolicy::Stop,
+        });
+    }
+
+    #[test]
+    fn targets_config_with_concurrency_and_failure_policy() {
+        let config = TargetsConfig::new(TargetDatabases::All)
+            .with_concurrency(8)
+            .with_failure_policy(FailurePolicy::Continue);
+
+        assert_that!(config.concurrency).is_equal_to(8);
+        assert_that!(config.failure_policy).is_equal_to(FailurePolicy::Continue);
+    }
+}
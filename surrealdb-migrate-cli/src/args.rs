@@ -1,6 +1,10 @@
 #![allow(clippy::doc_markdown, clippy::struct_excessive_bools)]
 
 use std::path::PathBuf;
+use std::time::Duration;
+use surrealdb_migrate::config::DbAuthLevel;
+use surrealdb_migrate::settings::parse_duration;
+use surrealdb_migrate::targets::DEFAULT_TARGET_CONCURRENCY;
 
 /// Create and apply migrations for a SurrealDB database.
 #[derive(clap::Parser, Debug, Clone)]
@@ -11,12 +15,59 @@ pub struct Args {
     /// Path to the folder containing the surrealdb-migrate.toml config file
     #[clap(long)]
     pub config_dir: Option<PathBuf>,
+    /// Path to an additional config file overwriting the settings of the config files found. Can be given several times
+    #[clap(long = "config", value_name = "FILE")]
+    pub config_files: Vec<PathBuf>,
+    /// Name of the profile in the config file whose settings shall be used, e.g. "prod"
+    #[clap(long)]
+    pub profile: Option<String>,
     /// Path to the folder that contains the migration files
     #[clap(long)]
     pub migrations_folder: Option<PathBuf>,
     /// Address of the database server, e.g. "ws://localhost:8000"
     #[clap(long)]
     pub db_address: Option<String>,
+    /// Method used to authenticate to the database server. Overwrites the auth-level setting
+    #[clap(long, value_enum)]
+    pub auth_level: Option<AuthLevel>,
+    /// Token to authenticate with, e.g. a JWT. Implies "--auth-level token". Prefer the environment variable SURMIG_DATABASE_TOKEN to keep the token out of the process list
+    #[clap(long)]
+    pub token: Option<String>,
+    /// Name of the record access method to sign in with. Implies "--auth-level record"
+    #[clap(long)]
+    pub access: Option<String>,
+    /// Variable passed to the SIGNIN clause of the record access method, e.g. "email=ci@example.com". Can be given several times
+    #[clap(long, value_name = "NAME=VALUE", value_parser = parse_access_variable)]
+    pub access_variable: Vec<(String, String)>,
+}
+
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthLevel {
+    Root,
+    Namespace,
+    Database,
+    Record,
+    Token,
+}
+
+impl From<AuthLevel> for DbAuthLevel {
+    fn from(auth_level: AuthLevel) -> Self {
+        match auth_level {
+            AuthLevel::Root => Self::Root,
+            AuthLevel::Namespace => Self::Namespace,
+            AuthLevel::Database => Self::Database,
+            AuthLevel::Record => Self::Record,
+            AuthLevel::Token => Self::Token,
+        }
+    }
+}
+
+fn parse_access_variable(value: &str) -> Result<(String, String), String> {
+    value
+        .split_once('=')
+        .filter(|(name, _)| !name.is_empty())
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .ok_or_else(|| format!("expected NAME=VALUE, but got \"{value}\""))
 }
 
 #[derive(clap::Subcommand, Debug, Clone)]
@@ -33,8 +84,22 @@ pub enum Command {
     /// List migrations defined and/or applied to the database.
     #[clap(aliases = &["ls"])]
     List(ListArgs),
+    /// Show the history of migrations applied to and reverted on the database.
+    History,
     /// Verify applied migrations against the defined ones.
     Verify(VerifyArgs),
+    /// Remove failed migration attempts from the migrations table.
+    Repair,
+    /// Squash applied migrations into a new baseline migration.
+    Squash(SquashArgs),
+    /// Create a new migration from the differences between the schemas of two databases.
+    Diff(DiffArgs),
+    /// Plan the migration of the database to the desired schema defined in the schema folder.
+    Schema(SchemaArgs),
+    /// Wait until the database accepts authenticated queries.
+    Wait(WaitArgs),
+    /// Show the effective configuration settings and where each value comes from.
+    Config(ConfigArgs),
 }
 
 #[derive(clap::Args, Debug, Clone)]
@@ -47,6 +112,12 @@ pub struct CreateArgs {
     /// Also create a new down migration file.
     #[clap(long, short, action)]
     pub down: bool,
+    /// The template for the content of the new migration files, e.g. "table", "field", "index", "backfill" or the name of a custom template. Default: empty.
+    #[clap(long, short)]
+    pub template: Option<String>,
+    /// The author inserted into the template. Default: &lt;current user&gt;.
+    #[clap(long)]
+    pub author: Option<String>,
 }
 
 #[derive(clap::Args, Debug, Clone)]
@@ -60,6 +131,21 @@ pub struct MigrateArgs {
     /// Do not verify the order of migrations to be applied.
     #[clap(long, action)]
     pub ignore_order: bool,
+    /// Migrate the given databases of the configured namespace instead of the configured database, e.g. "tenant_a,tenant_b".
+    #[clap(long, value_delimiter = ',', conflicts_with_all = ["databases_matching", "all_databases"])]
+    pub databases: Vec<String>,
+    /// Migrate all databases of the configured namespace whose names match the given pattern, e.g. "tenant_*". "*" matches any number of characters, "?" exactly one character.
+    #[clap(long, conflicts_with = "all_databases")]
+    pub databases_matching: Option<String>,
+    /// Migrate all databases of the configured namespace.
+    #[clap(long, action)]
+    pub all_databases: bool,
+    /// The maximum number of databases migrated concurrently.
+    #[clap(long, default_value_t = DEFAULT_TARGET_CONCURRENCY)]
+    pub concurrency: usize,
+    /// Continue migrating the remaining databases when migrating one of them fails.
+    #[clap(long, action)]
+    pub continue_on_failure: bool,
 }
 
 #[derive(clap::Args, Debug, Clone)]
@@ -94,3 +180,59 @@ pub struct VerifyArgs {
     #[clap(long, short, action)]
     pub order: bool,
 }
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct SquashArgs {
+    /// The key of the last migration squashed into the baseline in the format YYYYMMDD_HHMMSS. The database must be migrated up to this migration.
+    #[clap(long)]
+    pub to: String,
+    /// The title of the new baseline migration. Default: "baseline".
+    pub title: Option<String>,
+}
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct DiffArgs {
+    /// The database with the desired schema, either as "namespace/database" on the configured database server or as "address/namespace/database", e.g. "ws://localhost:8000/playground/dev".
+    #[clap(long)]
+    pub source: String,
+    /// The database the new migration is going to be applied to, in the same format as the source database.
+    #[clap(long)]
+    pub target: String,
+    /// The key of the new migration in the format YYYYMMDD_HHMMSS. Default: &lt;current date and time&gt;.
+    #[clap(long, short)]
+    pub key: Option<String>,
+    /// The title of the new migration. Default: &lt;no title&gt;.
+    pub title: Option<String>,
+}
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct SchemaArgs {
+    /// Write the migration plan as a new migration into the migrations folder.
+    #[clap(long, short, action, conflicts_with = "apply")]
+    pub write: bool,
+    /// Write the migration plan as a new migration and apply it to the database.
+    #[clap(long, short, action)]
+    pub apply: bool,
+    /// An empty database to evaluate the schema definition files in, either as "namespace/database" on the configured database server or as "address/namespace/database". Default: "&lt;namespace&gt;/&lt;database&gt;_scratch".
+    #[clap(long)]
+    pub scratch: Option<String>,
+    /// The key of the new migration in the format YYYYMMDD_HHMMSS. Default: &lt;current date and time&gt;.
+    #[clap(long, short)]
+    pub key: Option<String>,
+    /// The title of the new migration. Default: "schema".
+    pub title: Option<String>,
+}
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct WaitArgs {
+    /// The maximum time to wait, e.g. "30s", "500ms" or "2m".
+    #[clap(long, default_value = "60s", value_parser = parse_duration)]
+    pub timeout: Duration,
+}
+
+#[derive(clap::Args, Debug, Clone)]
+pub struct ConfigArgs {
+    /// Print the JSON Schema of the config file instead of the settings, e.g. for validation and autocompletion in editors.
+    #[clap(long)]
+    pub schema: bool,
+}
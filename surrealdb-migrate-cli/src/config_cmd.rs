@@ -0,0 +1,21 @@
+use crate::args::ConfigArgs;
+use crate::tables::format_settings_table;
+use color_eyre::Report;
+use color_eyre::eyre::WrapErr;
+use surrealdb_migrate::settings::{SettingsLoader, config_file_schema};
+
+pub fn run(args: &ConfigArgs, loader: &SettingsLoader) -> Result<(), Report> {
+    if args.schema {
+        let schema = serde_json::to_string_pretty(&config_file_schema())
+            .wrap_err("failed to format the JSON Schema of the config file")?;
+        println!("{schema}");
+        return Ok(());
+    }
+
+    let (_settings, origins) = loader.load_with_origins()?;
+
+    println!("\nEffective configuration settings:");
+    let settings_table = format_settings_table(origins)?;
+    println!("{settings_table}");
+    Ok(())
+}
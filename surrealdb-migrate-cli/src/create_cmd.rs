@@ -2,9 +2,11 @@ use crate::args::CreateArgs;
 use chrono::{NaiveDateTime, Utc};
 use color_eyre::Report;
 use color_eyre::eyre::{ContextCompat, WrapErr};
+use std::env;
 use surrealdb_migrate::config::{MIGRATION_KEY_FORMAT_STR, RunnerConfig};
 use surrealdb_migrate::definition::MigrationFilenameStrategy;
 use surrealdb_migrate::files::MigrationDirectory;
+use surrealdb_migrate::files::template::{DEFAULT_TEMPLATE, TemplateFolder, TemplateVariables};
 use surrealdb_migrate::migration::{MigrationKind, NewMigration};
 use surrealdb_migrate::repository::CreateNewMigration;
 
@@ -21,6 +23,9 @@ pub fn run(args: CreateArgs, config: RunnerConfig<'_>) -> Result<(), Report> {
         )
         .wrap_err("Invalid key! Please specify a key in the format YYYYmmdd_HHMMSS.")?;
     let title = args.title.unwrap_or_default();
+    let template_name = args.template.as_deref().unwrap_or(DEFAULT_TEMPLATE);
+    let template = TemplateFolder::new(&config.template_folder).find_template(template_name)?;
+    let author = args.author.unwrap_or_else(current_user);
 
     let (new_migration, down_migration) = if args.down {
         (
@@ -47,17 +52,20 @@ pub fn run(args: CreateArgs, config: RunnerConfig<'_>) -> Result<(), Report> {
     };
 
     let filename_strategy = MigrationFilenameStrategy::default();
+    let variables = TemplateVariables::new(&new_migration, author, Utc::now().date_naive());
 
+    let up_content = template.render(MigrationKind::Up, &variables);
     let up_migration = mig_dir
         .files(filename_strategy)
-        .create_new_migration(new_migration)?;
+        .create_new_migration_with_content(new_migration, &up_content)?;
 
     let up_filename = up_migration.script_path.file_name().wrap_err("migration script path does not have a filename part - should not be reachable - please report a bug!")?.to_string_lossy();
 
     let down_filename = if let Some(down_migration) = down_migration {
+        let down_content = template.render(MigrationKind::Down, &variables);
         let down_migration = mig_dir
             .files(filename_strategy)
-            .create_new_migration(down_migration)?;
+            .create_new_migration_with_content(down_migration, &down_content)?;
         let down_filename = down_migration.script_path.file_name().wrap_err("migration script path does not have a filename part - should not be reachable - please report a bug!")?.to_string_lossy().to_string();
         Some(down_filename)
     } else {
@@ -78,3 +86,9 @@ pub fn run(args: CreateArgs, config: RunnerConfig<'_>) -> Result<(), Report> {
 
     Ok(())
 }
+
+fn current_user() -> String {
+    env::var("USER")
+        .or_else(|_| env::var("USERNAME"))
+        .unwrap_or_default()
+}
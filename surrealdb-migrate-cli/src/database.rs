@@ -0,0 +1,91 @@
+use color_eyre::Report;
+use color_eyre::eyre::eyre;
+use surrealdb_migrate::config::DbClientConfig;
+
+/// Derives the client config for a database given as `namespace/database` or
+/// as `address/namespace/database` from the configured client config.
+pub fn database_config<'a>(
+    database: &str,
+    db_config: &DbClientConfig<'a>,
+) -> Result<DbClientConfig<'a>, Report> {
+    let invalid_database = || {
+        eyre!(
+            r#"invalid database "{database}". please specify a database as "namespace/database" or as "address/namespace/database", e.g. "ws://localhost:8000/playground/dev""#
+        )
+    };
+    let (location, database_name) = database.rsplit_once('/').ok_or_else(invalid_database)?;
+    let (address, namespace) = location
+        .rsplit_once('/')
+        .map_or((None, location), |(address, namespace)| {
+            (Some(address), namespace)
+        });
+    if namespace.is_empty() || database_name.is_empty() || address.is_some_and(str::is_empty) {
+        return Err(invalid_database());
+    }
+    let config = db_config
+        .clone()
+        .with_namespace(namespace.to_string())
+        .with_database(database_name.to_string());
+    Ok(match address {
+        Some(address) => config.with_address(address.to_string()),
+        None => config,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use asserting::prelude::*;
+
+    mod database_config {
+        use super::*;
+
+        #[test]
+        fn namespace_and_database_on_configured_server() {
+            let db_config = DbClientConfig::default().with_address("ws://localhost:9000");
+
+            let config = database_config("playground/dev", &db_config)
+                .unwrap_or_else(|err| panic!("unexpected error: {err}"));
+
+            assert_that!(config).is_equal_to(
+                DbClientConfig::default()
+                    .with_address("ws://localhost:9000")
+                    .with_namespace("playground")
+                    .with_database("dev"),
+            );
+        }
+
+        #[test]
+        fn address_namespace_and_database() {
+            let db_config = DbClientConfig::default().with_address("ws://localhost:9000");
+
+            let config = database_config("wss://db.example.com:8000/playground/dev", &db_config)
+                .unwrap_or_else(|err| panic!("unexpected error: {err}"));
+
+            assert_that!(config).is_equal_to(
+                DbClientConfig::default()
+                    .with_address("wss://db.example.com:8000")
+                    .with_namespace("playground")
+                    .with_database("dev"),
+            );
+        }
+
+        #[test]
+        fn database_without_namespace_is_invalid() {
+            let db_config = DbClientConfig::default();
+
+            let config = database_config("dev", &db_config);
+
+            assert_that!(config.is_err()).is_true();
+        }
+
+        #[test]
+        fn database_with_empty_namespace_is_invalid() {
+            let db_config = DbClientConfig::default();
+
+            let config = database_config("/dev", &db_config);
+
+            assert_that!(config.is_err()).is_true();
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use crate::args::DiffArgs;
+use crate::database::database_config;
+use crate::runner::runner;
+use chrono::{NaiveDateTime, Utc};
+use color_eyre::Report;
+use color_eyre::eyre::{ContextCompat, WrapErr};
+use surrealdb_migrate::config::{DbClientConfig, MIGRATION_KEY_FORMAT_STR, RunnerConfig};
+use surrealdb_migrate::db_client::connect_to_database;
+use surrealdb_migrate::result::Diffed;
+
+pub async fn run(
+    args: DiffArgs,
+    config: RunnerConfig<'_>,
+    db_config: DbClientConfig<'_>,
+) -> Result<(), Report> {
+    let key = args
+        .key
+        .map_or_else(
+            || Ok(Utc::now().naive_local()),
+            |arg| NaiveDateTime::parse_from_str(&arg, MIGRATION_KEY_FORMAT_STR),
+        )
+        .wrap_err("Invalid key! Please specify a key in the format YYYYmmdd_HHMMSS.")?;
+    let title = args.title.unwrap_or_default();
+
+    let source_config = database_config(&args.source, &db_config)?;
+    let target_config = database_config(&args.target, &db_config)?;
+
+    let migrations_folder = config.migrations_folder.display().to_string();
+    let runner = runner(config);
+
+    println!();
+    println!(
+        r#"Comparing the schema of database "{}/{}" (source) with database "{}/{}" (target)..."#,
+        &source_config.namespace,
+        &source_config.database,
+        &target_config.namespace,
+        &target_config.database,
+    );
+
+    let source = connect_to_database(&source_config)
+        .await
+        .wrap_err("failed to connect to the source database")?;
+    let target = connect_to_database(&target_config)
+        .await
+        .wrap_err("failed to connect to the target database")?;
+
+    let diffed = runner.diff(key, &title, &source, &target).await?;
+
+    println!();
+    match diffed {
+        Diffed::NoDifferences => {
+            println!("No differences found. No migration created.");
+        },
+        Diffed::Created { up, down } => {
+            let up_filename = up.script_path.file_name().wrap_err("migration script path does not have a filename part - should not be reachable - please report a bug!")?.to_string_lossy();
+            let down_filename = down.script_path.file_name().wrap_err("migration script path does not have a filename part - should not be reachable - please report a bug!")?.to_string_lossy();
+            println!("Migrations located at {migrations_folder}:");
+            println!();
+            println!("New migration {up_filename} created.");
+            println!("New backward migration {down_filename} created.");
+        },
+    }
+    println!();
+
+    Ok(())
+}
@@ -0,0 +1,127 @@
+use crate::script_error::ScriptFailed;
+use color_eyre::Report;
+use std::process::ExitCode;
+use surrealdb_migrate::error::{Error, ErrorCategory};
+
+/// Exit code for failures that do not belong to any specific category.
+pub const GENERAL_FAILURE: u8 = 1;
+// exit code 2 is used by clap for invalid command line arguments
+pub const CONFIGURATION_ERROR: u8 = 3;
+pub const FILESYSTEM_ERROR: u8 = 4;
+pub const MIGRATION_ERROR: u8 = 5;
+pub const SCRIPT_ERROR: u8 = 6;
+pub const CONNECTION_ERROR: u8 = 10;
+pub const AUTHENTICATION_ERROR: u8 = 11;
+pub const PERMISSION_ERROR: u8 = 12;
+pub const CONSTRAINT_ERROR: u8 = 13;
+pub const QUERY_ERROR: u8 = 14;
+
+/// Determines the process exit code for the given error report.
+///
+/// The exit code is derived from the category of the first error in the
+/// chain of errors that has a known category.
+pub fn exit_code(report: &Report) -> ExitCode {
+    ExitCode::from(category_of(report).map_or(GENERAL_FAILURE, exit_code_of_category))
+}
+
+fn category_of(report: &Report) -> Option<ErrorCategory> {
+    report.chain().find_map(|err| {
+        err.downcast_ref::<Error>()
+            .map(Error::category)
+            .or_else(|| {
+                err.downcast_ref::<ScriptFailed>()
+                    .map(|_| ErrorCategory::Script)
+            })
+    })
+}
+
+const fn exit_code_of_category(category: ErrorCategory) -> u8 {
+    match category {
+        ErrorCategory::Configuration => CONFIGURATION_ERROR,
+        ErrorCategory::Filesystem => FILESYSTEM_ERROR,
+        ErrorCategory::Migration => MIGRATION_ERROR,
+        ErrorCategory::Script => SCRIPT_ERROR,
+        ErrorCategory::Connection => CONNECTION_ERROR,
+        ErrorCategory::Authentication => AUTHENTICATION_ERROR,
+        ErrorCategory::Permission => PERMISSION_ERROR,
+        ErrorCategory::Constraint => CONSTRAINT_ERROR,
+        ErrorCategory::Query => QUERY_ERROR,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use asserting::prelude::*;
+    use color_eyre::eyre::{WrapErr, eyre};
+    use surrealdb_migrate::error::DatabaseError;
+
+    #[test]
+    fn every_category_has_its_own_exit_code() {
+        let exit_codes = [
+            ErrorCategory::Authentication,
+            ErrorCategory::Configuration,
+            ErrorCategory::Connection,
+            ErrorCategory::Constraint,
+            ErrorCategory::Filesystem,
+            ErrorCategory::Migration,
+            ErrorCategory::Permission,
+            ErrorCategory::Query,
+            ErrorCategory::Script,
+        ]
+        .map(exit_code_of_category);
+
+        assert_that!(exit_codes).contains_exactly_in_any_order([
+            AUTHENTICATION_ERROR,
+            CONFIGURATION_ERROR,
+            CONNECTION_ERROR,
+            CONSTRAINT_ERROR,
+            FILESYSTEM_ERROR,
+            MIGRATION_ERROR,
+            PERMISSION_ERROR,
+            QUERY_ERROR,
+            SCRIPT_ERROR,
+        ]);
+    }
+
+    #[test]
+    fn category_of_report_without_category() {
+        let report = eyre!("no key specified for option '--to'");
+
+        assert_that!(category_of(&report)).is_none();
+    }
+
+    #[test]
+    fn category_of_migration_error() {
+        let report = Report::from(Error::NotMigratedUpTo("20250103_140520".into()));
+
+        assert_that!(category_of(&report))
+            .some()
+            .is_equal_to(ErrorCategory::Migration);
+    }
+
+    #[test]
+    fn category_of_wrapped_authentication_error() {
+        let report = Err::<(), _>(Error::Db(DatabaseError::new(
+            ErrorCategory::Authentication,
+            "There was a problem with authentication",
+        )))
+        .wrap_err("failed to connect to the source database")
+        .expect_err("error expected");
+
+        assert_that!(category_of(&report))
+            .some()
+            .is_equal_to(ErrorCategory::Authentication);
+    }
+
+    #[test]
+    fn category_of_failed_script() {
+        let report = Report::new(ScriptFailed::new(
+            "migrations/20250103_140520_define_quote_table.surql".into(),
+        ));
+
+        assert_that!(category_of(&report))
+            .some()
+            .is_equal_to(ErrorCategory::Script);
+    }
+}
@@ -0,0 +1,19 @@
+use crate::runner::runner;
+use crate::tables::format_history_table;
+use color_eyre::Report;
+use surrealdb_migrate::config::RunnerConfig;
+use surrealdb_migrate::db_client::DbConnection;
+
+pub async fn run(config: RunnerConfig<'_>, db: &DbConnection) -> Result<(), Report> {
+    let runner = runner(config);
+    let history = runner.history(db).await?;
+
+    println!("\nHistory of migrations:");
+    let no_events_recorded = history.is_empty();
+    let history_table = format_history_table(history)?;
+    println!("{history_table}");
+    if no_events_recorded {
+        println!("  No migrations applied or reverted yet.\n");
+    }
+    Ok(())
+}
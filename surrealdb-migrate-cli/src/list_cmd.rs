@@ -1,6 +1,6 @@
 use crate::args::ListArgs;
 use crate::runner::runner;
-use crate::tables::format_migration_table;
+use crate::tables::{format_failed_attempts_table, format_migration_table};
 use color_eyre::Report;
 use std::cmp::Ordering;
 use surrealdb_migrate::config::RunnerConfig;
@@ -66,6 +66,14 @@ pub async fn run(
         if no_migrations_listed {
             println!("  No migrations found for the specified options.\n");
         }
+
+        let failed_attempts = runner.list_failed_attempts(db).await?;
+        if !failed_attempts.is_empty() {
+            println!("Failed migrations:");
+            let failed_attempts_table = format_failed_attempts_table(failed_attempts)?;
+            println!("{failed_attempts_table}");
+            println!("  Run `surmig repair` after fixing the failed migrations.\n");
+        }
     }
     Ok(())
 }
@@ -53,33 +53,63 @@
 //! [SurrealDB]: https://surrealdb.com
 
 mod args;
+mod config_cmd;
 mod create_cmd;
+mod database;
+mod diff_cmd;
+mod exit_code;
+mod history_cmd;
 mod list_cmd;
 mod migrate_cmd;
+mod repair_cmd;
 mod revert_cmd;
 mod runner;
+mod schema_cmd;
+mod script_error;
+mod squash_cmd;
 mod tables;
 mod verify_cmd;
+mod wait_cmd;
 
 use crate::args::{Args, Command};
+use crate::exit_code::exit_code;
 use clap::Parser;
 use color_eyre::Report;
 use color_eyre::eyre::WrapErr;
 use simplelog::{ConfigBuilder, LevelFilter, SimpleLogger};
-use std::path::Path;
-use surrealdb_migrate::config::{DbClientConfig, RunnerConfig};
+use std::io::{self, IsTerminal};
+use std::process::ExitCode;
+use surrealdb_migrate::config::{DbAuthLevel, DbClientConfig, RunnerConfig};
 use surrealdb_migrate::db_client::connect_to_database;
-use surrealdb_migrate::settings::Settings;
+use surrealdb_migrate::error::Error;
+use surrealdb_migrate::settings::{Settings, SettingsLoader, unknown_environment_variables};
 
 #[tokio::main]
-async fn main() -> Result<(), Report> {
+async fn main() -> ExitCode {
+    if let Err(report) = run().await {
+        eprintln!("Error: {report:?}");
+        return exit_code(&report);
+    }
+    ExitCode::SUCCESS
+}
+
+async fn run() -> Result<(), Report> {
     color_eyre::install()?;
 
     let args = Args::parse();
 
-    let settings = args.config_dir.map_or_else(Settings::load, |dir| {
-        Settings::load_from_dir(Path::new(&dir))
-    })?;
+    for variable in unknown_environment_variables() {
+        eprintln!("Warning: environment variable {variable} does not define a known setting");
+    }
+
+    let settings_loader = settings_loader(&args);
+    if let Command::Config(config_args) = &args.command {
+        return config_cmd::run(config_args, &settings_loader);
+    }
+    let mut settings = settings_loader.load()?;
+    check_command_allowed(&args.command, &settings)?;
+    apply_auth_options(&args, &mut settings);
+    prompt_for_missing_password(&args.command, &mut settings)?;
 
     let runner_config = args.migrations_folder.map_or_else(
         || settings.runner_config(),
@@ -105,6 +135,9 @@ async fn run_command(
         Command::Migrate(args) => {
             SimpleLogger::init(LevelFilter::Info, logger_config())
                 .wrap_err("failed to initialize terminal logger")?;
+            if let Some(targets) = migrate_cmd::targets_config(&args)? {
+                return migrate_cmd::run_targets(args, targets, runner_config, db_config).await;
+            }
             let db = connect_to_database(&db_config).await?;
             migrate_cmd::run(args, runner_config, db_config, &db).await
         },
@@ -118,13 +151,111 @@ async fn run_command(
             let db = connect_to_database(&db_config).await?;
             list_cmd::run(args, runner_config, &db).await
         },
+        Command::History => {
+            let db = connect_to_database(&db_config).await?;
+            history_cmd::run(runner_config, &db).await
+        },
         Command::Verify(args) => {
             let db = connect_to_database(&db_config).await?;
             verify_cmd::run(args, runner_config, db_config, &db).await
         },
+        Command::Repair => {
+            let db = connect_to_database(&db_config).await?;
+            repair_cmd::run(runner_config, db_config, &db).await
+        },
+        Command::Squash(args) => {
+            let db = connect_to_database(&db_config).await?;
+            squash_cmd::run(args, runner_config, db_config, &db).await
+        },
+        Command::Diff(args) => diff_cmd::run(args, runner_config, db_config).await,
+        Command::Schema(args) => {
+            SimpleLogger::init(LevelFilter::Info, logger_config())
+                .wrap_err("failed to initialize terminal logger")?;
+            let db = connect_to_database(&db_config).await?;
+            schema_cmd::run(args, runner_config, db_config, &db).await
+        },
+        Command::Wait(args) => wait_cmd::run(args, db_config).await,
+        Command::Config(_) => {
+            unreachable!("the config command is run before the settings are loaded")
+        },
     }
 }
 
+fn settings_loader(args: &Args) -> SettingsLoader {
+    let mut loader = SettingsLoader::new();
+    if let Some(config_dir) = &args.config_dir {
+        loader = loader.with_config_dir(config_dir.clone());
+    }
+    for config_file in &args.config_files {
+        loader = loader.with_config_file(config_file.clone());
+    }
+    if let Some(profile) = &args.profile {
+        loader = loader.with_profile(profile.clone());
+    }
+    loader
+}
+
+/// Checks that the command line options do not override settings which are
+/// forbidden by the configuration.
+fn check_command_allowed(command: &Command, settings: &Settings) -> Result<(), Error> {
+    if let Command::Migrate(args) = command {
+        if args.ignore_checksum && !settings.migration.allow_ignore_checksum {
+            return Err(Error::Configuration(
+                "the option '--ignore-checksum' is not allowed by the allow-ignore-checksum setting"
+                    .into(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Overwrites the authentication settings with the related command line
+/// options.
+///
+/// The options `--token` and `--access` select the related auth level, unless
+/// an auth level is given explicitly.
+fn apply_auth_options(args: &Args, settings: &mut Settings) {
+    let database = &mut settings.database;
+    if let Some(token) = &args.token {
+        database.token.clone_from(token);
+        database.auth_level = DbAuthLevel::Token;
+    }
+    if let Some(access) = &args.access {
+        database.access.clone_from(access);
+        database.auth_level = DbAuthLevel::Record;
+    }
+    database
+        .access_variables
+        .extend(args.access_variable.iter().cloned());
+    if let Some(auth_level) = args.auth_level {
+        database.auth_level = auth_level.into();
+    }
+}
+
+/// Asks for the database password if none is configured and the command
+/// connects to the database as a system user.
+///
+/// The password is only asked for if stdin is an interactive terminal. The
+/// input is not echoed.
+fn prompt_for_missing_password(command: &Command, settings: &mut Settings) -> Result<(), Report> {
+    if matches!(command, Command::Create(_))
+        || matches!(
+            settings.database.auth_level,
+            DbAuthLevel::Record | DbAuthLevel::Token
+        )
+        || !settings.database.password.is_empty()
+        || !io::stdin().is_terminal()
+    {
+        return Ok(());
+    }
+    settings.database.password = rpassword::prompt_password(format!(
+        "Password for database user {}: ",
+        settings.database.username
+    ))
+    .wrap_err("failed to read the password from the terminal")?;
+    Ok(())
+}
+
 fn logger_config() -> simplelog::Config {
     ConfigBuilder::new()
         .set_location_level(LevelFilter::Off)
@@ -1,11 +1,14 @@
 use crate::args::MigrateArgs;
 use crate::runner::runner;
+use crate::script_error::report_script_error;
+use crate::tables::format_target_results_table;
 use chrono::NaiveDateTime;
 use color_eyre::Report;
 use color_eyre::eyre::eyre;
 use surrealdb_migrate::config::{DbClientConfig, MIGRATION_KEY_FORMAT_STR, RunnerConfig};
 use surrealdb_migrate::db_client::DbConnection;
 use surrealdb_migrate::result::Migrated;
+use surrealdb_migrate::targets::{FailurePolicy, TargetDatabases, TargetOutcome, TargetsConfig};
 
 pub async fn run(
     args: MigrateArgs,
@@ -18,15 +21,7 @@ pub async fn run(
 
     let migrated_to = if let Some(max_key_arg) = args.to {
         let max_key_arg = max_key_arg.trim();
-        if max_key_arg.is_empty() {
-            return Err(eyre!(
-                "no key specified for option '--to'. please specify a key following the '--to' option in the format yyyymmdd_HHMMSS, e.g. --to 20250103_140520"
-            ));
-        }
-        let max_key = NaiveDateTime::parse_from_str(max_key_arg, MIGRATION_KEY_FORMAT_STR)
-            .map_err(|_| {
-                eyre!("the argument in option '--to {max_key_arg}' is not a valid migration key. please specify the key in the format yyyymmdd_HHMMSS, e.g. --to 20250103_140520")
-            })?;
+        let max_key = parse_max_key(max_key_arg)?;
 
         println!();
         log::info!(
@@ -36,7 +31,10 @@ pub async fn run(
         );
         println!();
 
-        runner.migrate_to(max_key, db).await.map_err(Report::from)
+        runner
+            .migrate_to(max_key, db)
+            .await
+            .map_err(report_script_error)
     } else {
         println!();
         log::info!(
@@ -46,7 +44,7 @@ pub async fn run(
         );
         println!();
 
-        runner.migrate(db).await.map_err(Report::from)
+        runner.migrate(db).await.map_err(report_script_error)
     }?;
 
     match migrated_to {
@@ -72,6 +70,103 @@ pub async fn run(
     Ok(())
 }
 
+/// Migrates several databases of the configured namespace as selected by the
+/// command line arguments.
+pub async fn run_targets(
+    args: MigrateArgs,
+    targets: TargetsConfig,
+    config: RunnerConfig<'_>,
+    db_config: DbClientConfig<'_>,
+) -> Result<(), Report> {
+    let config = apply_command_args_to_runner_config(config, &args);
+    let runner = runner(config);
+
+    let max_key = args
+        .to
+        .as_deref()
+        .map(|max_key_arg| parse_max_key(max_key_arg.trim()))
+        .transpose()?;
+
+    println!();
+    log::info!(
+        r#"Migrating databases of namespace "{}"..."#,
+        &db_config.namespace
+    );
+    println!();
+
+    let results = runner
+        .migrate_targets(&targets, max_key, &db_config)
+        .await?;
+
+    println!();
+    if results.is_empty() {
+        log::info!(
+            r#"No databases to migrate found in namespace "{}"."#,
+            &db_config.namespace
+        );
+        println!();
+        return Ok(());
+    }
+    let num_targets = results.len();
+    let num_failed = results.iter().filter(|result| result.is_failed()).count();
+    let first_error = results.iter().find_map(|result| match &result.outcome {
+        TargetOutcome::Failed(error) => Some(error.clone()),
+        _ => None,
+    });
+    let results_table = format_target_results_table(results)?;
+    println!("{results_table}");
+    println!();
+
+    match first_error {
+        None => {
+            log::info!(
+                r#"Successfully migrated {num_targets} database(s) of namespace "{}"."#,
+                &db_config.namespace
+            );
+            println!();
+            Ok(())
+        },
+        Some(error) => Err(Report::from(error).wrap_err(format!(
+            "failed to migrate {num_failed} of {num_targets} database(s)"
+        ))),
+    }
+}
+
+/// Returns the config for migrating several databases if the command line
+/// arguments select target databases, otherwise `None`.
+pub fn targets_config(args: &MigrateArgs) -> Result<Option<TargetsConfig>, Report> {
+    let databases = if args.all_databases {
+        TargetDatabases::All
+    } else if let Some(pattern) = &args.databases_matching {
+        TargetDatabases::Matching(pattern.parse()?)
+    } else if !args.databases.is_empty() {
+        TargetDatabases::Names(args.databases.clone())
+    } else {
+        return Ok(None);
+    };
+    let failure_policy = if args.continue_on_failure {
+        FailurePolicy::Continue
+    } else {
+        FailurePolicy::Stop
+    };
+    Ok(Some(
+        TargetsConfig::new(databases)
+            .with_concurrency(args.concurrency)
+            .with_failure_policy(failure_policy),
+    ))
+}
+
+fn parse_max_key(max_key_arg: &str) -> Result<NaiveDateTime, Report> {
+    if max_key_arg.is_empty() {
+        return Err(eyre!(
+            "no key specified for option '--to'. please specify a key following the '--to' option in the format yyyymmdd_HHMMSS, e.g. --to 20250103_140520"
+        ));
+    }
+    NaiveDateTime::parse_from_str(max_key_arg, MIGRATION_KEY_FORMAT_STR).map_err(|_| {
+        eyre!("the argument in option '--to {max_key_arg}' is not a valid migration key. please specify the key in the format yyyymmdd_HHMMSS, e.g. --to 20250103_140520")
+    })
+}
+
 #[allow(clippy::let_and_return)]
 const fn apply_command_args_to_runner_config<'a>(
     runner_config: RunnerConfig<'a>,
@@ -89,3 +184,95 @@ const fn apply_command_args_to_runner_config<'a>(
     };
     runner_config
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use asserting::prelude::*;
+    use surrealdb_migrate::targets::DEFAULT_TARGET_CONCURRENCY;
+
+    fn migrate_args() -> MigrateArgs {
+        MigrateArgs {
+            to: None,
+            ignore_checksum: false,
+            ignore_order: false,
+            databases: vec![],
+            databases_matching: None,
+            all_databases: false,
+            concurrency: DEFAULT_TARGET_CONCURRENCY,
+            continue_on_failure: false,
+        }
+    }
+
+    #[test]
+    fn targets_config_without_target_databases_is_none() {
+        let targets = targets_config(&migrate_args());
+
+        assert_that!(targets).ok().is_none();
+    }
+
+    #[test]
+    fn targets_config_for_list_of_databases() {
+        let args = MigrateArgs {
+            databases: vec!["tenant_a".into(), "tenant_b".into()],
+            concurrency: 2,
+            continue_on_failure: true,
+            ..migrate_args()
+        };
+
+        let targets = targets_config(&args);
+
+        assert_that!(targets).ok().is_equal_to(Some(
+            TargetsConfig::new(TargetDatabases::Names(vec![
+                "tenant_a".into(),
+                "tenant_b".into(),
+            ]))
+            .with_concurrency(2)
+            .with_failure_policy(FailurePolicy::Continue),
+        ));
+    }
+
+    #[test]
+    fn targets_config_for_databases_matching_a_pattern() {
+        let args = MigrateArgs {
+            databases_matching: Some("tenant_*".into()),
+            ..migrate_args()
+        };
+
+        let targets = targets_config(&args);
+
+        assert_that!(targets)
+            .ok()
+            .is_equal_to(Some(TargetsConfig::new(TargetDatabases::Matching(
+                "tenant_*".parse().expect("invalid database pattern"),
+            ))));
+    }
+
+    #[test]
+    fn targets_config_for_all_databases() {
+        let args = MigrateArgs {
+            all_databases: true,
+            ..migrate_args()
+        };
+
+        let targets = targets_config(&args);
+
+        assert_that!(targets)
+            .ok()
+            .is_equal_to(Some(TargetsConfig::new(TargetDatabases::All)));
+    }
+
+    #[test]
+    fn targets_config_for_empty_database_pattern_is_an_error() {
+        let args = MigrateArgs {
+            databases_matching: Some(String::new()),
+            ..migrate_args()
+        };
+
+        let targets = targets_config(&args);
+
+        assert_that!(targets.map_err(|err| err.to_string()))
+            .err()
+            .contains("the database pattern must not be empty");
+    }
+}
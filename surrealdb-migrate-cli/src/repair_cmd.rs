@@ -0,0 +1,43 @@
+use crate::runner::runner;
+use color_eyre::Report;
+use surrealdb_migrate::config::{DbClientConfig, MIGRATION_KEY_FORMAT_STR, RunnerConfig};
+use surrealdb_migrate::db_client::DbConnection;
+use surrealdb_migrate::result::Repaired;
+
+pub async fn run(
+    config: RunnerConfig<'_>,
+    db_config: DbClientConfig<'_>,
+    db: &DbConnection,
+) -> Result<(), Report> {
+    let runner = runner(config);
+
+    println!();
+    println!(
+        r#"Repairing migrations table of database "{}/{}"..."#,
+        &db_config.namespace, &db_config.database
+    );
+    println!();
+
+    match runner.repair(db).await? {
+        Repaired::Nothing => {
+            println!("No failed migration attempts found. Nothing to repair.");
+        },
+        Repaired::Cleared(keys) => {
+            for key in &keys {
+                println!(
+                    "* removed failed attempt of migration {}",
+                    key.format(MIGRATION_KEY_FORMAT_STR)
+                );
+            }
+            let num_cleared = keys.len();
+            if num_cleared == 1 {
+                println!("\nRemoved 1 failed migration attempt.");
+            } else {
+                println!("\nRemoved {num_cleared} failed migration attempts.");
+            }
+        },
+    }
+    println!();
+
+    Ok(())
+}
@@ -1,5 +1,6 @@
 use crate::args::RevertArgs;
 use crate::runner::runner;
+use crate::script_error::report_script_error;
 use chrono::NaiveDateTime;
 use color_eyre::Report;
 use color_eyre::eyre::eyre;
@@ -35,7 +36,10 @@ pub async fn run(
         );
         println!();
 
-        runner.revert_to(max_key, db).await?
+        runner
+            .revert_to(max_key, db)
+            .await
+            .map_err(report_script_error)?
     } else {
         println!();
         log::info!(
@@ -45,7 +49,7 @@ pub async fn run(
         );
         println!();
 
-        runner.revert(db).await?
+        runner.revert(db).await.map_err(report_script_error)?
     };
 
     match reverted_to {
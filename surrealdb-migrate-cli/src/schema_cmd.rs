@@ -0,0 +1,100 @@
+use crate::args::SchemaArgs;
+use crate::database::database_config;
+use crate::runner::runner;
+use chrono::{NaiveDateTime, Utc};
+use color_eyre::Report;
+use color_eyre::eyre::{ContextCompat, WrapErr};
+use surrealdb_migrate::config::{DbClientConfig, MIGRATION_KEY_FORMAT_STR, RunnerConfig};
+use surrealdb_migrate::db_client::{DbConnection, connect_to_database};
+use surrealdb_migrate::result::{Diffed, Migrated};
+
+const DEFAULT_SCHEMA_MIGRATION_TITLE: &str = "schema";
+
+pub async fn run(
+    args: SchemaArgs,
+    config: RunnerConfig<'_>,
+    db_config: DbClientConfig<'_>,
+    db: &DbConnection,
+) -> Result<(), Report> {
+    let key = args
+        .key
+        .map_or_else(
+            || Ok(Utc::now().naive_local()),
+            |arg| NaiveDateTime::parse_from_str(&arg, MIGRATION_KEY_FORMAT_STR),
+        )
+        .wrap_err("Invalid key! Please specify a key in the format YYYYmmdd_HHMMSS.")?;
+    let title = args
+        .title
+        .unwrap_or_else(|| DEFAULT_SCHEMA_MIGRATION_TITLE.to_string());
+
+    let scratch_config = match args.scratch {
+        Some(scratch) => database_config(&scratch, &db_config)?,
+        None => db_config
+            .clone()
+            .with_database(format!("{}_scratch", db_config.database)),
+    };
+
+    let migrations_folder = config.migrations_folder.display().to_string();
+    let schema_folder = config.schema_folder.display().to_string();
+    let runner = runner(config);
+
+    println!();
+    println!(
+        r#"Comparing the desired schema defined in {schema_folder} with database "{}/{}"..."#,
+        &db_config.namespace, &db_config.database,
+    );
+
+    let scratch = connect_to_database(&scratch_config)
+        .await
+        .wrap_err("failed to connect to the scratch database")?;
+
+    let plan = runner.plan_schema(&scratch, db).await?;
+
+    println!();
+    if plan.is_empty() {
+        println!("The database schema is up to date. Nothing to do.");
+        println!();
+        return Ok(());
+    }
+    println!("Migration plan:");
+    println!();
+    print!("{}", plan.up_script());
+    println!();
+
+    if !args.write && !args.apply {
+        return Ok(());
+    }
+
+    let diffed = runner.create_migration_from_diff(key, &title, &plan)?;
+    if let Diffed::Created { up, down } = diffed {
+        let up_filename = up.script_path.file_name().wrap_err("migration script path does not have a filename part - should not be reachable - please report a bug!")?.to_string_lossy();
+        let down_filename = down.script_path.file_name().wrap_err("migration script path does not have a filename part - should not be reachable - please report a bug!")?.to_string_lossy();
+        println!("Migrations located at {migrations_folder}:");
+        println!();
+        println!("New migration {up_filename} created.");
+        println!("New backward migration {down_filename} created.");
+        println!();
+    }
+
+    if args.apply {
+        log::info!(
+            r#"Migrating database "{}/{}"..."#,
+            &db_config.namespace,
+            &db_config.database
+        );
+        println!();
+
+        if let Migrated::UpTo(last_applied) = runner.migrate_to(key, db).await? {
+            println!();
+            log::info!(
+                r#"Successfully migrated database "{}/{}" up to {}."#,
+                &db_config.namespace,
+                &db_config.database,
+                &last_applied.format(MIGRATION_KEY_FORMAT_STR).to_string()
+            );
+        }
+        println!();
+    }
+
+    Ok(())
+}
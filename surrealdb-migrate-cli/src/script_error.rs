@@ -0,0 +1,142 @@
+use color_eyre::Report;
+use std::fmt::{Display, Formatter, Write};
+use std::path::PathBuf;
+use surrealdb_migrate::error::Error;
+use surrealdb_migrate::script::{ScriptError, StatementError};
+
+/// A migration script failed. The details have been printed already.
+#[derive(Debug)]
+pub struct ScriptFailed(PathBuf);
+
+impl ScriptFailed {
+    pub const fn new(script_path: PathBuf) -> Self {
+        Self(script_path)
+    }
+}
+
+impl Display for ScriptFailed {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "failed to execute migration script {}", self.0.display())
+    }
+}
+
+impl std::error::Error for ScriptFailed {}
+
+/// Converts an error of the migration runner into a report.
+///
+/// Errors of a failed migration script are printed with the location and the
+/// text of each failed statement before they are returned as a short report.
+pub fn report_script_error(error: Error) -> Report {
+    match error {
+        Error::DbScript(script_error) => {
+            eprint!("{}", format_script_error(&script_error));
+            Report::new(ScriptFailed::new(script_error.script_path))
+        },
+        other => Report::from(other),
+    }
+}
+
+fn format_script_error(script_error: &ScriptError) -> String {
+    let mut output = format!(
+        "\nFailed to execute migration script {}\n",
+        script_error.script_path.display()
+    );
+    for error in &script_error.errors {
+        output.push('\n');
+        output.push_str(&format_statement_error(error));
+    }
+    output.push('\n');
+    output
+}
+
+fn format_statement_error(error: &StatementError) -> String {
+    let mut output = String::new();
+    if let Some(statement) = &error.statement {
+        let start = statement.start;
+        let end = statement.end;
+        let line_no_width = end.line.to_string().len();
+        _ = writeln!(
+            output,
+            "statement {} at line {}, column {} to line {}, column {}:",
+            error.number, start.line, start.column, end.line, end.column
+        );
+        let first_line_indent = " ".repeat(start.column - 1);
+        for (line_no, line) in (start.line..).zip(statement.text.lines()) {
+            let indent = if line_no == start.line {
+                first_line_indent.as_str()
+            } else {
+                ""
+            };
+            _ = writeln!(output, "  {line_no:>line_no_width$} | {indent}{line}");
+        }
+        _ = writeln!(output, "  {:>line_no_width$} = {}", "", error.message);
+    } else {
+        _ = writeln!(output, "statement {}:", error.number);
+        _ = writeln!(output, "  = {}", error.message);
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use asserting::prelude::*;
+    use surrealdb_migrate::script::{Position, Statement};
+
+    #[test]
+    fn format_statement_error_spanning_one_line() {
+        let error = StatementError {
+            number: 2,
+            statement: Some(Statement {
+                text: "THROW 'boom'".into(),
+                start: Position { line: 3, column: 5 },
+                end: Position {
+                    line: 3,
+                    column: 16,
+                },
+            }),
+            message: "An error occurred: boom".into(),
+        };
+
+        let formatted = format_statement_error(&error);
+
+        assert_that!(formatted).is_equal_to(
+            "statement 2 at line 3, column 5 to line 3, column 16:\n  3 |     THROW 'boom'\n    = An error occurred: boom\n",
+        );
+    }
+
+    #[test]
+    fn format_statement_error_spanning_several_lines() {
+        let error = StatementError {
+            number: 1,
+            statement: Some(Statement {
+                text: "DEFINE TABLE quote SCHEMALESS\n    PERMISSIONS FOR select FULL".into(),
+                start: Position { line: 9, column: 1 },
+                end: Position {
+                    line: 10,
+                    column: 31,
+                },
+            }),
+            message: "Parse error".into(),
+        };
+
+        let formatted = format_statement_error(&error);
+
+        assert_that!(formatted).is_equal_to(
+            "statement 1 at line 9, column 1 to line 10, column 31:\n   9 | DEFINE TABLE quote SCHEMALESS\n  10 |     PERMISSIONS FOR select FULL\n     = Parse error\n",
+        );
+    }
+
+    #[test]
+    fn format_statement_error_without_statement() {
+        let error = StatementError {
+            number: 3,
+            statement: None,
+            message: "transaction conflict".into(),
+        };
+
+        let formatted = format_statement_error(&error);
+
+        assert_that!(formatted).is_equal_to("statement 3:\n  = transaction conflict\n");
+    }
+}
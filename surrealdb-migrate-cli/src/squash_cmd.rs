@@ -0,0 +1,45 @@
+use crate::args::SquashArgs;
+use crate::runner::runner;
+use chrono::NaiveDateTime;
+use color_eyre::Report;
+use color_eyre::eyre::{ContextCompat, eyre};
+use surrealdb_migrate::config::{DbClientConfig, MIGRATION_KEY_FORMAT_STR, RunnerConfig};
+use surrealdb_migrate::db_client::DbConnection;
+
+const DEFAULT_BASELINE_TITLE: &str = "baseline";
+
+pub async fn run(
+    args: SquashArgs,
+    config: RunnerConfig<'_>,
+    db_config: DbClientConfig<'_>,
+    db: &DbConnection,
+) -> Result<(), Report> {
+    let key_arg = args.to.trim();
+    let key = NaiveDateTime::parse_from_str(key_arg, MIGRATION_KEY_FORMAT_STR).map_err(|_| {
+        eyre!("the argument in option '--to {key_arg}' is not a valid migration key. please specify the key in the format yyyymmdd_HHMMSS, e.g. --to 20250103_140520")
+    })?;
+    let title = args
+        .title
+        .unwrap_or_else(|| DEFAULT_BASELINE_TITLE.to_string());
+
+    let migrations_folder = config.migrations_folder.display().to_string();
+    let runner = runner(config);
+
+    println!();
+    println!(
+        r#"Squashing migrations of database "{}/{}" up to {key_arg}..."#,
+        &db_config.namespace, &db_config.database,
+    );
+
+    let baseline = runner.squash(key, &title, db).await?;
+
+    let baseline_filename = baseline.script_path.file_name().wrap_err("migration script path does not have a filename part - should not be reachable - please report a bug!")?.to_string_lossy();
+
+    println!();
+    println!("Migrations located at {migrations_folder}:");
+    println!();
+    println!("New baseline migration {baseline_filename} created.");
+    println!();
+
+    Ok(())
+}
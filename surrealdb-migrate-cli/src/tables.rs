@@ -3,7 +3,10 @@ use cli_table::{Cell, CellStruct, Table, TableDisplay};
 use color_eyre::Report;
 use color_eyre::eyre::WrapErr;
 use surrealdb_migrate::config::MIGRATION_KEY_FORMAT_STR;
-use surrealdb_migrate::migration::{Execution, Migration};
+use surrealdb_migrate::migration::{Execution, FailedAttempt, HistoryEvent, Migration};
+use surrealdb_migrate::result::Migrated;
+use surrealdb_migrate::settings::SettingOrigin;
+use surrealdb_migrate::targets::{TargetOutcome, TargetResult};
 
 fn migrations_table_header() -> Vec<CellStruct> {
     vec![
@@ -67,3 +70,164 @@ pub fn format_migration_table(
         .display()
         .wrap_err("can not format migrations as table")
 }
+
+fn history_table_header() -> Vec<CellStruct> {
+    vec![
+        "Performed at".cell(),
+        "Action".cell(),
+        "Key".cell(),
+        "Title".cell(),
+        "Kind".cell(),
+        "Performed by".cell(),
+        "Execution time".cell(),
+        "Checksum".cell(),
+    ]
+}
+
+pub fn format_history_table(events: Vec<HistoryEvent>) -> Result<TableDisplay, Report> {
+    events
+        .into_iter()
+        .map(|event| {
+            vec![
+                event
+                    .performed_at
+                    .naive_local()
+                    .format("%Y-%m-%d %H:%M:%S")
+                    .to_string()
+                    .cell(),
+                event.action.to_string().cell(),
+                event
+                    .key
+                    .format(MIGRATION_KEY_FORMAT_STR)
+                    .to_string()
+                    .cell(),
+                event.title.cell(),
+                event.kind.to_string().cell(),
+                event.performed_by.cell(),
+                format!("{:?}", event.execution_time)
+                    .cell()
+                    .justify(Justify::Right),
+                event.checksum.to_string().cell(),
+            ]
+        })
+        .table()
+        .title(history_table_header())
+        .display()
+        .wrap_err("can not format history as table")
+}
+
+fn failed_attempts_table_header() -> Vec<CellStruct> {
+    vec![
+        "Key".cell(),
+        "Title".cell(),
+        "Kind".cell(),
+        "Script".cell(),
+        "Attempted at".cell(),
+        "Attempted by".cell(),
+        "Failed statements".cell(),
+    ]
+}
+
+pub fn format_failed_attempts_table(attempts: Vec<FailedAttempt>) -> Result<TableDisplay, Report> {
+    attempts
+        .into_iter()
+        .map(|attempt| {
+            vec![
+                attempt
+                    .key
+                    .format(MIGRATION_KEY_FORMAT_STR)
+                    .to_string()
+                    .cell(),
+                attempt.title.cell(),
+                attempt.kind.to_string().cell(),
+                attempt
+                    .script_path
+                    .file_name()
+                    .map_or_else(String::new, |filename| {
+                        filename.to_string_lossy().to_string()
+                    })
+                    .cell(),
+                attempt
+                    .attempted_at
+                    .naive_local()
+                    .format("%Y-%m-%d %H:%M:%S")
+                    .to_string()
+                    .cell(),
+                attempt.attempted_by.cell(),
+                attempt
+                    .errors
+                    .keys()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+                    .cell(),
+            ]
+        })
+        .table()
+        .title(failed_attempts_table_header())
+        .display()
+        .wrap_err("can not format failed attempts as table")
+}
+
+fn target_results_table_header() -> Vec<CellStruct> {
+    vec!["Database".cell(), "Result".cell(), "Details".cell()]
+}
+
+pub fn format_target_results_table(
+    results: Vec<TargetResult<Migrated>>,
+) -> Result<TableDisplay, Report> {
+    results
+        .into_iter()
+        .map(|result| {
+            let (outcome, details) = match result.outcome {
+                TargetOutcome::Succeeded(Migrated::UpTo(last_applied)) => (
+                    "migrated",
+                    format!("up to {}", last_applied.format(MIGRATION_KEY_FORMAT_STR)),
+                ),
+                TargetOutcome::Succeeded(Migrated::Nothing) => {
+                    ("up to date", "all migrations are applied already".into())
+                },
+                TargetOutcome::Succeeded(Migrated::NoForwardMigrationsFound) => {
+                    ("up to date", "no forward migrations found".into())
+                },
+                TargetOutcome::Failed(error) => ("failed", error.to_string()),
+                TargetOutcome::Skipped => ("skipped", "not migrated after a failure".into()),
+            };
+            vec![result.database.cell(), outcome.cell(), details.cell()]
+        })
+        .table()
+        .title(target_results_table_header())
+        .display()
+        .wrap_err("can not format results of target databases as table")
+}
+
+fn settings_table_header() -> Vec<CellStruct> {
+    vec!["Setting".cell(), "Value".cell(), "Source".cell()]
+}
+
+/// Whether the value of the given setting must not be shown, like passwords,
+/// including the password variable of a record access method, and tokens.
+fn is_secret_setting(key: &str) -> bool {
+    key.ends_with("password") || key.ends_with("token")
+}
+
+pub fn format_settings_table(origins: Vec<SettingOrigin>) -> Result<TableDisplay, Report> {
+    origins
+        .into_iter()
+        .map(|origin| {
+            let value = if is_secret_setting(&origin.key) && !origin.value.is_empty() {
+                "********".to_string()
+            } else {
+                origin.value
+            };
+            vec![
+                origin.key.cell(),
+                value.cell(),
+                origin.source.to_string().cell(),
+            ]
+        })
+        .table()
+        .title(settings_table_header())
+        .display()
+        .wrap_err("can not format settings as table")
+}
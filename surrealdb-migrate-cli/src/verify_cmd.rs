@@ -62,7 +62,7 @@ pub async fn run(
 }
 
 fn print_problematic_migration(pm: &ProblematicMigration) {
-    match pm.problem {
+    match &pm.problem {
         Problem::ChecksumMismatch {
             definition_checksum,
             execution_checksum,
@@ -83,5 +83,16 @@ fn print_problematic_migration(pm: &ProblematicMigration) {
                 "* migration {pm_key} is out of order - last applied migration is {last_applied_key}",
             );
         },
+        Problem::Failed {
+            attempted_at,
+            errors,
+        } => {
+            let pm_key = pm.key.format(MIGRATION_KEY_FORMAT_STR).to_string();
+            let attempted_at = attempted_at.naive_local().format("%Y-%m-%d %H:%M:%S");
+            println!("* migration {pm_key} failed at {attempted_at}");
+            for (statement, message) in errors {
+                println!("  - statement {statement}: {message}");
+            }
+        },
     }
 }
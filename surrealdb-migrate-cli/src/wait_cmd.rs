@@ -0,0 +1,16 @@
+use crate::args::WaitArgs;
+use color_eyre::Report;
+use surrealdb_migrate::config::DbClientConfig;
+use surrealdb_migrate::db_client::wait_for_database;
+
+pub async fn run(args: WaitArgs, db_config: DbClientConfig<'_>) -> Result<(), Report> {
+    println!(
+        r#"Waiting up to {:?} for database "{}/{}" at {}..."#,
+        args.timeout, &db_config.namespace, &db_config.database, &db_config.address
+    );
+
+    wait_for_database(&db_config, args.timeout).await?;
+
+    println!("Database is ready.");
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use crate::fixtures::surmig;
+use snapbox::file;
+
+mod fixtures;
+
+#[test]
+fn show_settings_with_their_sources() {
+    let cmd = surmig()
+        .env("XDG_CONFIG_HOME", "tests/config_cmd/no_user_config")
+        .env("SURMIG_DATABASE_DATABASE", "from_environment")
+        .args([
+            "--config-dir",
+            "tests/config_cmd",
+            "--profile",
+            "ci",
+            "config",
+        ]);
+
+    cmd.assert()
+        .code(0)
+        .stdout_eq(file!("config_cmd/settings_with_sources.stdout"))
+        .stderr_eq("");
+}
+
+#[test]
+fn show_settings_with_missing_config_file_fails() {
+    let cmd = surmig().args([
+        "--config-dir",
+        "tests/config_cmd",
+        "--config",
+        "tests/config_cmd/missing.toml",
+        "config",
+    ]);
+
+    cmd.assert().code(3).stdout_eq("").stderr_eq(
+        "Error: 
+   0: [..]failed to load settings: configuration file tests/config_cmd/missing.toml not found[..]
+...
+",
+    );
+}
+
+#[test]
+fn unknown_environment_variables_are_reported_as_warnings() {
+    let cmd = surmig()
+        .env("XDG_CONFIG_HOME", "tests/config_cmd/no_user_config")
+        .env("SURMIG_DATABSE_ADDRESS", "wss://typo.example.com")
+        .args(["--config-dir", "tests/config_cmd", "config"]);
+
+    cmd.assert().code(0).stderr_eq(
+        "Warning: environment variable SURMIG_DATABSE_ADDRESS does not define a known setting
+",
+    );
+}
+
+#[test]
+fn print_json_schema_of_config_file() {
+    let cmd = surmig().args(["config", "--schema"]);
+
+    cmd.assert()
+        .code(0)
+        .stdout_eq(
+            file!("../../surrealdb-migrate-config/resources/surrealdb-migrate.schema.json").raw(),
+        )
+        .stderr_eq("");
+}
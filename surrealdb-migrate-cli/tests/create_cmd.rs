@@ -4,6 +4,7 @@ use crate::fixtures::files::list_filenames_in_dir;
 use crate::fixtures::surmig;
 use assert_fs::TempDir;
 use asserting::prelude::*;
+use std::fs;
 
 #[test]
 fn create_migration_with_current_date_and_time_and_no_title() {
@@ -147,3 +148,68 @@ fn create_migration_including_down_migration() {
         "20250126_120033_add_some_more_quotes.down.surql",
     ]);
 }
+
+#[test]
+fn create_migration_with_builtin_table_template() {
+    let temp_dir = TempDir::new().expect("failed to create temp dir");
+    let migrations_folder = temp_dir.path().join("migrations");
+
+    let cmd = surmig().args([
+        "--migrations-folder",
+        migrations_folder
+            .to_str()
+            .expect("failed to convert migrations folder path to str"),
+        "create",
+        "--key",
+        "20250126_120033",
+        "define quote table",
+        "--down",
+        "--template",
+        "table",
+        "--author",
+        "some.user",
+    ]);
+
+    cmd.assert()
+        .code(0)
+        .stdout_eq(snapbox::file![
+            "create_cmd/create_migration_with_builtin_table_template.stdout"
+        ])
+        .stderr_eq("");
+
+    let up_content =
+        fs::read_to_string(migrations_folder.join("20250126_120033_define_quote_table.up.surql"))
+            .expect("failed to read up migration");
+    assert_that!(up_content)
+        .contains("-- define quote table")
+        .contains("-- key:     20250126_120033")
+        .contains("-- author:  some.user")
+        .contains("DEFINE TABLE some_table");
+    let down_content =
+        fs::read_to_string(migrations_folder.join("20250126_120033_define_quote_table.down.surql"))
+            .expect("failed to read down migration");
+    assert_that!(down_content).contains("REMOVE TABLE some_table;");
+}
+
+#[test]
+fn create_migration_with_not_existing_template() {
+    let temp_dir = TempDir::new().expect("failed to create temp dir");
+    let migrations_folder = temp_dir.path().join("migrations");
+
+    let cmd = surmig().args([
+        "--migrations-folder",
+        migrations_folder
+            .to_str()
+            .expect("failed to convert migrations folder path to str"),
+        "create",
+        "--template",
+        "not_existing",
+    ]);
+
+    cmd.assert().code(3).stdout_eq("").stderr_eq(snapbox::file![
+        "create_cmd/create_migration_with_not_existing_template.stderr"
+    ]);
+
+    let mig_files = list_filenames_in_dir(&migrations_folder);
+    assert_that!(mig_files.count()).is_equal_to(0);
+}
@@ -0,0 +1,104 @@
+mod fixtures;
+
+use crate::fixtures::db::{prepare_test_database, start_surrealdb_testcontainer};
+use crate::fixtures::files::list_filenames_in_dir;
+use crate::fixtures::surmig;
+use assert_fs::TempDir;
+use asserting::prelude::*;
+use surrealdb_migrate::config::DbClientConfig;
+use surrealdb_migrate::db_client::connect_to_database;
+
+const QUOTE_SCHEMA: &str = "DEFINE TABLE quote SCHEMAFULL; DEFINE FIELD text ON quote TYPE string;";
+
+async fn define_schema(address: &str, database: &str, schema: &str) {
+    let db = connect_to_database(
+        &DbClientConfig::default()
+            .with_address(address)
+            .with_namespace("playground")
+            .with_database(database),
+    )
+    .await
+    .expect("failed to connect to database");
+    db.query(schema)
+        .await
+        .expect("failed to define schema")
+        .check()
+        .expect("failed to define schema");
+}
+
+#[tokio::test]
+async fn diff_databases_with_equal_schema() {
+    let temp_dir = TempDir::new().expect("failed to create temp dir");
+    let migrations_folder = temp_dir.path();
+
+    let db_server = start_surrealdb_testcontainer().await;
+    let db_config = prepare_test_database(&db_server).await;
+    define_schema(&db_config.address, "dev", QUOTE_SCHEMA).await;
+    define_schema(&db_config.address, "test", QUOTE_SCHEMA).await;
+
+    let cmd = surmig().args([
+        "--config-dir",
+        "tests/diff_cmd",
+        "--migrations-folder",
+        migrations_folder
+            .to_str()
+            .expect("failed to convert migrations folder path to str"),
+        "--db-address",
+        &db_config.address,
+        "diff",
+        "--source",
+        "playground/dev",
+        "--target",
+        "playground/test",
+        "--key",
+        "20250614_101520",
+        "define quote",
+    ]);
+
+    cmd.assert()
+        .code(0)
+        .stdout_eq(snapbox::file!("diff_cmd/no_differences.stdout"))
+        .stderr_eq("");
+
+    assert_that!(list_filenames_in_dir(migrations_folder).count()).is_equal_to(0);
+}
+
+#[tokio::test]
+async fn diff_source_database_with_new_table_and_empty_target_database() {
+    let temp_dir = TempDir::new().expect("failed to create temp dir");
+    let migrations_folder = temp_dir.path();
+
+    let db_server = start_surrealdb_testcontainer().await;
+    let db_config = prepare_test_database(&db_server).await;
+    define_schema(&db_config.address, "dev", QUOTE_SCHEMA).await;
+
+    let cmd = surmig().args([
+        "--config-dir",
+        "tests/diff_cmd",
+        "--migrations-folder",
+        migrations_folder
+            .to_str()
+            .expect("failed to convert migrations folder path to str"),
+        "--db-address",
+        &db_config.address,
+        "diff",
+        "--source",
+        "playground/dev",
+        "--target",
+        "playground/test",
+        "--key",
+        "20250614_101520",
+        "define quote",
+    ]);
+
+    cmd.assert()
+        .code(0)
+        .stdout_eq(snapbox::file!("diff_cmd/migrations_created.stdout"))
+        .stderr_eq("");
+
+    let migration_files = list_filenames_in_dir(migrations_folder).collect::<Vec<_>>();
+    assert_that!(migration_files).contains_exactly_in_any_order([
+        "20250614_101520_define_quote.up.surql",
+        "20250614_101520_define_quote.down.surql",
+    ]);
+}
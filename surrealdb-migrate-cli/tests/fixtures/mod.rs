@@ -1,7 +1,7 @@
 pub mod db;
 pub mod files;
 
-use snapbox::cmd::{Command, cargo_bin};
+use snapbox::cmd::Command;
 
 pub fn load_environment_variables() {
     let _env_file =
@@ -9,7 +9,7 @@ pub fn load_environment_variables() {
 }
 
 pub fn surmig() -> Command {
-    Command::new(cargo_bin!("surmig"))
+    Command::new(snapbox::cmd::cargo_bin!("surmig"))
 }
 
 // workaround for false positive 'unused extern crate' warnings until
@@ -24,6 +24,8 @@ mod dummy_extern_uses {
     use color_eyre as _;
     use database_migration as _;
     use log as _;
+    use rpassword as _;
+    use serde_json as _;
     use simplelog as _;
     use surrealdb_migrate as _;
     use surrealdb_migrate_db_client as _;
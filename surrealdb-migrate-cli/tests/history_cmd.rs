@@ -0,0 +1,89 @@
+mod fixtures;
+
+use crate::fixtures::db::{
+    connect_to_test_database_as_database_user, prepare_test_database, start_surrealdb_testcontainer,
+};
+use crate::fixtures::surmig;
+use database_migration::test_dsl::{datetime, key};
+use snapbox::file;
+use std::time::Duration;
+use surrealdb_migrate::checksum::hash_migration_script;
+use surrealdb_migrate::config::DEFAULT_MIGRATIONS_TABLE;
+use surrealdb_migrate::migration::{Execution, Migration, MigrationKind, Reversion};
+use surrealdb_migrate_db_client::{delete_migration_execution, insert_migration_execution};
+
+#[tokio::test]
+async fn history_of_empty_database() {
+    let db_server = start_surrealdb_testcontainer().await;
+    let db_config = prepare_test_database(&db_server).await;
+
+    let cmd = surmig().args([
+        "--config-dir",
+        "tests/history_cmd",
+        "--db-address",
+        &db_config.address,
+        "history",
+    ]);
+
+    cmd.assert()
+        .code(0)
+        .stdout_eq(file!("history_cmd/empty_database.stdout"))
+        .stderr_eq("");
+}
+
+#[tokio::test]
+async fn history_of_one_migration_applied_and_reverted() {
+    let db_server = start_surrealdb_testcontainer().await;
+    let db_config = prepare_test_database(&db_server).await;
+    let db = connect_to_test_database_as_database_user(&db_config).await;
+
+    let migration1 = Migration {
+        key: key("20250103_140520"),
+        title: "define quote table".into(),
+        kind: MigrationKind::Up,
+        script_path:
+            "../fixture/with_down_migrations/migrations/20250103_140520_define_quote_table.surql"
+                .into(),
+    };
+
+    let checksum1 = hash_migration_script(&migration1, "");
+
+    let execution1 = Execution {
+        key: key("20250103_140520"),
+        applied_rank: 1,
+        applied_by: "tester".into(),
+        applied_at: datetime("2025-01-20T09:10:19Z"),
+        checksum: checksum1,
+        execution_time: Duration::from_micros(256),
+    };
+
+    insert_migration_execution(migration1, execution1, DEFAULT_MIGRATIONS_TABLE, &db)
+        .await
+        .expect("failed to insert migration 1");
+
+    let reversion1 = Reversion {
+        key: key("20250103_140520"),
+        reverted_by: "tester".into(),
+        reverted_at: datetime("2025-01-21T14:31:02Z"),
+        execution_time: Duration::from_micros(131),
+    };
+
+    delete_migration_execution(reversion1, DEFAULT_MIGRATIONS_TABLE, &db)
+        .await
+        .expect("failed to delete migration 1");
+
+    let cmd = surmig().args([
+        "--config-dir",
+        "tests/history_cmd",
+        "--db-address",
+        &db_config.address,
+        "history",
+    ]);
+
+    cmd.assert()
+        .code(0)
+        .stdout_eq(file!(
+            "history_cmd/one_migration_applied_and_reverted.stdout"
+        ))
+        .stderr_eq("");
+}
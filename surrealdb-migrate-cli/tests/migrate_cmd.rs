@@ -4,7 +4,9 @@ use crate::fixtures::db::{
     connect_to_test_database_as_database_user, prepare_test_database, start_surrealdb_testcontainer,
 };
 use crate::fixtures::surmig;
+use assert_fs::TempDir;
 use database_migration::test_dsl::{datetime, key};
+use std::fs;
 use std::path::Path;
 use std::time::Duration;
 use surrealdb_migrate::checksum::hash_migration_script;
@@ -214,3 +216,171 @@ async fn migrate_empty_database_up_to_invalid_key() {
         "migrate_cmd/empty_database_up_to_invalid_key.stderr"
     ));
 }
+
+#[tokio::test]
+async fn migrate_with_failing_migration_script_exits_with_script_error() {
+    let db_server = start_surrealdb_testcontainer().await;
+    let db_config = prepare_test_database(&db_server).await;
+
+    let temp_dir = TempDir::new().expect("failed to create temp dir");
+    let migrations_folder = temp_dir.path();
+    fs::write(
+        migrations_folder.join("20250103_140520_failing_migration.surql"),
+        "DEFINE TABLE quote SCHEMAFULL;\nTHROW 'boom';\n",
+    )
+    .expect("failed to write failing migration file");
+
+    let cmd = surmig().args([
+        "--config-dir",
+        "tests/migrate_cmd",
+        "--db-address",
+        &db_config.address,
+        "--migrations-folder",
+        migrations_folder
+            .to_str()
+            .expect("failed to convert migrations folder path to str"),
+        "migrate",
+    ]);
+
+    cmd.assert().code(6).stderr_eq(
+        "
+Failed to execute migration script [..]20250103_140520_failing_migration.surql
+
+statement 2 at line 2, column 1 to line 2, column 12:
+  2 | THROW 'boom'
+    = An error occurred: boom
+
+Error: 
+...
+",
+    );
+}
+
+#[tokio::test]
+async fn migrate_list_of_databases() {
+    let db_server = start_surrealdb_testcontainer().await;
+    let db_config = prepare_test_database(&db_server).await;
+
+    let cmd = surmig().args([
+        "--config-dir",
+        "tests/migrate_cmd",
+        "--db-address",
+        &db_config.address,
+        "migrate",
+        "--databases",
+        "test",
+    ]);
+
+    cmd.assert()
+        .code(0)
+        .stdout_eq(
+            r#"
+Migrating databases of namespace "playground"...
+
+20250103_140520: define quote table (up) applied
+20250103_141521: create some quotes (up) applied
+
+...
+Successfully migrated 1 database(s) of namespace "playground".
+
+"#,
+        )
+        .stderr_eq("");
+}
+
+#[test]
+fn migrate_with_database_list_and_pattern_is_rejected() {
+    let cmd = surmig().args([
+        "--config-dir",
+        "tests/migrate_cmd",
+        "migrate",
+        "--databases",
+        "tenant_a",
+        "--databases-matching",
+        "tenant_*",
+    ]);
+
+    cmd.assert().code(2).stdout_eq("");
+}
+
+#[test]
+fn migrate_ignoring_checksum_in_profile_that_forbids_it_exits_with_config_error() {
+    let cmd = surmig().args([
+        "--config-dir",
+        "tests/migrate_cmd",
+        "--profile",
+        "prod",
+        "migrate",
+        "--ignore-checksum",
+    ]);
+
+    cmd.assert().code(3).stdout_eq("").stderr_eq(
+        "Error: 
+   0: [..]the option '--ignore-checksum' is not allowed by the allow-ignore-checksum setting[..]
+...
+",
+    );
+}
+
+#[test]
+fn migrate_with_token_auth_level_but_without_token_exits_with_config_error() {
+    let cmd = surmig().args([
+        "--config-dir",
+        "tests/migrate_cmd",
+        "--auth-level",
+        "token",
+        "migrate",
+    ]);
+
+    cmd.assert().code(3).stdout_eq("").stderr_eq(
+        "Error: 
+   0: [..]auth-level is Token, but no token is given[..]
+...
+",
+    );
+}
+
+#[test]
+fn migrate_with_access_variable_without_value_is_rejected() {
+    let cmd = surmig().args([
+        "--config-dir",
+        "tests/migrate_cmd",
+        "--access",
+        "migrator",
+        "--access-variable",
+        "email",
+        "migrate",
+    ]);
+
+    cmd.assert().code(2).stdout_eq("").stderr_eq(
+        "error: invalid value 'email' for '--access-variable <NAME=VALUE>': expected NAME=VALUE, but got \"email\"
+...
+",
+    );
+}
+
+#[test]
+fn migrate_with_undefined_profile_exits_with_config_error() {
+    let cmd = surmig().args([
+        "--config-dir",
+        "tests/migrate_cmd",
+        "--profile",
+        "qa",
+        "migrate",
+    ]);
+
+    cmd.assert().code(3).stdout_eq("");
+}
+
+#[test]
+fn migrate_with_unreachable_database_exits_with_connection_error() {
+    let cmd = surmig().args([
+        "--config-dir",
+        "tests/migrate_cmd",
+        "--db-address",
+        "ws://127.0.0.1:1",
+        "migrate",
+    ]);
+
+    cmd.assert().code(10).stdout_eq("");
+}
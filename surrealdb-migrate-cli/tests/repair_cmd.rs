@@ -0,0 +1,74 @@
+mod fixtures;
+
+use crate::fixtures::db::{
+    connect_to_test_database_as_database_user, prepare_test_database, start_surrealdb_testcontainer,
+};
+use crate::fixtures::surmig;
+use asserting::prelude::*;
+use database_migration::test_dsl::{datetime, key};
+use std::iter;
+use surrealdb_migrate::config::DEFAULT_MIGRATIONS_TABLE;
+use surrealdb_migrate::migration::{FailedAttempt, MigrationKind};
+use surrealdb_migrate_db_client::{insert_failed_attempt, select_failed_attempts};
+
+#[tokio::test]
+async fn repair_empty_database() {
+    let db_server = start_surrealdb_testcontainer().await;
+    let db_config = prepare_test_database(&db_server).await;
+
+    let cmd = surmig().args([
+        "--config-dir",
+        "tests/repair_cmd",
+        "--db-address",
+        &db_config.address,
+        "repair",
+    ]);
+
+    cmd.assert()
+        .code(0)
+        .stdout_eq(snapbox::file!("repair_cmd/empty_database.stdout"))
+        .stderr_eq("");
+}
+
+#[tokio::test]
+async fn repair_database_with_one_failed_attempt() {
+    let db_server = start_surrealdb_testcontainer().await;
+    let db_config = prepare_test_database(&db_server).await;
+    let db = connect_to_test_database_as_database_user(&db_config).await;
+
+    let attempt = FailedAttempt {
+        key: key("20250103_140520"),
+        title: "define quote table".into(),
+        kind: MigrationKind::Up,
+        script_path:
+            "../fixtures/with_down_migrations/migrations/20250103_140520_define_quote_table.up.surql"
+                .into(),
+        checksum: "1234567".parse().expect("valid checksum"),
+        attempted_by: "tester".into(),
+        attempted_at: datetime("2025-01-20T09:10:19Z"),
+        errors: iter::once((1, "Parse error: unexpected token".to_string())).collect(),
+    };
+
+    insert_failed_attempt(attempt, DEFAULT_MIGRATIONS_TABLE, &db)
+        .await
+        .expect("failed to insert failed attempt");
+
+    let cmd = surmig().args([
+        "--config-dir",
+        "tests/repair_cmd",
+        "--db-address",
+        &db_config.address,
+        "repair",
+    ]);
+
+    cmd.assert()
+        .code(0)
+        .stdout_eq(snapbox::file!("repair_cmd/one_failed_attempt.stdout"))
+        .stderr_eq("");
+
+    let failed_attempts = select_failed_attempts(DEFAULT_MIGRATIONS_TABLE, &db)
+        .await
+        .expect("failed to select failed attempts");
+
+    assert_that!(failed_attempts).is_empty();
+}
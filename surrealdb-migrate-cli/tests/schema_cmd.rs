@@ -0,0 +1,112 @@
+mod fixtures;
+
+use crate::fixtures::db::{
+    connect_to_test_database_as_database_user, prepare_test_database, start_surrealdb_testcontainer,
+};
+use crate::fixtures::files::list_filenames_in_dir;
+use crate::fixtures::surmig;
+use assert_fs::TempDir;
+use asserting::prelude::*;
+use database_migration::test_dsl::key;
+use std::fs;
+use surrealdb_migrate::config::RunnerConfig;
+use surrealdb_migrate::runner::MigrationRunner;
+
+#[tokio::test]
+async fn schema_with_empty_schema_folder_and_empty_database() {
+    let temp_dir = TempDir::new().expect("failed to create temp dir");
+    let migrations_folder = temp_dir.path().join("migrations");
+    let schema_folder = temp_dir.path().join("schema");
+    fs::create_dir_all(&schema_folder).expect("failed to create schema folder");
+
+    let db_server = start_surrealdb_testcontainer().await;
+    let db_config = prepare_test_database(&db_server).await;
+
+    let cmd = surmig()
+        .env(
+            "SURMIG_FILES_SCHEMA_FOLDER",
+            schema_folder
+                .to_str()
+                .expect("failed to convert schema folder path to str"),
+        )
+        .args([
+            "--config-dir",
+            "tests/schema_cmd",
+            "--migrations-folder",
+            migrations_folder
+                .to_str()
+                .expect("failed to convert migrations folder path to str"),
+            "--db-address",
+            &db_config.address,
+            "schema",
+            "--apply",
+        ]);
+
+    cmd.assert()
+        .code(0)
+        .stdout_eq(snapbox::file!("schema_cmd/empty_schema.stdout"))
+        .stderr_eq("");
+
+    assert_that!(migrations_folder.exists()).is_false();
+}
+
+#[tokio::test]
+async fn schema_apply_new_table_to_empty_database() {
+    let temp_dir = TempDir::new().expect("failed to create temp dir");
+    let migrations_folder = temp_dir.path().join("migrations");
+    let schema_folder = temp_dir.path().join("schema");
+    fs::create_dir_all(&schema_folder).expect("failed to create schema folder");
+    fs::write(
+        schema_folder.join("quote.surql"),
+        "DEFINE TABLE quote SCHEMAFULL;\nDEFINE FIELD text ON quote TYPE string;\n",
+    )
+    .expect("failed to write schema definition");
+
+    let db_server = start_surrealdb_testcontainer().await;
+    let db_config = prepare_test_database(&db_server).await;
+
+    let cmd = surmig()
+        .env(
+            "SURMIG_FILES_SCHEMA_FOLDER",
+            schema_folder
+                .to_str()
+                .expect("failed to convert schema folder path to str"),
+        )
+        .args([
+            "--config-dir",
+            "tests/schema_cmd",
+            "--migrations-folder",
+            migrations_folder
+                .to_str()
+                .expect("failed to convert migrations folder path to str"),
+            "--db-address",
+            &db_config.address,
+            "schema",
+            "--apply",
+            "--key",
+            "20250614_101520",
+            "define quote",
+        ]);
+
+    cmd.assert()
+        .code(0)
+        .stdout_eq(snapbox::file!("schema_cmd/apply_schema.stdout"))
+        .stderr_eq("");
+
+    let migration_files = list_filenames_in_dir(&migrations_folder).collect::<Vec<_>>();
+    assert_that!(migration_files).contains_exactly_in_any_order([
+        "20250614_101520_define_quote.up.surql",
+        "20250614_101520_define_quote.down.surql",
+    ]);
+
+    let db = connect_to_test_database_as_database_user(&db_config).await;
+    let runner = MigrationRunner::new(
+        RunnerConfig::default().with_migrations_folder(migrations_folder.as_path()),
+    );
+    let applied = runner
+        .list_applied_migrations(&db)
+        .await
+        .expect("failed to list applied migrations");
+    let applied_keys = applied.iter().map(|exec| exec.key).collect::<Vec<_>>();
+    assert_that!(applied_keys).contains_exactly([key("20250614_101520")]);
+}
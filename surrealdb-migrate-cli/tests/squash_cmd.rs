@@ -0,0 +1,113 @@
+mod fixtures;
+
+use crate::fixtures::db::{
+    connect_to_test_database_as_database_user, prepare_test_database, start_surrealdb_testcontainer,
+};
+use crate::fixtures::files::list_filenames_in_dir;
+use crate::fixtures::surmig;
+use assert_fs::TempDir;
+use asserting::prelude::*;
+use database_migration::test_dsl::key;
+use std::fs;
+use std::path::Path;
+use surrealdb_migrate::config::RunnerConfig;
+use surrealdb_migrate::runner::MigrationRunner;
+
+fn copy_migration_files(source_folder: &Path, migrations_folder: &Path) {
+    for dir_entry in fs::read_dir(source_folder)
+        .expect("failed to read migrations folder")
+        .flatten()
+    {
+        let src_path = dir_entry.path();
+        if src_path.is_file() {
+            let filename = src_path.file_name().expect("src path has no filename");
+            fs::copy(&src_path, migrations_folder.join(filename))
+                .expect("failed to copy migration file");
+        }
+    }
+}
+
+#[tokio::test]
+async fn squash_database_migrated_up_to_20250103_140520() {
+    let temp_dir = TempDir::new().expect("failed to create temp dir");
+    let migrations_folder = temp_dir.path();
+    copy_migration_files(
+        Path::new("../fixtures/with_down_migrations/migrations"),
+        migrations_folder,
+    );
+
+    let db_server = start_surrealdb_testcontainer().await;
+    let db_config = prepare_test_database(&db_server).await;
+    let db = connect_to_test_database_as_database_user(&db_config).await;
+
+    let runner =
+        MigrationRunner::new(RunnerConfig::default().with_migrations_folder(migrations_folder));
+    runner
+        .migrate_to(key("20250103_140520"), &db)
+        .await
+        .expect("failed to run migrate");
+
+    let cmd = surmig().args([
+        "--config-dir",
+        "tests/squash_cmd",
+        "--migrations-folder",
+        migrations_folder
+            .to_str()
+            .expect("failed to convert migrations folder path to str"),
+        "--db-address",
+        &db_config.address,
+        "squash",
+        "--to",
+        "20250103_140520",
+        "squashed schema",
+    ]);
+
+    cmd.assert()
+        .code(0)
+        .stdout_eq(snapbox::file!(
+            "squash_cmd/database_migrated_up_to_20250103_140520.stdout"
+        ))
+        .stderr_eq("");
+
+    let baseline_files = list_filenames_in_dir(migrations_folder)
+        .filter(|filename| filename.ends_with(".baseline.surql"))
+        .collect::<Vec<_>>();
+    assert_that!(baseline_files)
+        .contains_exactly(["20250103_140520_squashed_schema.baseline.surql"]);
+}
+
+#[tokio::test]
+async fn squash_empty_database() {
+    let temp_dir = TempDir::new().expect("failed to create temp dir");
+    let migrations_folder = temp_dir.path();
+    copy_migration_files(
+        Path::new("../fixtures/with_down_migrations/migrations"),
+        migrations_folder,
+    );
+
+    let db_server = start_surrealdb_testcontainer().await;
+    let db_config = prepare_test_database(&db_server).await;
+
+    let cmd = surmig().args([
+        "--config-dir",
+        "tests/squash_cmd",
+        "--migrations-folder",
+        migrations_folder
+            .to_str()
+            .expect("failed to convert migrations folder path to str"),
+        "--db-address",
+        &db_config.address,
+        "squash",
+        "--to",
+        "20250103_140520",
+    ]);
+
+    cmd.assert()
+        .code(5)
+        .stdout_eq(snapbox::file!("squash_cmd/empty_database.stdout"))
+        .stderr_eq(snapbox::file!("squash_cmd/empty_database.stderr"));
+
+    let baseline_files = list_filenames_in_dir(migrations_folder)
+        .filter(|filename| filename.ends_with(".baseline.surql"));
+    assert_that!(baseline_files.count()).is_equal_to(0);
+}
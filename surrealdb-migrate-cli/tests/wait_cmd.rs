@@ -0,0 +1,55 @@
+mod fixtures;
+
+use crate::fixtures::db::{prepare_test_database, start_surrealdb_testcontainer};
+use crate::fixtures::surmig;
+use snapbox::file;
+
+#[tokio::test]
+async fn wait_for_running_database() {
+    let db_server = start_surrealdb_testcontainer().await;
+    let db_config = prepare_test_database(&db_server).await;
+
+    let cmd = surmig().args([
+        "--config-dir",
+        "tests/wait_cmd",
+        "--db-address",
+        &db_config.address,
+        "wait",
+        "--timeout",
+        "10s",
+    ]);
+
+    cmd.assert()
+        .code(0)
+        .stdout_eq(file!("wait_cmd/running_database.stdout"));
+}
+
+#[test]
+fn wait_for_unreachable_database_times_out() {
+    let cmd = surmig().args([
+        "--config-dir",
+        "tests/wait_cmd",
+        "--db-address",
+        "ws://127.0.0.1:1",
+        "wait",
+        "--timeout",
+        "500ms",
+    ]);
+
+    cmd.assert()
+        .code(10)
+        .stdout_eq(file!("wait_cmd/unreachable_database.stdout"));
+}
+
+#[test]
+fn wait_with_invalid_timeout_fails() {
+    let cmd = surmig().args([
+        "--config-dir",
+        "tests/wait_cmd",
+        "wait",
+        "--timeout",
+        "soon",
+    ]);
+
+    cmd.assert().code(2).stdout_eq("");
+}
@@ -0,0 +1,86 @@
+//! Durations in settings, like `500ms`, `10s`, `5m` or `1h`.
+
+use serde::Deserializer;
+use serde::de::{Unexpected, Visitor};
+use std::fmt::Formatter;
+use std::time::Duration;
+
+/// Parses a duration given as a non-negative integer followed by one of the
+/// units `ms`, `s`, `m` or `h`, e.g. `"500ms"` or `"10s"`.
+///
+/// A number without unit is interpreted as seconds.
+pub fn parse_duration(value: &str) -> Result<Duration, String> {
+    let value = value.trim();
+    let unit_start = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (amount, unit) = value.split_at(unit_start);
+    let amount = amount.parse::<u64>().ok();
+    let duration = match (amount, unit.trim()) {
+        (Some(amount), "ms") => Some(Duration::from_millis(amount)),
+        (Some(amount), "s" | "") => Some(Duration::from_secs(amount)),
+        (Some(amount), "m") => amount.checked_mul(60).map(Duration::from_secs),
+        (Some(amount), "h") => amount.checked_mul(3600).map(Duration::from_secs),
+        _ => None,
+    };
+    duration.ok_or_else(|| {
+        format!(
+            "invalid duration \"{value}\", expected a number followed by one of the units ms, s, m or h, e.g. \"10s\""
+        )
+    })
+}
+
+pub fn duration_from_string<'de, D>(deserializer: D) -> Result<Duration, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_str(DurationVisitor)
+}
+
+pub fn optional_duration_from_string<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_option(OptionalDurationVisitor)
+}
+
+struct DurationVisitor;
+
+impl Visitor<'_> for DurationVisitor {
+    type Value = Duration;
+
+    fn expecting(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        formatter.write_str("a duration like \"500ms\", \"10s\", \"5m\" or \"1h\"")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        parse_duration(v).map_err(|_| serde::de::Error::invalid_value(Unexpected::Str(v), &self))
+    }
+}
+
+struct OptionalDurationVisitor;
+
+impl<'de> Visitor<'de> for OptionalDurationVisitor {
+    type Value = Option<Duration>;
+
+    fn expecting(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        DurationVisitor.expecting(formatter)
+    }
+
+    fn visit_none<E>(self) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(None)
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        duration_from_string(deserializer).map(Some)
+    }
+}
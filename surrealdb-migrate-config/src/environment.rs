@@ -0,0 +1,119 @@
+//! Reading settings from `SURMIG_*` environment variables.
+
+use crate::schema::{KnownSetting, SettingType, known_settings};
+use crate::{PROFILE_ENVIRONMENT_VAR, env};
+use config::{Map, Value, ValueKind};
+use database_migration::error::Error;
+
+/// Prefix of all environment variables that define settings.
+pub const ENVIRONMENT_VAR_PREFIX: &str = "SURMIG_";
+
+/// A setting defined by an environment variable.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnvironmentSetting {
+    /// The name of the environment variable.
+    pub variable: String,
+    /// The key of the setting including its section, e.g. `database.address`.
+    pub key: String,
+    /// The value of the environment variable converted to the type of the
+    /// setting.
+    pub value: ValueKind,
+}
+
+/// Reads the settings from the `SURMIG_*` environment variables.
+///
+/// Returns an error if the value of an environment variable can not be
+/// converted to the type of the setting it defines.
+pub fn read_environment() -> Result<Vec<EnvironmentSetting>, Error> {
+    let mut settings = Vec::new();
+    for (variable, value) in env::vars() {
+        let Some(setting) = find_setting(&variable) else {
+            continue;
+        };
+        settings.push(EnvironmentSetting {
+            key: format!("{}.{}", setting.section, setting.key),
+            value: convert_value(&variable, value, setting.setting_type)?,
+            variable,
+        });
+    }
+    settings.sort_unstable_by(|setting1, setting2| setting1.variable.cmp(&setting2.variable));
+    Ok(settings)
+}
+
+/// Returns the environment variables with the `SURMIG_` prefix that do not
+/// define a known setting, sorted by name.
+///
+/// Such variables are ignored when loading the settings. Usually they are
+/// misspelled names of settings.
+pub fn unknown_environment_variables() -> Vec<String> {
+    let mut unknown_variables = env::vars()
+        .map(|(variable, _)| variable)
+        .filter(|variable| {
+            variable.starts_with(ENVIRONMENT_VAR_PREFIX)
+                && variable != PROFILE_ENVIRONMENT_VAR
+                && find_setting(variable).is_none()
+        })
+        .collect::<Vec<_>>();
+    unknown_variables.sort_unstable();
+    unknown_variables
+}
+
+/// Returns the name of the environment variable that defines the given
+/// setting.
+pub fn environment_variable_name(section: &str, key: &str) -> String {
+    format!("{ENVIRONMENT_VAR_PREFIX}{section}_{key}")
+        .replace('-', "_")
+        .to_ascii_uppercase()
+}
+
+fn find_setting(variable: &str) -> Option<&'static KnownSetting> {
+    known_settings()
+        .iter()
+        .find(|setting| environment_variable_name(&setting.section, &setting.key) == variable)
+}
+
+fn convert_value(
+    variable: &str,
+    value: String,
+    setting_type: SettingType,
+) -> Result<ValueKind, Error> {
+    match setting_type {
+        SettingType::Boolean => parse_bool(&value).map(ValueKind::Boolean).ok_or_else(|| {
+            Error::Configuration(format!(
+                "invalid value of environment variable {variable}: expected a boolean, like true or false, but got \"{value}\""
+            ))
+        }),
+        SettingType::Integer => value.trim().parse().map(ValueKind::U64).map_err(|_| {
+            Error::Configuration(format!(
+                "invalid value of environment variable {variable}: expected a non-negative integer, but got \"{value}\""
+            ))
+        }),
+        SettingType::String => Ok(ValueKind::String(value)),
+        SettingType::Table => parse_table(&value).map(ValueKind::Table).ok_or_else(|| {
+            Error::Configuration(format!(
+                "invalid value of environment variable {variable}: expected a comma separated list of name=value pairs, but got \"{value}\""
+            ))
+        }),
+    }
+}
+
+/// Parses a comma separated list of `name=value` pairs.
+fn parse_table(value: &str) -> Option<Map<String, Value>> {
+    value
+        .split(',')
+        .filter(|entry| !entry.trim().is_empty())
+        .map(|entry| {
+            let (name, value) = entry.split_once('=')?;
+            let name = name.trim();
+            (!name.is_empty()).then(|| (name.to_string(), Value::from(value.trim())))
+        })
+        .collect()
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "true" | "yes" | "on" | "1" => Some(true),
+        "false" | "no" | "off" | "0" => Some(false),
+        _ => None,
+    }
+}
@@ -0,0 +1,74 @@
+//! Interpolation of environment variables in the values of the configuration
+//! files.
+
+use crate::env;
+use std::fmt::{Display, Formatter};
+
+/// Reasons why the interpolation of a value failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InterpolationError {
+    /// The referenced environment variable is not set and no default value
+    /// is given.
+    UnsetVariable(String),
+    /// A variable reference is not terminated by a closing brace.
+    Unterminated,
+    /// A variable reference does not contain a variable name.
+    MissingName,
+}
+
+impl Display for InterpolationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnsetVariable(name) => write!(f, "environment variable {name} is not set"),
+            Self::Unterminated => f.write_str("missing closing brace of variable reference"),
+            Self::MissingName => f.write_str("missing variable name in variable reference"),
+        }
+    }
+}
+
+/// Replaces references to environment variables in the given value with the
+/// value of the referenced variable.
+///
+/// Supported are `${VAR}`, which fails if the variable is not set, and
+/// `${VAR:-default}`, which uses the given default if the variable is not set
+/// or empty. `$$` is replaced with a single `$`. A `$` that is not followed by
+/// `{` or `$` is kept as is.
+pub fn interpolate(value: &str) -> Result<String, InterpolationError> {
+    let mut interpolated = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(dollar) = rest.find('$') {
+        interpolated.push_str(&rest[..dollar]);
+        let after_dollar = &rest[dollar + 1..];
+        if let Some(after_escape) = after_dollar.strip_prefix('$') {
+            interpolated.push('$');
+            rest = after_escape;
+        } else if let Some(reference) = after_dollar.strip_prefix('{') {
+            let closing = reference
+                .find('}')
+                .ok_or(InterpolationError::Unterminated)?;
+            interpolated.push_str(&resolve_reference(&reference[..closing])?);
+            rest = &reference[closing + 1..];
+        } else {
+            interpolated.push('$');
+            rest = after_dollar;
+        }
+    }
+    interpolated.push_str(rest);
+    Ok(interpolated)
+}
+
+fn resolve_reference(reference: &str) -> Result<String, InterpolationError> {
+    let (name, default) = match reference.split_once(":-") {
+        Some((name, default)) => (name.trim(), Some(default)),
+        None => (reference.trim(), None),
+    };
+    if name.is_empty() {
+        return Err(InterpolationError::MissingName);
+    }
+    match (env::var(name), default) {
+        (Ok(value), Some(default)) if value.is_empty() => Ok(default.to_string()),
+        (Ok(value), _) => Ok(value),
+        (Err(_), Some(default)) => Ok(default.to_string()),
+        (Err(_), None) => Err(InterpolationError::UnsetVariable(name.to_string())),
+    }
+}
@@ -13,21 +13,57 @@
 //! settings which shall get a value different from the default value and omit
 //! those settings where the default value is suitable for the application.
 //!
-//! First the settings are loaded from a configuration file with the name
-//! `surrealdb-migrate.toml`. By default, this configuration file must be
-//! located in the current working directory. To load the configuration file
-//! from a different directory, the environment variable
-//! `SURREALDB_MIGRATE_CONFIG_DIR` can be set to point to the directory where
-//! the configuration file is located. For example:
+//! First the settings are loaded from a hierarchy of configuration files
+//! named `surrealdb-migrate.toml`. Each configuration file overwrites the
+//! settings of the files read before:
+//!
+//! 1. the user configuration file in the subfolder `surrealdb-migrate` of the
+//!    user's config directory (`$XDG_CONFIG_HOME`, `$HOME/.config` or
+//!    `%APPDATA%`)
+//! 2. the table `[package.metadata.surrealdb-migrate]` in the `Cargo.toml` of
+//!    the project directory
+//! 3. the project configuration file in the project directory
+//! 4. configuration files given explicitly to the [`SettingsLoader`]
+//!
+//! The project directory is the first directory containing a configuration
+//! file or a `Cargo.toml` with settings, starting at the current working
+//! directory and walking up its parent directories. To use a different
+//! directory, the environment variable `SURREALDB_MIGRATE_CONFIG_DIR` can be
+//! set to point to it. For example:
 //!
 //! ```dotenv
 //! SURREALDB_MIGRATE_CONFIG_DIR="my_application/config"
 //! ```
 //!
-//! The configuration does not need to define all available settings. If a
-//! setting is not present in the configuration file the default value is used.
-//! If the configuration file is not present or can not be found, the default
-//! values for all settings are used.
+//! Instead of `surrealdb-migrate.toml` the configuration file can also be
+//! written in YAML as `surrealdb-migrate.yaml` (or `.yml`) or in JSON as
+//! `surrealdb-migrate.json`. Only one configuration file is allowed per
+//! directory.
+//!
+//! The configuration files do not need to define all available settings. If a
+//! setting is not present in any configuration file the default value is used.
+//! If no configuration file is present, the default values for all settings
+//! are used.
+//!
+//! String values in the configuration files can reference environment
+//! variables as `${VAR}` or `${VAR:-default}`. The reference is replaced with
+//! the value of the variable, or with the default value if the variable is
+//! not set or empty. Loading the settings fails with an
+//! [`Error::Configuration`] if a referenced variable is not set and no default
+//! value is given. `$$` is replaced with a single `$`.
+//!
+//! ```toml
+//! [database]
+//! address = "wss://${DB_HOST}:8000"
+//! namespace = "${TEAM}_app"
+//! ```
+//!
+//! Unknown settings in a configuration file fail loading the settings with an
+//! [`Error::Configuration`] that suggests a known setting with a similar name.
+//! The JSON Schema returned by [`config_file_schema()`] describes all
+//! available settings. It is also provided as the file
+//! [surrealdb-migrate.schema.json] for validation and autocompletion in
+//! editors.
 //!
 //! All available settings with their default values are listed in the example
 //! configuration file [surrealdb-migrate.default.toml].
@@ -40,9 +76,28 @@
 //! is used. If a setting is neither specified in the configuration file nor set
 //! via an environment variable, the default value is used.
 //!
+//! The values of environment variables are converted to the type of the
+//! setting they define, e.g. `true` or `false` for boolean settings. Variables
+//! with the prefix `SURMIG_` that do not define a known setting are ignored.
+//! They are logged as warnings and can be listed with
+//! [`unknown_environment_variables()`].
+//!
 //! All available environment variables that define configuration settings are
 //! listed in the example dotenv file [default.env].
 //!
+//! ## Profiles
+//!
+//! The configuration file can define named profiles in the `profiles` table,
+//! e.g. `[profiles.prod.database]`. A profile contains only those settings of
+//! the sections `migration`, `files` and `database` that differ from the base
+//! settings. When a profile is selected, its settings overwrite the base
+//! settings from the configuration file. Environment variables take precedence
+//! over the settings of the profile.
+//!
+//! A profile is selected by the environment variable `SURMIG_PROFILE` or by
+//! using the [`load_profile()`](Settings::load_profile) or
+//! [`load_profile_from_dir()`](Settings::load_profile_from_dir) function.
+//!
 //! ## Usage
 //!
 //! To load the settings via the mechanism described in the previous chapter,
@@ -80,6 +135,27 @@
 //! }
 //! ```
 //!
+//! To add further configuration files or to find out where the value of each
+//! setting comes from, the [`SettingsLoader`] is used.
+//!
+//! ```no_run
+//! use database_migration::error::Error;
+//! use surrealdb_migrate_config::SettingsLoader;
+//!
+//! fn main() -> Result<(), Error> {
+//!     let (_settings, origins) = SettingsLoader::new()
+//!         .with_config_file("local.toml")
+//!         .with_profile("dev")
+//!         .load_with_origins()?;
+//!
+//!     for origin in origins {
+//!         println!("{} = {} ({})", origin.key, origin.value, origin.source);
+//!     }
+//!
+//!     Ok(())
+//! }
+//! ```
+//!
 //! The loaded settings can then be used to get a [`DbClientConfig`] and a
 //! [`RunnerConfig`].
 //!
@@ -98,118 +174,246 @@
 //! ```
 //!
 //! [default.env]: https://github.com/innoave/surrealdb-migrate/blob/main/surrealdb-migrate-config/resources/default.env
+//! [surrealdb-migrate.schema.json]: https://github.com/innoave/surrealdb-migrate/blob/main/surrealdb-migrate-config/resources/surrealdb-migrate.schema.json
 //! [surrealdb-migrate.default.toml]: https://github.com/innoave/surrealdb-migrate/blob/main/surrealdb-migrate-config/resources/surrealdb-migrate.default.toml
 //! [`surrealdb-migrate`]: https://docs.rs/surrealdb-migrate/0.1.0
 
+mod duration;
 mod env;
+mod environment;
+mod interpolate;
+mod loader;
+mod password;
+mod schema;
+
+pub use duration::parse_duration;
+pub use environment::{ENVIRONMENT_VAR_PREFIX, unknown_environment_variables};
+pub use loader::{SettingOrigin, SettingSource, SettingsLoader, USER_CONFIG_SUBFOLDER};
+pub use schema::config_file_schema;
 
-use config::{Config, File, FileFormat};
-use database_migration::config::{DbAuthLevel, DbClientConfig, RunnerConfig};
+use database_migration::config::{
+    DbAuthLevel, DbCapabilities, DbClientConfig, REDACTED_PASSWORD, RunnerConfig,
+};
 use database_migration::error::Error;
+use schemars::JsonSchema;
 use serde::de::{Unexpected, Visitor};
 use serde::{Deserialize, Deserializer};
-use std::collections::HashMap;
-use std::fmt::{Formatter, Write as _};
+use std::collections::BTreeMap;
+use std::fmt::{Debug, Formatter};
 use std::path::Path;
+use std::time::Duration;
 
 pub const CONFIG_DIR_ENVIRONMENT_VAR: &str = "SURREALDB_MIGRATE_CONFIG_DIR";
+pub const PROFILE_ENVIRONMENT_VAR: &str = "SURMIG_PROFILE";
 pub const CONFIG_FILENAME: &str = "surrealdb-migrate";
 
 const DEFAULT_SETTINGS: &str = include_str!("../resources/surrealdb-migrate.default.toml");
 
-#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Deserialize, JsonSchema, Debug, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
 pub struct Settings {
+    /// Settings for applying and verifying migrations.
     pub migration: MigrationSettings,
+    /// Settings for the migration files.
     pub files: FilesSettings,
+    /// Settings for the connection to the database.
     pub database: DatabaseSettings,
 }
 
-#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
-#[serde(rename_all = "kebab-case")]
+#[derive(Deserialize, JsonSchema, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
 pub struct MigrationSettings {
+    /// Do not verify checksums of applied migrations.
     pub ignore_checksum: bool,
+    /// Allow execution of migration scripts in any order.
     pub ignore_order: bool,
+    /// Whether ignoring checksums is allowed at all.
+    pub allow_ignore_checksum: bool,
 }
 
-#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
-#[serde(rename_all = "kebab-case")]
+#[derive(Deserialize, JsonSchema, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
 pub struct FilesSettings {
+    /// Path to the folder that holds the migration scripts.
     pub migrations_folder: String,
+    /// Path to the folder that holds the migration scripts applied at root
+    /// level.
+    #[serde(default)]
+    pub root_migrations_folder: Option<String>,
+    /// Path to the folder that holds the migration scripts applied at
+    /// namespace level.
+    #[serde(default)]
+    pub namespace_migrations_folder: Option<String>,
+    /// Filename extension of migration script files.
     pub script_extension: String,
+    /// Filename extension of forward migration scripts.
     pub up_script_extension: String,
+    /// Filename extension of backward migration scripts.
     pub down_script_extension: String,
+    /// Patterns of files excluded when scanning the migrations folder,
+    /// separated by `|`.
     pub exclude: String,
+    /// Path to the folder that holds custom templates for new migrations.
+    pub template_folder: String,
+    /// Path to the folder that holds the declarative schema definition files.
+    pub schema_folder: String,
 }
 
-#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
-#[serde(rename_all = "kebab-case")]
+#[derive(Deserialize, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
 pub struct DatabaseSettings {
+    /// Name of the table where the migrations are tracked.
     pub migrations_table: String,
+    /// Address of the database server.
     pub address: String,
+    /// Username of the user applying the migrations.
     pub username: String,
+    /// Password of the user applying the migrations.
     pub password: String,
+    /// File to read the password from, e.g. a mounted Docker or Kubernetes
+    /// secret. Overwrites the `password` setting if set.
+    #[serde(default)]
+    pub password_file: Option<String>,
+    /// Command whose output is used as the password. Overwrites the
+    /// `password` setting if set.
+    #[serde(default)]
+    pub password_command: Option<String>,
+    /// Authentication level of the user, or the method of authentication:
+    /// `Record` signs in via a record access method, `Token` authenticates
+    /// with an existing token.
     #[serde(deserialize_with = "db_auth_level_from_string")]
+    #[schemars(with = "DbAuthLevelSchema")]
     pub auth_level: DbAuthLevel,
+    /// Name of the record access method used with the auth level `Record`.
+    #[serde(default)]
+    pub access: String,
+    /// Variables passed to the `SIGNIN` clause of the record access method,
+    /// e.g. `email` and `password`.
+    #[serde(default)]
+    pub access_variables: BTreeMap<String, String>,
+    /// Token used to authenticate with the auth level `Token`.
+    #[serde(default)]
+    pub token: String,
+    /// Maximum time for one attempt to connect and sign in, e.g. `10s`.
+    #[serde(deserialize_with = "duration::duration_from_string")]
+    #[schemars(with = "String")]
+    pub connect_timeout: Duration,
+    /// Number of times connecting is retried if the database server is not
+    /// reachable.
+    pub connect_retries: u32,
+    /// Time to wait before the first retry to connect, e.g. `1s`. The interval
+    /// doubles after each retry up to a maximum of one minute.
+    #[serde(deserialize_with = "duration::duration_from_string")]
+    #[schemars(with = "String")]
+    pub connect_retry_interval: Duration,
+    /// Namespace of the database where the migrations are applied.
     pub namespace: String,
+    /// Name of the database where the migrations are applied.
     pub database: String,
+    /// Capacity of the channels to the database.
     pub capacity: usize,
+    /// Maximum time a query may take, e.g. `1h`. Only applies to embedded
+    /// database engines.
+    #[serde(default, deserialize_with = "duration::optional_duration_from_string")]
+    #[schemars(with = "Option<String>")]
+    pub query_timeout: Option<Duration>,
+    /// Maximum time a transaction may take, e.g. `1h`. Only applies to
+    /// embedded database engines.
+    #[serde(default, deserialize_with = "duration::optional_duration_from_string")]
+    #[schemars(with = "Option<String>")]
+    pub transaction_timeout: Option<Duration>,
+    /// Whether namespaces, databases and tables must be defined before they
+    /// can be used. Only applies to embedded database engines.
+    pub strict: bool,
+    /// Capabilities allowed to be used by queries. Only applies to embedded
+    /// database engines.
+    #[serde(deserialize_with = "db_capabilities_from_string")]
+    #[schemars(with = "DbCapabilitiesSchema")]
+    pub capabilities: DbCapabilities,
+    /// PEM file with the certificates of the certificate authorities trusted
+    /// to sign the certificate of the database server.
+    #[serde(default)]
+    pub tls_ca_file: Option<String>,
+    /// PEM file with the client certificate presented to the database server.
+    #[serde(default)]
+    pub tls_client_cert_file: Option<String>,
+    /// PEM file with the private key of the client certificate.
+    #[serde(default)]
+    pub tls_client_key_file: Option<String>,
+    /// Name the certificate of the database server is verified against,
+    /// instead of the host name of the address.
+    #[serde(default)]
+    pub tls_server_name: Option<String>,
+    /// Accept any certificate of the database server. For development only!
+    pub tls_accept_invalid_certs: bool,
 }
 
-fn read_environment() -> String {
-    const MIGRATION_PREFIX: &str = "SURMIG_MIGRATION_";
-    const FILES_PREFIX: &str = "SURMIG_FILES_";
-    const DATABASE_PREFIX: &str = "SURMIG_DATABASE_";
-
-    let mut migration = HashMap::new();
-    let mut files = HashMap::new();
-    let mut database = HashMap::new();
-
-    for (key, val) in env::vars() {
-        if key.starts_with(MIGRATION_PREFIX) {
-            let offset = MIGRATION_PREFIX.len();
-            migration.insert(to_kebab_case(&key, offset), val);
-        } else if key.starts_with(DATABASE_PREFIX) {
-            let offset = DATABASE_PREFIX.len();
-            database.insert(to_kebab_case(&key, offset), val);
-        } else if key.starts_with(FILES_PREFIX) {
-            let offset = FILES_PREFIX.len();
-            files.insert(to_kebab_case(&key, offset), val);
-        }
+impl Debug for DatabaseSettings {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DatabaseSettings")
+            .field("migrations_table", &self.migrations_table)
+            .field("address", &self.address)
+            .field("username", &self.username)
+            .field("password", &REDACTED_PASSWORD)
+            .field("password_file", &self.password_file)
+            .field("password_command", &self.password_command)
+            .field("auth_level", &self.auth_level)
+            .field("access", &self.access)
+            .field(
+                "access_variables",
+                &self.access_variables.keys().collect::<Vec<_>>(),
+            )
+            .field("token", &REDACTED_PASSWORD)
+            .field("connect_timeout", &self.connect_timeout)
+            .field("connect_retries", &self.connect_retries)
+            .field("connect_retry_interval", &self.connect_retry_interval)
+            .field("namespace", &self.namespace)
+            .field("database", &self.database)
+            .field("capacity", &self.capacity)
+            .field("query_timeout", &self.query_timeout)
+            .field("transaction_timeout", &self.transaction_timeout)
+            .field("strict", &self.strict)
+            .field("capabilities", &self.capabilities)
+            .field("tls_ca_file", &self.tls_ca_file)
+            .field("tls_client_cert_file", &self.tls_client_cert_file)
+            .field("tls_client_key_file", &self.tls_client_key_file)
+            .field("tls_server_name", &self.tls_server_name)
+            .field("tls_accept_invalid_certs", &self.tls_accept_invalid_certs)
+            .finish()
     }
+}
 
-    let mut environment_toml = String::new();
-    if !migration.is_empty() {
-        environment_toml.push_str("[migration]\n");
-        for (key, val) in migration {
-            let _ = writeln!(environment_toml, "{key} = \"{val}\"");
-        }
-    }
-    if !files.is_empty() {
-        environment_toml.push_str("[files]\n");
-        for (key, val) in files {
-            let _ = writeln!(environment_toml, "{key} = \"{val}\"");
+impl DatabaseSettings {
+    /// Replaces the password with the one read from the password file or
+    /// printed by the password command, if one of them is configured.
+    fn resolve_password(&mut self) -> Result<(), Error> {
+        match (&self.password_file, &self.password_command) {
+            (Some(_), Some(_)) => Err(Error::Configuration(
+                "password-file and password-command must not be set both".into(),
+            )),
+            (Some(password_file), None) => {
+                self.password = password::read_password_file(Path::new(password_file))?;
+                Ok(())
+            },
+            (None, Some(password_command)) => {
+                self.password = password::run_password_command(password_command)?;
+                Ok(())
+            },
+            (None, None) => Ok(()),
         }
     }
-    if !database.is_empty() {
-        environment_toml.push_str("[database]\n");
-        for (key, val) in database {
-            let _ = writeln!(environment_toml, "{key} = \"{val}\"");
+
+    /// Returns the password file or the password command the password is
+    /// read from, if one of them is configured.
+    fn password_source(&self) -> Option<SettingSource> {
+        match (&self.password_file, &self.password_command) {
+            (Some(password_file), _) => Some(SettingSource::PasswordFile(password_file.into())),
+            (None, Some(password_command)) => {
+                Some(SettingSource::PasswordCommand(password_command.clone()))
+            },
+            (None, None) => None,
         }
     }
-    environment_toml
-}
-
-fn to_kebab_case(s: &str, offset: usize) -> String {
-    s.chars()
-        .skip(offset)
-        .map(|c| {
-            if c == '_' {
-                '-'
-            } else {
-                c.to_ascii_lowercase()
-            }
-        })
-        .collect()
 }
 
 fn db_auth_level_from_string<'de, D>(deserializer: D) -> Result<DbAuthLevel, D::Error>
@@ -219,14 +423,26 @@ where
     deserializer.deserialize_str(DbAuthLevelVisitor)
 }
 
+/// The accepted values of the `auth-level` setting in the JSON Schema.
+#[derive(JsonSchema)]
+#[allow(dead_code)]
+enum DbAuthLevelSchema {
+    Root,
+    Namespace,
+    Database,
+    Record,
+    Token,
+}
+
 struct DbAuthLevelVisitor;
 
 impl Visitor<'_> for DbAuthLevelVisitor {
     type Value = DbAuthLevel;
 
     fn expecting(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
-        formatter
-            .write_str("expecting a string containing one of 'Root', 'Namespace' or 'Database'")
+        formatter.write_str(
+            "expecting a string containing one of 'Root', 'Namespace', 'Database', 'Record' or 'Token'",
+        )
     }
 
     fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
@@ -237,9 +453,53 @@ impl Visitor<'_> for DbAuthLevelVisitor {
             "root" => Ok(DbAuthLevel::Root),
             "namespace" => Ok(DbAuthLevel::Namespace),
             "database" => Ok(DbAuthLevel::Database),
+            "record" => Ok(DbAuthLevel::Record),
+            "token" => Ok(DbAuthLevel::Token),
             _ => Err(serde::de::Error::invalid_value(
                 Unexpected::Str(v),
-                &"Root, Namespace or Database",
+                &"Root, Namespace, Database, Record or Token",
+            )),
+        }
+    }
+}
+
+fn db_capabilities_from_string<'de, D>(deserializer: D) -> Result<DbCapabilities, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_str(DbCapabilitiesVisitor)
+}
+
+/// The accepted values of the `capabilities` setting in the JSON Schema.
+#[derive(JsonSchema)]
+#[allow(dead_code)]
+enum DbCapabilitiesSchema {
+    Default,
+    AllowAll,
+    DenyAll,
+}
+
+struct DbCapabilitiesVisitor;
+
+impl Visitor<'_> for DbCapabilitiesVisitor {
+    type Value = DbCapabilities;
+
+    fn expecting(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        formatter
+            .write_str("expecting a string containing one of 'Default', 'AllowAll' or 'DenyAll'")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        match &v.to_ascii_lowercase()[..] {
+            "default" => Ok(DbCapabilities::Default),
+            "allowall" => Ok(DbCapabilities::AllowAll),
+            "denyall" => Ok(DbCapabilities::DenyAll),
+            _ => Err(serde::de::Error::invalid_value(
+                Unexpected::Str(v),
+                &"Default, AllowAll or DenyAll",
             )),
         }
     }
@@ -247,32 +507,52 @@ impl Visitor<'_> for DbAuthLevelVisitor {
 
 impl Settings {
     pub fn load() -> Result<Self, Error> {
-        let config_dir = env::var(CONFIG_DIR_ENVIRONMENT_VAR).unwrap_or_else(|_| "./".into());
-        Self::load_from_dir(Path::new(&config_dir))
+        SettingsLoader::new().load()
     }
 
     pub fn load_from_dir(path: &Path) -> Result<Self, Error> {
-        let environment = read_environment();
-        let config_file = path.join(CONFIG_FILENAME);
-        let config = Config::builder()
-            .add_source(File::from_str(DEFAULT_SETTINGS, FileFormat::Toml))
-            .add_source(File::from(config_file).required(false))
-            .add_source(File::from_str(&environment, FileFormat::Toml))
-            .build()
-            .map_err(|err| Error::Configuration(err.to_string()))?;
-
-        config
-            .try_deserialize()
-            .map_err(|err| Error::Configuration(err.to_string()))
+        SettingsLoader::new().with_config_dir(path).load()
+    }
+
+    pub fn load_profile(profile: &str) -> Result<Self, Error> {
+        SettingsLoader::new().with_profile(profile).load()
+    }
+
+    pub fn load_profile_from_dir(path: &Path, profile: &str) -> Result<Self, Error> {
+        SettingsLoader::new()
+            .with_config_dir(path)
+            .with_profile(profile)
+            .load()
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        if self.migration.ignore_checksum && !self.migration.allow_ignore_checksum {
+            return Err(Error::Configuration(
+                "ignore-checksum is set to true, but ignoring checksums is not allowed by the allow-ignore-checksum setting".into(),
+            ));
+        }
+        Ok(())
     }
 
     pub fn runner_config(&self) -> RunnerConfig<'_> {
         RunnerConfig {
             migrations_folder: Path::new(&self.files.migrations_folder).into(),
+            root_migrations_folder: self
+                .files
+                .root_migrations_folder
+                .as_ref()
+                .map(|folder| Path::new(folder).into()),
+            namespace_migrations_folder: self
+                .files
+                .namespace_migrations_folder
+                .as_ref()
+                .map(|folder| Path::new(folder).into()),
             excluded_files: self.files.exclude.parse().unwrap_or_else(|err| panic!("failed to create default `RunnerConfig`: {err} -- THIS IS AN IMPLEMENTATION ERROR! Please file a bug.")),
             migrations_table: (&self.database.migrations_table).into(),
             ignore_checksum: self.migration.ignore_checksum,
             ignore_order: self.migration.ignore_order,
+            template_folder: Path::new(&self.files.template_folder).into(),
+            schema_folder: Path::new(&self.files.schema_folder).into(),
         }
     }
 
@@ -284,7 +564,34 @@ impl Settings {
             auth_level: self.database.auth_level,
             username: (&self.database.username).into(),
             password: (&self.database.password).into(),
+            access: (&self.database.access).into(),
+            access_variables: self.database.access_variables.clone(),
+            token: (&self.database.token).into(),
+            connect_timeout: self.database.connect_timeout,
+            connect_retries: self.database.connect_retries,
+            connect_retry_interval: self.database.connect_retry_interval,
             capacity: self.database.capacity,
+            query_timeout: self.database.query_timeout,
+            transaction_timeout: self.database.transaction_timeout,
+            strict: self.database.strict,
+            capabilities: self.database.capabilities,
+            tls_ca_file: self
+                .database
+                .tls_ca_file
+                .as_ref()
+                .map(|file| Path::new(file).into()),
+            tls_client_cert_file: self
+                .database
+                .tls_client_cert_file
+                .as_ref()
+                .map(|file| Path::new(file).into()),
+            tls_client_key_file: self
+                .database
+                .tls_client_key_file
+                .as_ref()
+                .map(|file| Path::new(file).into()),
+            tls_server_name: self.database.tls_server_name.as_deref().map(Into::into),
+            tls_accept_invalid_certs: self.database.tls_accept_invalid_certs,
         }
     }
 }
@@ -0,0 +1,554 @@
+use crate::environment::{read_environment, unknown_environment_variables};
+use crate::interpolate::interpolate;
+use crate::schema::{SettingType, known_settings, similar_setting};
+use crate::{
+    CONFIG_DIR_ENVIRONMENT_VAR, CONFIG_FILENAME, DEFAULT_SETTINGS, PROFILE_ENVIRONMENT_VAR,
+    Settings, env,
+};
+use config::builder::DefaultState;
+use config::{Config, ConfigBuilder, ConfigError, File, FileFormat, Map, Source, Value, ValueKind};
+use database_migration::error::Error;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+
+/// Name of the subfolder in the user's config directory that holds the
+/// user-level configuration file.
+pub const USER_CONFIG_SUBFOLDER: &str = "surrealdb-migrate";
+
+/// Name of the Cargo manifest that can hold settings in its metadata.
+pub const CARGO_MANIFEST_FILENAME: &str = "Cargo.toml";
+
+/// The table in the Cargo manifest that holds the settings.
+pub const CARGO_METADATA_TABLE: &str = "package.metadata.surrealdb-migrate";
+
+/// Supported filename extensions of configuration files and their formats.
+///
+/// The order defines the order in which the files are listed in error
+/// messages.
+const CONFIG_FILE_FORMATS: [(&str, FileFormat); 4] = [
+    ("toml", FileFormat::Toml),
+    ("yaml", FileFormat::Yaml),
+    ("yml", FileFormat::Yaml),
+    ("json", FileFormat::Json),
+];
+
+/// Where the value of a setting comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SettingSource {
+    /// The default value of the setting.
+    Default,
+    /// The given configuration file.
+    File(PathBuf),
+    /// The `[package.metadata.surrealdb-migrate]` table of the given Cargo
+    /// manifest.
+    CargoMetadata(PathBuf),
+    /// The named profile defined in the given configuration file.
+    Profile { name: String, file: PathBuf },
+    /// The given environment variable.
+    Environment(String),
+    /// The password file configured by the setting `database.password-file`.
+    PasswordFile(PathBuf),
+    /// The command configured by the setting `database.password-command`.
+    PasswordCommand(String),
+}
+
+impl Display for SettingSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Default => f.write_str("default"),
+            Self::File(file) => write!(f, "{}", file.display()),
+            Self::CargoMetadata(file) => {
+                write!(f, "[{CARGO_METADATA_TABLE}] in {}", file.display())
+            },
+            Self::Profile { name, file } => {
+                write!(f, "profile \"{name}\" in {}", file.display())
+            },
+            Self::Environment(variable) => write!(f, "environment variable {variable}"),
+            Self::PasswordFile(file) => write!(f, "password file {}", file.display()),
+            Self::PasswordCommand(command) => write!(f, "password command `{command}`"),
+        }
+    }
+}
+
+/// The effective value of a setting together with its source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SettingOrigin {
+    /// The key of the setting including its section, e.g. `database.address`.
+    pub key: String,
+    /// The effective value of the setting.
+    pub value: String,
+    /// Where the effective value comes from.
+    pub source: SettingSource,
+}
+
+/// Loads the [`Settings`] from a hierarchy of configuration files and the
+/// environment.
+///
+/// The settings are merged from the following sources. Each source overwrites
+/// the settings of the sources listed before:
+///
+/// 1. the default settings
+/// 2. the user-level configuration file `surrealdb-migrate.toml` in the
+///    subfolder `surrealdb-migrate` of the user's config directory, which is
+///    `$XDG_CONFIG_HOME`, `$HOME/.config` or `%APPDATA%`
+/// 3. the `[package.metadata.surrealdb-migrate]` table of the `Cargo.toml` in
+///    the project directory
+/// 4. the project configuration file `surrealdb-migrate.toml` in the project
+///    directory
+/// 5. the explicitly given configuration files in the order they are given
+/// 6. the selected profile as defined in any of the configuration files
+/// 7. the environment variables
+///
+/// The project directory is the config directory if one is given, otherwise
+/// the first directory containing a configuration file or a `Cargo.toml` with
+/// settings found by walking up from the current working directory.
+///
+/// Instead of `surrealdb-migrate.toml` the configuration file can also be a
+/// YAML file `surrealdb-migrate.yaml` (or `.yml`) or a JSON file
+/// `surrealdb-migrate.json`. Only one configuration file is allowed per
+/// directory. The format of explicitly given configuration files is determined
+/// by their filename extension. An explicitly given `Cargo.toml` contributes
+/// its `[package.metadata.surrealdb-migrate]` table.
+///
+/// The config directory is given by [`with_config_dir`](Self::with_config_dir)
+/// or the environment variable `SURREALDB_MIGRATE_CONFIG_DIR`. The profile is
+/// selected by [`with_profile`](Self::with_profile) or the environment
+/// variable `SURMIG_PROFILE`.
+#[must_use]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SettingsLoader {
+    config_dir: Option<PathBuf>,
+    config_files: Vec<PathBuf>,
+    profile: Option<String>,
+}
+
+impl SettingsLoader {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_config_dir(mut self, config_dir: impl Into<PathBuf>) -> Self {
+        self.config_dir = Some(config_dir.into());
+        self
+    }
+
+    /// Adds a configuration file that overwrites the settings of the user-level
+    /// and the project configuration file as well as the settings of the
+    /// configuration files added before.
+    pub fn with_config_file(mut self, config_file: impl Into<PathBuf>) -> Self {
+        self.config_files.push(config_file.into());
+        self
+    }
+
+    pub fn with_profile(mut self, profile: impl Into<String>) -> Self {
+        self.profile = Some(profile.into());
+        self
+    }
+
+    pub fn load(&self) -> Result<Settings, Error> {
+        let layers = self.layers()?;
+        let mut settings = deserialize_settings(&merge_layers(&layers)?)?;
+        settings.database.resolve_password()?;
+        Ok(settings)
+    }
+
+    /// Loads the settings and determines the source of the effective value of
+    /// each setting.
+    ///
+    /// The password is not read from the password file nor from the output of
+    /// the password command, so that listing the settings does not run any
+    /// command. Instead, the password file or the password command is reported
+    /// as source of the password.
+    ///
+    /// The returned origins are sorted by the key of the setting.
+    pub fn load_with_origins(&self) -> Result<(Settings, Vec<SettingOrigin>), Error> {
+        let layers = self.layers()?;
+        let config = merge_layers(&layers)?;
+        let layer_keys = layers
+            .iter()
+            .map(|layer| {
+                flatten_config(&layer.config)
+                    .map(|settings| settings.into_iter().map(|(key, _)| key).collect::<Vec<_>>())
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut origins = flatten_config(&config)?
+            .into_iter()
+            .filter(|(key, _)| !key.starts_with("profiles."))
+            .map(|(key, value)| {
+                let source = layers
+                    .iter()
+                    .zip(&layer_keys)
+                    .rev()
+                    .find(|(_, keys)| keys.contains(&key))
+                    .map_or(SettingSource::Default, |(layer, _)| layer.source.clone());
+                SettingOrigin {
+                    key,
+                    value: value.to_string(),
+                    source,
+                }
+            })
+            .collect::<Vec<_>>();
+        origins.sort_unstable_by(|origin1, origin2| origin1.key.cmp(&origin2.key));
+        let settings = deserialize_settings(&config)?;
+        if let Some(password_source) = settings.database.password_source() {
+            origins
+                .iter_mut()
+                .filter(|origin| origin.key == "database.password")
+                .for_each(|origin| origin.source = password_source.clone());
+        }
+        Ok((settings, origins))
+    }
+
+    /// Returns the configuration files that are read in the order of
+    /// increasing precedence.
+    ///
+    /// Returns an error if an explicitly given configuration file does not
+    /// exist.
+    pub fn config_files(&self) -> Result<Vec<PathBuf>, Error> {
+        let mut config_files = Vec::with_capacity(self.config_files.len() + 3);
+        if let Some(user_config_dir) = user_config_dir() {
+            config_files.extend(config_file_in(&user_config_dir)?);
+        }
+        if let Some(project_dir) = self.project_dir()? {
+            let cargo_manifest = project_dir.join(CARGO_MANIFEST_FILENAME);
+            if read_cargo_metadata(&cargo_manifest)?.is_some() {
+                config_files.push(cargo_manifest);
+            }
+            config_files.extend(config_file_in(&project_dir)?);
+        }
+        for config_file in &self.config_files {
+            if !config_file.is_file() {
+                return Err(Error::Configuration(format!(
+                    "configuration file {} not found",
+                    config_file.display()
+                )));
+            }
+            config_files.push(config_file.clone());
+        }
+        Ok(config_files)
+    }
+
+    fn project_dir(&self) -> Result<Option<PathBuf>, Error> {
+        let config_dir = self
+            .config_dir
+            .clone()
+            .or_else(|| env::var(CONFIG_DIR_ENVIRONMENT_VAR).ok().map(PathBuf::from));
+        config_dir.map_or_else(find_project_dir, |config_dir| Ok(Some(config_dir)))
+    }
+
+    fn profile(&self) -> Option<String> {
+        self.profile.clone().or_else(|| {
+            env::var(PROFILE_ENVIRONMENT_VAR)
+                .ok()
+                .filter(|profile| !profile.is_empty())
+        })
+    }
+
+    fn layers(&self) -> Result<Vec<Layer>, Error> {
+        let mut layers = vec![Layer {
+            source: SettingSource::Default,
+            config: build_config(File::from_str(DEFAULT_SETTINGS, FileFormat::Toml))?,
+        }];
+        let mut file_layers = Vec::new();
+        for config_file in self.config_files()? {
+            file_layers.push(read_config_file(config_file)?);
+        }
+        let profile_layers = match self.profile() {
+            Some(profile) => read_profile(&file_layers, &profile)?,
+            None => Vec::new(),
+        };
+        layers.extend(file_layers);
+        layers.extend(profile_layers);
+        for variable in unknown_environment_variables() {
+            log::warn!("environment variable {variable} does not define a known setting");
+        }
+        for setting in read_environment()? {
+            let config = Config::builder()
+                .set_override(&setting.key, setting.value)
+                .and_then(ConfigBuilder::<DefaultState>::build)
+                .map_err(|err| Error::Configuration(err.to_string()))?;
+            layers.push(Layer {
+                config,
+                source: SettingSource::Environment(setting.variable),
+            });
+        }
+        Ok(layers)
+    }
+}
+
+/// The settings read from one source.
+struct Layer {
+    source: SettingSource,
+    config: Config,
+}
+
+/// Returns the configuration file in the given directory, if there is one.
+///
+/// Returns an error if the directory contains configuration files of several
+/// formats.
+fn config_file_in(dir: &Path) -> Result<Option<PathBuf>, Error> {
+    let mut config_files = CONFIG_FILE_FORMATS
+        .iter()
+        .map(|(extension, _)| dir.join(CONFIG_FILENAME).with_extension(extension))
+        .filter(|file| file.is_file())
+        .collect::<Vec<_>>();
+    if config_files.len() > 1 {
+        return Err(Error::Configuration(format!(
+            "found several configuration files in {}: {}; only one of them is allowed",
+            dir.display(),
+            config_files
+                .iter()
+                .map(|file| file.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )));
+    }
+    Ok(config_files.pop())
+}
+
+/// Finds the nearest project directory by walking up from the current working
+/// directory.
+///
+/// The project directory is the first directory that contains a configuration
+/// file or a Cargo manifest with settings in its metadata.
+fn find_project_dir() -> Result<Option<PathBuf>, Error> {
+    let Ok(working_dir) = std::env::current_dir() else {
+        return Ok(None);
+    };
+    for dir in working_dir.ancestors() {
+        if config_file_in(dir)?.is_some()
+            || read_cargo_metadata(&dir.join(CARGO_MANIFEST_FILENAME))?.is_some()
+        {
+            return Ok(Some(dir.to_path_buf()));
+        }
+    }
+    Ok(None)
+}
+
+fn user_config_dir() -> Option<PathBuf> {
+    let user_config_dir = env::var("XDG_CONFIG_HOME")
+        .ok()
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| {
+            env::var("HOME")
+                .ok()
+                .filter(|dir| !dir.is_empty())
+                .map(|home| Path::new(&home).join(".config"))
+        })
+        .or_else(|| {
+            env::var("APPDATA")
+                .ok()
+                .filter(|dir| !dir.is_empty())
+                .map(PathBuf::from)
+        })?;
+    Some(user_config_dir.join(USER_CONFIG_SUBFOLDER))
+}
+
+fn build_config(source: impl Source + Send + Sync + 'static) -> Result<Config, Error> {
+    Config::builder()
+        .add_source(source)
+        .build()
+        .map_err(|err| Error::Configuration(err.to_string()))
+}
+
+/// Reads the settings in the `[package.metadata.surrealdb-migrate]` table of
+/// the given Cargo manifest.
+///
+/// Returns `None` if the manifest does not exist or does not contain the
+/// table.
+fn read_cargo_metadata(cargo_manifest: &Path) -> Result<Option<Config>, Error> {
+    if !cargo_manifest.is_file() {
+        return Ok(None);
+    }
+    let manifest = build_config(File::from(cargo_manifest).format(FileFormat::Toml))?;
+    match manifest.get_table(CARGO_METADATA_TABLE) {
+        Ok(table) => config_from_table(table).map(Some),
+        Err(ConfigError::NotFound(_)) => Ok(None),
+        Err(err) => Err(Error::Configuration(format!(
+            "invalid [{CARGO_METADATA_TABLE}] table in {}: {err}",
+            cargo_manifest.display()
+        ))),
+    }
+}
+
+/// Reads the given configuration file in the format given by its filename
+/// extension or the settings of a Cargo manifest.
+fn read_config_file(config_file: PathBuf) -> Result<Layer, Error> {
+    if config_file.file_name() == Some(CARGO_MANIFEST_FILENAME.as_ref()) {
+        let config = read_cargo_metadata(&config_file)?.unwrap_or_default();
+        return Ok(Layer {
+            config: prepare_file_config(&config_file, &config)?,
+            source: SettingSource::CargoMetadata(config_file),
+        });
+    }
+    let extension = config_file
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or_default();
+    let Some((_, format)) = CONFIG_FILE_FORMATS
+        .iter()
+        .find(|(known_extension, _)| known_extension.eq_ignore_ascii_case(extension))
+    else {
+        return Err(Error::Configuration(format!(
+            "unsupported format of configuration file {}, the filename extension must be one of .toml, .yaml, .yml or .json",
+            config_file.display()
+        )));
+    };
+    let config = build_config(File::from(config_file.as_path()).format(*format))?;
+    Ok(Layer {
+        config: prepare_file_config(&config_file, &config)?,
+        source: SettingSource::File(config_file),
+    })
+}
+
+/// Checks that the given settings read from a configuration file contain only
+/// known settings and interpolates the environment variables referenced in
+/// their string values.
+fn prepare_file_config(config_file: &Path, config: &Config) -> Result<Config, Error> {
+    check_known_settings(config_file, config)?;
+    let mut builder = Config::builder().add_source(config.clone());
+    for (key, value) in flatten_config(config)? {
+        let ValueKind::String(value) = value.kind else {
+            continue;
+        };
+        if !value.contains('$') {
+            continue;
+        }
+        let interpolated = interpolate(&value).map_err(|err| {
+            Error::Configuration(format!(
+                "invalid value of setting {key} in {}: {err}",
+                config_file.display()
+            ))
+        })?;
+        builder = builder
+            .set_override(key, interpolated)
+            .map_err(|err| Error::Configuration(err.to_string()))?;
+    }
+    builder
+        .build()
+        .map_err(|err| Error::Configuration(err.to_string()))
+}
+
+fn merge_layers(layers: &[Layer]) -> Result<Config, Error> {
+    layers
+        .iter()
+        .fold(Config::builder(), |builder, layer| {
+            builder.add_source(layer.config.clone())
+        })
+        .build()
+        .map_err(|err| Error::Configuration(err.to_string()))
+}
+
+/// Checks that the given configuration file only contains known settings.
+///
+/// The error for an unknown setting suggests a known setting with a similar
+/// name, if there is one.
+fn check_known_settings(config_file: &Path, config: &Config) -> Result<(), Error> {
+    for (key, _) in flatten_config(config)? {
+        let (prefix, setting_key) = match key.splitn(3, '.').collect::<Vec<_>>()[..] {
+            ["profiles", profile, setting_key] => (format!("profiles.{profile}."), setting_key),
+            _ => (String::new(), key.as_str()),
+        };
+        let is_known = known_settings().iter().any(|setting| {
+            let known_key = format!("{}.{}", setting.section, setting.key);
+            setting_key == known_key
+                || setting.setting_type == SettingType::Table
+                    && setting_key
+                        .strip_prefix(&known_key)
+                        .is_some_and(|entry| entry.starts_with('.'))
+        });
+        if is_known {
+            continue;
+        }
+        let suggestion = similar_setting(setting_key)
+            .map(|similar_key| format!(", did you mean {prefix}{similar_key}?"))
+            .unwrap_or_default();
+        return Err(Error::Configuration(format!(
+            "unknown setting {key} in {}{suggestion}",
+            config_file.display()
+        )));
+    }
+    Ok(())
+}
+
+fn deserialize_settings(config: &Config) -> Result<Settings, Error> {
+    let mut table = config
+        .collect()
+        .map_err(|err| Error::Configuration(err.to_string()))?;
+    table.remove("profiles");
+    let settings: Settings = config_from_table(table)?
+        .try_deserialize()
+        .map_err(|err| Error::Configuration(err.to_string()))?;
+    settings.validate()?;
+    Ok(settings)
+}
+
+/// Reads the settings of the given profile from the `profiles` table of each
+/// configuration file that defines the profile.
+///
+/// The returned configs contain the sections of the profile, like `migration`
+/// or `database`, at the top level, so that they can be layered on top of the
+/// base settings.
+fn read_profile(file_layers: &[Layer], profile: &str) -> Result<Vec<Layer>, Error> {
+    let mut profile_layers = Vec::new();
+    for file_layer in file_layers {
+        let (SettingSource::File(file) | SettingSource::CargoMetadata(file)) = &file_layer.source
+        else {
+            continue;
+        };
+        let Ok(profile_settings) = file_layer.config.get_table(&format!("profiles.{profile}"))
+        else {
+            continue;
+        };
+        let config = config_from_table(profile_settings)?;
+        profile_layers.push(Layer {
+            source: SettingSource::Profile {
+                name: profile.into(),
+                file: file.clone(),
+            },
+            config,
+        });
+    }
+    if profile_layers.is_empty() {
+        return Err(Error::Configuration(format!(
+            "profile \"{profile}\" is not defined in the configuration file"
+        )));
+    }
+    Ok(profile_layers)
+}
+
+/// Creates a config with the entries of the given table at the top level.
+fn config_from_table(table: Map<String, Value>) -> Result<Config, Error> {
+    table
+        .into_iter()
+        .try_fold(Config::builder(), |builder, (key, value)| {
+            builder.set_override(key, value)
+        })
+        .and_then(ConfigBuilder::<DefaultState>::build)
+        .map_err(|err| Error::Configuration(err.to_string()))
+}
+
+/// Returns the key and the value of each setting in the given config.
+fn flatten_config(config: &Config) -> Result<Vec<(String, Value)>, Error> {
+    let table = config
+        .collect()
+        .map_err(|err| Error::Configuration(err.to_string()))?;
+    let mut settings = Vec::new();
+    flatten_table("", table, &mut settings);
+    Ok(settings)
+}
+
+fn flatten_table(prefix: &str, table: Map<String, Value>, settings: &mut Vec<(String, Value)>) {
+    for (key, value) in table {
+        let key = if prefix.is_empty() {
+            key
+        } else {
+            format!("{prefix}.{key}")
+        };
+        match value.clone().into_table() {
+            Ok(table) => flatten_table(&key, table, settings),
+            Err(_) => settings.push((key, value)),
+        }
+    }
+}
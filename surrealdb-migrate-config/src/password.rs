@@ -0,0 +1,63 @@
+//! Reading the database password from a file or from the output of a command.
+
+use database_migration::error::Error;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+/// Reads the password from the given file.
+///
+/// Trailing line breaks are removed from the content of the file, as they
+/// are usually not part of the password.
+pub fn read_password_file(path: &Path) -> Result<String, Error> {
+    let content = fs::read_to_string(path).map_err(|err| {
+        Error::Configuration(format!(
+            "failed to read password file {}: {err}",
+            path.display()
+        ))
+    })?;
+    Ok(trim_line_breaks(&content).to_string())
+}
+
+/// Runs the given command in the shell of the operating system and returns
+/// what it prints to stdout as the password.
+///
+/// Trailing line breaks are removed from the output of the command.
+pub fn run_password_command(command: &str) -> Result<String, Error> {
+    let output = shell_command(command).output().map_err(|err| {
+        Error::Configuration(format!("failed to run password command `{command}`: {err}"))
+    })?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let mut message = format!("password command `{command}` failed with {}", output.status);
+        if !stderr.trim().is_empty() {
+            message.push_str(": ");
+            message.push_str(stderr.trim());
+        }
+        return Err(Error::Configuration(message));
+    }
+    let stdout = String::from_utf8(output.stdout).map_err(|_| {
+        Error::Configuration(format!(
+            "password command `{command}` did not print valid UTF-8"
+        ))
+    })?;
+    Ok(trim_line_breaks(&stdout).to_string())
+}
+
+#[cfg(windows)]
+fn shell_command(command: &str) -> Command {
+    let mut shell = Command::new("cmd");
+    shell.args(["/C", command]);
+    shell
+}
+
+#[cfg(not(windows))]
+fn shell_command(command: &str) -> Command {
+    let mut shell = Command::new("sh");
+    shell.args(["-c", command]);
+    shell
+}
+
+fn trim_line_breaks(value: &str) -> &str {
+    value.trim_end_matches(['\n', '\r'])
+}
@@ -0,0 +1,139 @@
+//! JSON Schema of the configuration file, generated from the settings types.
+
+use crate::Settings;
+use schemars::generate::SchemaSettings;
+use serde_json::{Value, json};
+use std::sync::LazyLock;
+
+/// The type of value a setting expects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingType {
+    Boolean,
+    Integer,
+    String,
+    /// A table of string values with arbitrary keys.
+    Table,
+}
+
+/// A setting that can be defined in the configuration file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KnownSetting {
+    /// The section of the setting, e.g. `database`.
+    pub section: String,
+    /// The key of the setting within its section, e.g. `address`.
+    pub key: String,
+    /// The type of value the setting expects.
+    pub setting_type: SettingType,
+}
+
+static KNOWN_SETTINGS: LazyLock<Vec<KnownSetting>> =
+    LazyLock::new(|| read_known_settings(&config_file_schema()));
+
+/// Returns the JSON Schema of the configuration file `surrealdb-migrate.toml`.
+///
+/// The schema follows JSON Schema draft 7, which is supported by editors
+/// like VS Code with the Taplo extension. No setting is required, as every
+/// setting has a default value. The keys of all objects are sorted, so the
+/// schema is printed in the same order regardless of the features of
+/// `serde_json`.
+pub fn config_file_schema() -> Value {
+    let mut schema = SchemaSettings::draft07()
+        .with(|settings| settings.inline_subschemas = true)
+        .into_generator()
+        .into_root_schema_for::<Settings>()
+        .to_value();
+    remove_required(&mut schema);
+    if let Some(root) = schema.as_object_mut() {
+        root.insert("title".into(), "surrealdb-migrate configuration".into());
+        let sections = root.get("properties").cloned().unwrap_or_default();
+        if let Some(Value::Object(properties)) = root.get_mut("properties") {
+            properties.insert(
+                "profiles".into(),
+                json!({
+                    "description": "Named profiles holding settings that differ from the base settings for a specific environment.",
+                    "type": "object",
+                    "additionalProperties": {
+                        "type": "object",
+                        "additionalProperties": false,
+                        "properties": sections,
+                    },
+                }),
+            );
+        }
+    }
+    schema.sort_all_objects();
+    schema
+}
+
+/// Returns all settings that can be defined in the configuration file.
+pub fn known_settings() -> &'static [KnownSetting] {
+    &KNOWN_SETTINGS
+}
+
+/// Returns the known setting that is most similar to the given key, if there
+/// is one similar enough to be a likely candidate for a misspelled key.
+///
+/// The key includes the section, e.g. `database.adress`.
+pub fn similar_setting(key: &str) -> Option<String> {
+    known_settings()
+        .iter()
+        .map(|setting| format!("{}.{}", setting.section, setting.key))
+        .map(|known_key| {
+            (
+                strsim::normalized_damerau_levenshtein(key, &known_key),
+                known_key,
+            )
+        })
+        .filter(|(similarity, _)| *similarity >= 0.7)
+        .max_by(|(similarity1, _), (similarity2, _)| similarity1.total_cmp(similarity2))
+        .map(|(_, known_key)| known_key)
+}
+
+fn remove_required(schema: &mut Value) {
+    match schema {
+        Value::Object(object) => {
+            object.remove("required");
+            object.values_mut().for_each(remove_required);
+        },
+        Value::Array(array) => array.iter_mut().for_each(remove_required),
+        _ => {},
+    }
+}
+
+fn read_known_settings(schema: &Value) -> Vec<KnownSetting> {
+    let Some(Value::Object(sections)) = schema.get("properties") else {
+        return Vec::new();
+    };
+    sections
+        .iter()
+        .filter(|(section, _)| *section != "profiles")
+        .filter_map(|(section, section_schema)| {
+            let Some(Value::Object(keys)) = section_schema.get("properties") else {
+                return None;
+            };
+            Some(keys.iter().map(|(key, key_schema)| KnownSetting {
+                section: section.clone(),
+                key: key.clone(),
+                setting_type: setting_type_of(key_schema),
+            }))
+        })
+        .flatten()
+        .collect()
+}
+
+fn setting_type_of(schema: &Value) -> SettingType {
+    let types = match schema.get("type") {
+        Some(Value::String(name)) => vec![name.as_str()],
+        Some(Value::Array(names)) => names.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new(),
+    };
+    if types.contains(&"boolean") {
+        SettingType::Boolean
+    } else if types.contains(&"integer") {
+        SettingType::Integer
+    } else if types.contains(&"object") {
+        SettingType::Table
+    } else {
+        SettingType::String
+    }
+}
@@ -1,8 +1,11 @@
 use super::*;
 use asserting::prelude::*;
 use database_migration::config::{
-    DEFAULT_EXCLUDED_FILES, DbAuthLevel, DbClientConfig, RunnerConfig,
+    DEFAULT_EXCLUDED_FILES, DbAuthLevel, DbCapabilities, DbClientConfig, RunnerConfig,
 };
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::Duration;
 
 #[test]
 fn default_settings_are_as_defined() {
@@ -12,23 +15,45 @@ fn default_settings_are_as_defined() {
         migration: MigrationSettings {
             ignore_checksum: false,
             ignore_order: false,
+            allow_ignore_checksum: true,
         },
         files: FilesSettings {
             migrations_folder: "migrations".into(),
+            root_migrations_folder: None,
+            namespace_migrations_folder: None,
             script_extension: ".surql".into(),
             up_script_extension: ".up.surql".into(),
             down_script_extension: ".down.surql".into(),
             exclude: DEFAULT_EXCLUDED_FILES.into(),
+            template_folder: "templates".into(),
+            schema_folder: "schema".into(),
         },
         database: DatabaseSettings {
             migrations_table: "migrations".into(),
             address: "ws://localhost:8000".into(),
             username: "root".into(),
             password: "root".into(),
+            password_file: None,
+            password_command: None,
             auth_level: DbAuthLevel::Root,
+            access: String::new(),
+            access_variables: BTreeMap::new(),
+            token: String::new(),
+            connect_timeout: Duration::from_secs(10),
+            connect_retries: 0,
+            connect_retry_interval: Duration::from_secs(1),
             namespace: "test".into(),
             database: "test".into(),
             capacity: 20,
+            query_timeout: None,
+            transaction_timeout: None,
+            strict: false,
+            capabilities: DbCapabilities::Default,
+            tls_ca_file: None,
+            tls_client_cert_file: None,
+            tls_client_key_file: None,
+            tls_server_name: None,
+            tls_accept_invalid_certs: false,
         },
     }));
 }
@@ -43,6 +68,10 @@ fn overwrite_settings_from_environment_variables() {
         "SURMIG_FILES_MIGRATIONS_FOLDER",
         "environment/migration/scripts",
     );
+    env::set_var(
+        "SURMIG_FILES_NAMESPACE_MIGRATIONS_FOLDER",
+        "environment/migration/namespace",
+    );
     env::set_var("SURMIG_FILES_EXCLUDE", ".keep|.gitignore|TODO.md");
     env::set_var("SURMIG_FILES_UP_SCRIPT_EXTENSION", ".surql");
     env::set_var("SURMIG_DATABASE_ADDRESS", "wss://localhost:8000");
@@ -56,29 +85,52 @@ fn overwrite_settings_from_environment_variables() {
         migration: MigrationSettings {
             ignore_checksum: true,
             ignore_order: false,
+            allow_ignore_checksum: true,
         },
         files: FilesSettings {
             migrations_folder: "environment/migration/scripts".into(),
+            root_migrations_folder: None,
+            namespace_migrations_folder: Some("environment/migration/namespace".into()),
             script_extension: ".surql".into(),
             up_script_extension: ".surql".into(),
             down_script_extension: ".down.surql".into(),
             exclude: ".keep|.gitignore|TODO.md".into(),
+            template_folder: "database_migration/templates".into(),
+            schema_folder: "database_migration/schema".into(),
         },
         database: DatabaseSettings {
             migrations_table: "schema_version".into(),
             address: "wss://localhost:8000".into(),
             username: "tester".into(),
             password: "s3cr3t".into(),
+            password_file: None,
+            password_command: None,
             auth_level: DbAuthLevel::Namespace,
+            access: String::new(),
+            access_variables: BTreeMap::new(),
+            token: String::new(),
+            connect_timeout: Duration::from_secs(10),
+            connect_retries: 0,
+            connect_retry_interval: Duration::from_secs(1),
             namespace: "playground".into(),
             database: "test".into(),
             capacity: 101,
+            query_timeout: None,
+            transaction_timeout: None,
+            strict: false,
+            capabilities: DbCapabilities::Default,
+            tls_ca_file: None,
+            tls_client_cert_file: None,
+            tls_client_key_file: None,
+            tls_server_name: None,
+            tls_accept_invalid_certs: false,
         },
     }));
 
     env::remove_var("SURMIG_MIGRATION_IGNORE_CHECKSUM");
     env::remove_var("SURMIG_MIGRATION_IGNORE_ORDER");
     env::remove_var("SURMIG_FILES_MIGRATIONS_FOLDER");
+    env::remove_var("SURMIG_FILES_NAMESPACE_MIGRATIONS_FOLDER");
     env::remove_var("SURMIG_FILES_EXCLUDE");
     env::remove_var("SURMIG_FILES_UP_SCRIPT_EXTENSION");
     env::remove_var("SURMIG_DATABASE_ADDRESS");
@@ -99,23 +151,45 @@ fn load_settings_from_custom_config_directory() {
         migration: MigrationSettings {
             ignore_checksum: false,
             ignore_order: true,
+            allow_ignore_checksum: true,
         },
         files: FilesSettings {
             migrations_folder: "database_migration/scripts".into(),
+            root_migrations_folder: None,
+            namespace_migrations_folder: None,
             script_extension: ".surql".into(),
             up_script_extension: ".up.surql".into(),
             down_script_extension: ".down.surql".into(),
             exclude: "**/.*".into(),
+            template_folder: "database_migration/templates".into(),
+            schema_folder: "database_migration/schema".into(),
         },
         database: DatabaseSettings {
             migrations_table: "schema_version".into(),
             address: "ws://localhost:8000".into(),
             username: "tester".into(),
             password: "s3cr3t".into(),
+            password_file: None,
+            password_command: None,
             auth_level: DbAuthLevel::Database,
+            access: String::new(),
+            access_variables: BTreeMap::new(),
+            token: String::new(),
+            connect_timeout: Duration::from_secs(10),
+            connect_retries: 0,
+            connect_retry_interval: Duration::from_secs(1),
             namespace: "test".into(),
             database: "test".into(),
             capacity: 99,
+            query_timeout: None,
+            transaction_timeout: None,
+            strict: false,
+            capabilities: DbCapabilities::Default,
+            tls_ca_file: None,
+            tls_client_cert_file: None,
+            tls_client_key_file: None,
+            tls_server_name: None,
+            tls_accept_invalid_certs: false,
         },
     }));
 
@@ -131,12 +205,16 @@ fn get_runner_config_from_settings() {
 
     assert_that!(runner_config).is_equal_to(RunnerConfig {
         migrations_folder: Path::new("database_migration/migrations").into(),
+        root_migrations_folder: Some(Path::new("database_migration/root").into()),
+        namespace_migrations_folder: Some(Path::new("database_migration/namespace").into()),
         excluded_files: ".keep|.*ignore|README*|TODO*|FIXME*"
             .parse()
             .unwrap_or_else(|err| panic!("invalid excluded files string: {err}")),
         migrations_table: "migration_executions".into(),
         ignore_checksum: true,
         ignore_order: false,
+        template_folder: Path::new("templates").into(),
+        schema_folder: Path::new("schema").into(),
     });
 }
 
@@ -154,6 +232,946 @@ fn get_db_client_config_from_settings() {
         auth_level: DbAuthLevel::Database,
         username: "player".into(),
         password: "s3cr3t".into(),
+        access: "".into(),
+        access_variables: BTreeMap::new(),
+        token: "".into(),
+        connect_timeout: Duration::from_secs(30),
+        connect_retries: 5,
+        connect_retry_interval: Duration::from_millis(500),
         capacity: 150,
+        query_timeout: Some(Duration::from_secs(7200)),
+        transaction_timeout: Some(Duration::from_secs(5400)),
+        strict: true,
+        capabilities: DbCapabilities::AllowAll,
+        tls_ca_file: Some(Path::new("certs/ca.pem").into()),
+        tls_client_cert_file: Some(Path::new("certs/client.pem").into()),
+        tls_client_key_file: Some(Path::new("certs/client.key").into()),
+        tls_server_name: Some("db.internal.example.com".into()),
+        tls_accept_invalid_certs: true,
+    });
+}
+
+#[test]
+fn load_settings_of_profile_inherits_base_settings() {
+    let settings = Settings::load_profile_from_dir(Path::new("fixtures/with_profiles"), "staging");
+
+    assert_that!(settings).is_equal_to(Ok(Settings {
+        migration: MigrationSettings {
+            ignore_checksum: true,
+            ignore_order: false,
+            allow_ignore_checksum: true,
+        },
+        files: FilesSettings {
+            migrations_folder: "database_migration/scripts".into(),
+            root_migrations_folder: None,
+            namespace_migrations_folder: None,
+            script_extension: ".surql".into(),
+            up_script_extension: ".up.surql".into(),
+            down_script_extension: ".down.surql".into(),
+            exclude: DEFAULT_EXCLUDED_FILES.into(),
+            template_folder: "templates".into(),
+            schema_folder: "schema".into(),
+        },
+        database: DatabaseSettings {
+            migrations_table: "schema_version".into(),
+            address: "wss://staging.example.com:8000".into(),
+            username: "tester".into(),
+            password: "s3cr3t".into(),
+            password_file: None,
+            password_command: None,
+            auth_level: DbAuthLevel::Database,
+            access: String::new(),
+            access_variables: BTreeMap::new(),
+            token: String::new(),
+            connect_timeout: Duration::from_secs(10),
+            connect_retries: 0,
+            connect_retry_interval: Duration::from_secs(1),
+            namespace: "staging".into(),
+            database: "shop".into(),
+            capacity: 20,
+            query_timeout: None,
+            transaction_timeout: None,
+            strict: false,
+            capabilities: DbCapabilities::Default,
+            tls_ca_file: None,
+            tls_client_cert_file: None,
+            tls_client_key_file: None,
+            tls_server_name: None,
+            tls_accept_invalid_certs: false,
+        },
+    }));
+}
+
+#[test]
+fn load_settings_of_profile_selected_by_environment_variable() {
+    env::set_var("SURMIG_PROFILE", "prod");
+
+    let settings = Settings::load_from_dir(Path::new("fixtures/with_profiles"))
+        .expect("failed to load settings");
+
+    env::remove_var("SURMIG_PROFILE");
+
+    assert_that!(settings.migration).is_equal_to(MigrationSettings {
+        ignore_checksum: false,
+        ignore_order: false,
+        allow_ignore_checksum: false,
     });
+    assert_that!(settings.database.address).is_equal_to("wss://prod.example.com:8000");
+    assert_that!(settings.database.namespace).is_equal_to("prod");
+    assert_that!(settings.database.database).is_equal_to("shop");
+}
+
+#[test]
+fn environment_variables_overwrite_settings_of_profile() {
+    env::set_var("SURMIG_DATABASE_NAMESPACE", "from_environment");
+
+    let settings = Settings::load_profile_from_dir(Path::new("fixtures/with_profiles"), "prod")
+        .expect("failed to load settings");
+
+    env::remove_var("SURMIG_DATABASE_NAMESPACE");
+
+    assert_that!(settings.database.address).is_equal_to("wss://prod.example.com:8000");
+    assert_that!(settings.database.namespace).is_equal_to("from_environment");
+}
+
+#[test]
+fn load_settings_of_undefined_profile_fails() {
+    let settings = Settings::load_profile_from_dir(Path::new("fixtures/with_profiles"), "qa");
+
+    assert_that!(settings).is_equal_to(Err(Error::Configuration(
+        "profile \"qa\" is not defined in the configuration file".into(),
+    )));
+}
+
+#[test]
+fn ignore_checksum_in_profile_that_forbids_it_fails() {
+    env::set_var("SURMIG_MIGRATION_IGNORE_CHECKSUM", "true");
+
+    let settings = Settings::load_profile_from_dir(Path::new("fixtures/with_profiles"), "prod");
+
+    env::remove_var("SURMIG_MIGRATION_IGNORE_CHECKSUM");
+
+    assert_that!(settings).is_equal_to(Err(Error::Configuration(
+        "ignore-checksum is set to true, but ignoring checksums is not allowed by the allow-ignore-checksum setting".into(),
+    )));
+}
+
+#[test]
+fn settings_of_project_config_file_overwrite_user_config_file() {
+    env::set_var("XDG_CONFIG_HOME", "fixtures/user_config");
+
+    let settings = SettingsLoader::new()
+        .with_config_dir("fixtures/custom_config_dir")
+        .load()
+        .expect("failed to load settings");
+
+    env::remove_var("XDG_CONFIG_HOME");
+
+    assert_that!(settings.database.username).is_equal_to("tester");
+    assert_that!(settings.database.password).is_equal_to("s3cr3t");
+    assert_that!(settings.database.capacity).is_equal_to(99);
+}
+
+#[test]
+fn load_settings_from_user_config_file() {
+    env::set_var("XDG_CONFIG_HOME", "fixtures/user_config");
+
+    let settings = SettingsLoader::new()
+        .with_config_dir("fixtures/with_profiles")
+        .load()
+        .expect("failed to load settings");
+
+    env::remove_var("XDG_CONFIG_HOME");
+
+    assert_that!(settings.database.username).is_equal_to("tester");
+    assert_that!(settings.database.password).is_equal_to("s3cr3t");
+    assert_that!(settings.database.capacity).is_equal_to(5);
+}
+
+#[test]
+fn explicit_config_files_overwrite_project_config_file() {
+    let settings = SettingsLoader::new()
+        .with_config_dir("fixtures/custom_config_dir")
+        .with_config_file("fixtures/config_files/local.toml")
+        .load()
+        .expect("failed to load settings");
+
+    assert_that!(settings.database.address).is_equal_to("ws://localhost:8001");
+    assert_that!(settings.database.namespace).is_equal_to("local");
+    assert_that!(settings.database.username).is_equal_to("tester");
+    assert_that!(settings.database.capacity).is_equal_to(99);
+}
+
+#[test]
+fn load_settings_from_missing_config_file_fails() {
+    let settings = SettingsLoader::new()
+        .with_config_file("fixtures/config_files/missing.toml")
+        .load();
+
+    assert_that!(settings).is_equal_to(Err(Error::Configuration(
+        "configuration file fixtures/config_files/missing.toml not found".into(),
+    )));
+}
+
+#[test]
+fn config_files_are_listed_from_lowest_to_highest_precedence() {
+    env::set_var("XDG_CONFIG_HOME", "fixtures/user_config");
+
+    let config_files = SettingsLoader::new()
+        .with_config_dir("fixtures/custom_config_dir")
+        .with_config_file("fixtures/config_files/local.toml")
+        .config_files();
+
+    env::remove_var("XDG_CONFIG_HOME");
+
+    assert_that!(config_files).is_equal_to(Ok(vec![
+        PathBuf::from("fixtures/user_config/surrealdb-migrate/surrealdb-migrate.toml"),
+        PathBuf::from("fixtures/custom_config_dir/surrealdb-migrate.toml"),
+        PathBuf::from("fixtures/config_files/local.toml"),
+    ]));
+}
+
+#[test]
+fn load_settings_from_yaml_config_file() {
+    let settings = Settings::load_from_dir(Path::new("fixtures/with_yaml_file"))
+        .expect("failed to load settings");
+
+    assert_that!(settings.migration.ignore_order).is_true();
+    assert_that!(settings.files.migrations_folder).is_equal_to("database/migrations");
+    assert_that!(settings.database.address).is_equal_to("ws://yaml.example.com:8000");
+    assert_that!(settings.database.namespace).is_equal_to("from_yaml");
+    assert_that!(settings.database.capacity).is_equal_to(25);
+}
+
+#[test]
+fn load_settings_from_json_config_file() {
+    let settings = Settings::load_from_dir(Path::new("fixtures/with_json_file"))
+        .expect("failed to load settings");
+
+    assert_that!(settings.migration.ignore_order).is_true();
+    assert_that!(settings.files.migrations_folder).is_equal_to("database/migrations");
+    assert_that!(settings.database.address).is_equal_to("ws://json.example.com:8000");
+    assert_that!(settings.database.namespace).is_equal_to("from_json");
+    assert_that!(settings.database.capacity).is_equal_to(30);
+}
+
+#[test]
+fn load_settings_from_cargo_manifest_metadata() {
+    let settings = Settings::load_from_dir(Path::new("fixtures/with_cargo_metadata"))
+        .expect("failed to load settings");
+
+    assert_that!(settings.files.migrations_folder).is_equal_to("database/migrations");
+    assert_that!(settings.database.address).is_equal_to("ws://cargo.example.com:8000");
+    assert_that!(settings.database.namespace).is_equal_to("from_cargo");
+    assert_that!(settings.database.capacity).is_equal_to(15);
+}
+
+#[test]
+fn load_settings_of_profile_defined_in_cargo_manifest_metadata() {
+    let settings = SettingsLoader::new()
+        .with_config_dir("fixtures/with_cargo_metadata")
+        .with_profile("prod")
+        .load()
+        .expect("failed to load settings");
+
+    assert_that!(settings.database.address).is_equal_to("wss://prod.example.com:8000");
+    assert_that!(settings.database.namespace).is_equal_to("from_cargo");
+}
+
+#[test]
+fn settings_of_config_file_overwrite_cargo_manifest_metadata() {
+    let (settings, origins) = SettingsLoader::new()
+        .with_config_dir("fixtures/with_cargo_metadata_and_config_file")
+        .load_with_origins()
+        .expect("failed to load settings");
+
+    let source_of = |key: &str| {
+        origins
+            .iter()
+            .find(|origin| origin.key == key)
+            .map(|origin| origin.source.clone())
+    };
+    assert_that!(settings.database.address).is_equal_to("ws://cargo.example.com:8000");
+    assert_that!(settings.database.namespace).is_equal_to("from_config_file");
+    assert_that!(source_of("database.address")).is_equal_to(Some(SettingSource::CargoMetadata(
+        PathBuf::from("fixtures/with_cargo_metadata_and_config_file/Cargo.toml"),
+    )));
+    assert_that!(source_of("database.namespace")).is_equal_to(Some(SettingSource::File(
+        PathBuf::from("fixtures/with_cargo_metadata_and_config_file/surrealdb-migrate.toml"),
+    )));
+}
+
+#[test]
+fn cargo_manifest_without_metadata_is_not_listed_as_config_file() {
+    let config_files = SettingsLoader::new()
+        .with_config_dir("fixtures/with_json_file")
+        .config_files();
+
+    assert_that!(config_files).is_equal_to(Ok(vec![PathBuf::from(
+        "fixtures/with_json_file/surrealdb-migrate.json",
+    )]));
+}
+
+#[test]
+fn several_config_files_in_one_directory_fail() {
+    let settings = Settings::load_from_dir(Path::new("fixtures/with_several_config_files"));
+
+    assert_that!(settings).is_equal_to(Err(Error::Configuration(
+        "found several configuration files in fixtures/with_several_config_files: fixtures/with_several_config_files/surrealdb-migrate.toml, fixtures/with_several_config_files/surrealdb-migrate.yaml; only one of them is allowed".into(),
+    )));
+}
+
+#[test]
+fn explicit_yaml_config_file_overwrites_project_config_file() {
+    let settings = SettingsLoader::new()
+        .with_config_dir("fixtures/custom_config_dir")
+        .with_config_file("fixtures/config_files/local.yml")
+        .load()
+        .expect("failed to load settings");
+
+    assert_that!(settings.database.address).is_equal_to("ws://localhost:8002");
+    assert_that!(settings.database.username).is_equal_to("tester");
+}
+
+#[test]
+fn explicit_cargo_manifest_contributes_its_metadata() {
+    let (settings, origins) = SettingsLoader::new()
+        .with_config_dir("fixtures/custom_config_dir")
+        .with_config_file("fixtures/with_cargo_metadata/Cargo.toml")
+        .load_with_origins()
+        .expect("failed to load settings");
+
+    assert_that!(settings.database.address).is_equal_to("ws://cargo.example.com:8000");
+    assert_that!(settings.database.username).is_equal_to("tester");
+    assert_that!(
+        origins
+            .iter()
+            .find(|origin| origin.key == "database.address")
+            .map(|origin| origin.source.to_string())
+    )
+    .is_equal_to(Some(
+        "[package.metadata.surrealdb-migrate] in fixtures/with_cargo_metadata/Cargo.toml".into(),
+    ));
+}
+
+#[test]
+fn explicit_config_file_of_unsupported_format_fails() {
+    let settings = SettingsLoader::new()
+        .with_config_dir("fixtures/custom_config_dir")
+        .with_config_file("fixtures/config_files/local.ini")
+        .load();
+
+    assert_that!(settings).is_equal_to(Err(Error::Configuration(
+        "unsupported format of configuration file fixtures/config_files/local.ini, the filename extension must be one of .toml, .yaml, .yml or .json".into(),
+    )));
+}
+
+#[test]
+fn unknown_setting_in_json_config_file_fails_with_suggestion() {
+    let settings = SettingsLoader::new()
+        .with_config_dir("fixtures/custom_config_dir")
+        .with_config_file("fixtures/config_files/unknown_setting.json")
+        .load();
+
+    assert_that!(settings).is_equal_to(Err(Error::Configuration(
+        "unknown setting database.adress in fixtures/config_files/unknown_setting.json, did you mean database.address?".into(),
+    )));
+}
+
+#[test]
+fn load_settings_with_the_source_of_each_value() {
+    env::set_var("SURMIG_DATABASE_DATABASE", "from_environment");
+
+    let (settings, origins) = SettingsLoader::new()
+        .with_config_dir("fixtures/with_profiles")
+        .with_profile("staging")
+        .load_with_origins()
+        .expect("failed to load settings");
+
+    env::remove_var("SURMIG_DATABASE_DATABASE");
+
+    let project_file = PathBuf::from("fixtures/with_profiles/surrealdb-migrate.toml");
+    let source_of = |key: &str| {
+        origins
+            .iter()
+            .find(|origin| origin.key == key)
+            .map(|origin| origin.source.clone())
+    };
+    assert_that!(settings.database.database).is_equal_to("from_environment");
+    assert_that!(source_of("database.capacity")).is_equal_to(Some(SettingSource::Default));
+    assert_that!(source_of("database.username"))
+        .is_equal_to(Some(SettingSource::File(project_file.clone())));
+    assert_that!(source_of("database.address")).is_equal_to(Some(SettingSource::Profile {
+        name: "staging".into(),
+        file: project_file,
+    }));
+    assert_that!(source_of("database.database")).is_equal_to(Some(SettingSource::Environment(
+        "SURMIG_DATABASE_DATABASE".into(),
+    )));
+    assert_that!(source_of("profiles.staging.database.address")).is_equal_to(None);
+}
+
+#[test]
+fn load_settings_with_the_password_file_as_source_of_the_password() {
+    let (_settings, origins) = SettingsLoader::new()
+        .with_config_dir("fixtures/with_password_file")
+        .load_with_origins()
+        .expect("failed to load settings");
+
+    let source_of_password = origins
+        .iter()
+        .find(|origin| origin.key == "database.password")
+        .map(|origin| origin.source.clone());
+    assert_that!(source_of_password).is_equal_to(Some(SettingSource::PasswordFile(
+        "fixtures/with_password_file/db_password".into(),
+    )));
+}
+
+#[test]
+fn load_settings_with_origins_does_not_run_the_password_command() {
+    let (_settings, origins) = SettingsLoader::new()
+        .with_config_dir("fixtures/with_password_command")
+        .load_with_origins()
+        .expect("failed to load settings");
+
+    let source_of_password = origins
+        .iter()
+        .find(|origin| origin.key == "database.password")
+        .map(|origin| origin.source.clone());
+    assert_that!(source_of_password)
+        .is_equal_to(Some(SettingSource::PasswordCommand("exit 3".into())));
+}
+
+#[test]
+fn load_password_from_password_file() {
+    let settings = Settings::load_from_dir(Path::new("fixtures/with_password_file"))
+        .expect("failed to load settings");
+
+    assert_that!(&settings.database.password).is_equal_to("s3cr3t-from-file");
+    assert_that!(settings.db_client_config().password).is_equal_to("s3cr3t-from-file");
+}
+
+#[test]
+fn load_password_from_missing_password_file_fails() {
+    env::set_var("SURMIG_DATABASE_PASSWORD_FILE", "fixtures/missing_password");
+
+    let settings = Settings::load_from_dir(Path::new("fixtures/custom_config_dir"));
+
+    env::remove_var("SURMIG_DATABASE_PASSWORD_FILE");
+
+    let error = settings.expect_err("loading settings should fail");
+    assert_that!(error.to_string()).starts_with(
+        "failed to load settings: failed to read password file fixtures/missing_password: ",
+    );
+}
+
+#[test]
+fn load_password_from_output_of_password_command() {
+    env::set_var(
+        "SURMIG_DATABASE_PASSWORD_COMMAND",
+        "echo s3cr3t-from-command",
+    );
+
+    let settings = Settings::load_from_dir(Path::new("fixtures/custom_config_dir"));
+
+    env::remove_var("SURMIG_DATABASE_PASSWORD_COMMAND");
+
+    let settings = settings.expect("failed to load settings");
+    assert_that!(settings.database.password).is_equal_to("s3cr3t-from-command");
+}
+
+#[test]
+fn load_password_from_failing_password_command_fails() {
+    env::set_var("SURMIG_DATABASE_PASSWORD_COMMAND", "exit 3");
+
+    let settings = Settings::load_from_dir(Path::new("fixtures/custom_config_dir"));
+
+    env::remove_var("SURMIG_DATABASE_PASSWORD_COMMAND");
+
+    let error = settings.expect_err("loading settings should fail");
+    assert_that!(error.to_string())
+        .starts_with("failed to load settings: password command `exit 3` failed with ");
+}
+
+#[test]
+fn password_file_and_password_command_must_not_be_set_both() {
+    env::set_var(
+        "SURMIG_DATABASE_PASSWORD_COMMAND",
+        "echo s3cr3t-from-command",
+    );
+
+    let settings = Settings::load_from_dir(Path::new("fixtures/with_password_file"));
+
+    env::remove_var("SURMIG_DATABASE_PASSWORD_COMMAND");
+
+    assert_that!(settings).is_equal_to(Err(Error::Configuration(
+        "password-file and password-command must not be set both".into(),
+    )));
+}
+
+#[test]
+fn debug_output_of_database_settings_does_not_contain_the_password() {
+    let settings = Settings::load_from_dir(Path::new("fixtures/custom_config_dir"))
+        .expect("failed to load settings");
+
+    let settings_debug = format!("{:?}", settings.database);
+    let db_config_debug = format!("{:?}", settings.db_client_config());
+
+    assert_that!(settings_debug.as_str()).does_not_contain("s3cr3t");
+    assert_that!(settings_debug.as_str()).contains(r#"password: "<redacted>""#);
+    assert_that!(db_config_debug.as_str()).does_not_contain("s3cr3t");
+    assert_that!(db_config_debug.as_str()).contains(r#"password: "<redacted>""#);
+}
+
+#[test]
+fn load_record_access_settings() {
+    let settings = Settings::load_from_dir(Path::new("fixtures/with_record_access"))
+        .expect("failed to load settings");
+
+    let db_client_config = settings.db_client_config();
+
+    assert_that!(db_client_config.auth_level).is_equal_to(DbAuthLevel::Record);
+    assert_that!(db_client_config.access).is_equal_to("migrator");
+    assert_that!(db_client_config.access_variables).is_equal_to(BTreeMap::from([
+        ("email".to_string(), "ci@example.com".to_string()),
+        ("password".into(), "s3cr3t".into()),
+        ("tenantId".into(), "acme".into()),
+    ]));
+}
+
+#[test]
+fn load_token_settings_of_profile() {
+    let settings = Settings::load_profile_from_dir(Path::new("fixtures/with_record_access"), "ci")
+        .expect("failed to load settings");
+
+    let db_client_config = settings.db_client_config();
+
+    assert_that!(db_client_config.auth_level).is_equal_to(DbAuthLevel::Token);
+    assert_that!(db_client_config.token)
+        .is_equal_to("eyJhbGciOiJIUzUxMiIsInR5cCI6IkpXVCJ9.e30.c2lnbmF0dXJl");
+}
+
+#[test]
+fn access_variables_from_environment_variable_are_merged_with_config_file() {
+    env::set_var(
+        "SURMIG_DATABASE_ACCESS_VARIABLES",
+        "password=from-environment, region = eu",
+    );
+
+    let settings = Settings::load_from_dir(Path::new("fixtures/with_record_access"));
+
+    env::remove_var("SURMIG_DATABASE_ACCESS_VARIABLES");
+
+    let settings = settings.expect("failed to load settings");
+    assert_that!(settings.database.access_variables).is_equal_to(BTreeMap::from([
+        ("email".to_string(), "ci@example.com".to_string()),
+        ("password".into(), "from-environment".into()),
+        ("region".into(), "eu".into()),
+        ("tenantId".into(), "acme".into()),
+    ]));
+}
+
+#[test]
+fn invalid_access_variables_in_environment_variable_fails() {
+    env::set_var("SURMIG_DATABASE_ACCESS_VARIABLES", "email");
+
+    let settings = Settings::load_from_dir(Path::new("fixtures/with_record_access"));
+
+    env::remove_var("SURMIG_DATABASE_ACCESS_VARIABLES");
+
+    assert_that!(settings).is_equal_to(Err(Error::Configuration(
+        "invalid value of environment variable SURMIG_DATABASE_ACCESS_VARIABLES: expected a comma separated list of name=value pairs, but got \"email\"".into(),
+    )));
+}
+
+#[test]
+fn unknown_table_setting_fails_with_suggestion() {
+    let settings = Settings::load_from_dir(Path::new("fixtures/with_unknown_access_setting"));
+
+    assert_that!(settings).is_equal_to(Err(Error::Configuration(
+        "unknown setting database.access-variable.email in fixtures/with_unknown_access_setting/surrealdb-migrate.toml, did you mean database.access-variables?".into(),
+    )));
+}
+
+#[test]
+fn debug_output_of_database_settings_does_not_contain_the_token_or_access_variables() {
+    let settings = Settings::load_profile_from_dir(Path::new("fixtures/with_record_access"), "ci")
+        .expect("failed to load settings");
+
+    let settings_debug = format!("{:?}", settings.database);
+    let db_config_debug = format!("{:?}", settings.db_client_config());
+
+    assert_that!(settings_debug.as_str()).does_not_contain("eyJhbGciOiJIUzUxMiIsInR5cCI6IkpXVCJ9");
+    assert_that!(settings_debug.as_str()).does_not_contain("s3cr3t");
+    assert_that!(db_config_debug.as_str()).does_not_contain("eyJhbGciOiJIUzUxMiIsInR5cCI6IkpXVCJ9");
+    assert_that!(db_config_debug.as_str()).does_not_contain("s3cr3t");
+}
+
+#[test]
+fn interpolate_environment_variables_in_config_file() {
+    env::set_var("DB_HOST", "db.example.com");
+    env::set_var("TEAM", "blue");
+
+    let settings = Settings::load_from_dir(Path::new("fixtures/with_env_references"));
+
+    env::remove_var("DB_HOST");
+    env::remove_var("TEAM");
+
+    let settings = settings.expect("failed to load settings");
+    assert_that!(settings.database.address).is_equal_to("wss://db.example.com:8000");
+    assert_that!(settings.database.namespace).is_equal_to("blue_app");
+    assert_that!(settings.database.database).is_equal_to("shop");
+    assert_that!(settings.database.password).is_equal_to("pa$word");
+    assert_that!(settings.files.exclude).is_equal_to("$HOME|.*");
+}
+
+#[test]
+fn interpolate_environment_variable_with_default_value_in_config_file() {
+    env::set_var("DB_HOST", "db.example.com");
+    env::set_var("TEAM", "blue");
+    env::set_var("DB_NAME", "orders");
+
+    let settings = Settings::load_from_dir(Path::new("fixtures/with_env_references"));
+
+    env::remove_var("DB_HOST");
+    env::remove_var("TEAM");
+    env::remove_var("DB_NAME");
+
+    let settings = settings.expect("failed to load settings");
+    assert_that!(settings.database.database).is_equal_to("orders");
+}
+
+#[test]
+fn interpolate_unset_environment_variable_in_config_file_fails() {
+    env::set_var("DB_HOST", "db.example.com");
+
+    let settings = Settings::load_from_dir(Path::new("fixtures/with_env_references"));
+
+    env::remove_var("DB_HOST");
+
+    assert_that!(settings).is_equal_to(Err(Error::Configuration(
+        "invalid value of setting database.namespace in fixtures/with_env_references/surrealdb-migrate.toml: environment variable TEAM is not set".into(),
+    )));
+}
+
+mod interpolation {
+    #![allow(clippy::literal_string_with_formatting_args)]
+
+    use super::*;
+    use crate::interpolate::{InterpolationError, interpolate};
+
+    #[test]
+    fn value_without_variable_references_is_unchanged() {
+        assert_that!(interpolate("ws://localhost:8000"))
+            .is_equal_to(Ok(String::from("ws://localhost:8000")));
+    }
+
+    #[test]
+    fn default_value_is_used_for_empty_variable() {
+        env::set_var("EMPTY_VAR", "");
+
+        let interpolated = interpolate("${EMPTY_VAR:-fallback}");
+
+        env::remove_var("EMPTY_VAR");
+
+        assert_that!(interpolated).is_equal_to(Ok(String::from("fallback")));
+    }
+
+    #[test]
+    fn several_references_in_one_value() {
+        env::set_var("FIRST", "one");
+        env::set_var("SECOND", "two");
+
+        let interpolated = interpolate("${FIRST}-${SECOND}-${THIRD:-three}");
+
+        env::remove_var("FIRST");
+        env::remove_var("SECOND");
+
+        assert_that!(interpolated).is_equal_to(Ok(String::from("one-two-three")));
+    }
+
+    #[test]
+    fn escaped_dollar_sign_is_not_interpolated() {
+        assert_that!(interpolate("$${NOT_A_VAR} costs 5$"))
+            .is_equal_to(Ok(String::from("${NOT_A_VAR} costs 5$")));
+    }
+
+    #[test]
+    fn unterminated_reference_fails() {
+        assert_that!(interpolate("wss://${DB_HOST:8000"))
+            .is_equal_to(Err(InterpolationError::Unterminated));
+    }
+
+    #[test]
+    fn reference_without_name_fails() {
+        assert_that!(interpolate("${:-default}")).is_equal_to(Err(InterpolationError::MissingName));
+    }
+}
+
+#[test]
+fn connect_settings_from_environment_variables() {
+    env::set_var("SURMIG_DATABASE_CONNECT_TIMEOUT", "2m");
+    env::set_var("SURMIG_DATABASE_CONNECT_RETRIES", "12");
+    env::set_var("SURMIG_DATABASE_CONNECT_RETRY_INTERVAL", "250ms");
+
+    let settings = Settings::load_from_dir(Path::new("fixtures/custom_config_dir"));
+
+    env::remove_var("SURMIG_DATABASE_CONNECT_TIMEOUT");
+    env::remove_var("SURMIG_DATABASE_CONNECT_RETRIES");
+    env::remove_var("SURMIG_DATABASE_CONNECT_RETRY_INTERVAL");
+
+    let settings = settings.expect("failed to load settings");
+    let db_client_config = settings.db_client_config();
+    assert_that!(db_client_config.connect_timeout).is_equal_to(Duration::from_secs(120));
+    assert_that!(db_client_config.connect_retries).is_equal_to(12);
+    assert_that!(db_client_config.connect_retry_interval).is_equal_to(Duration::from_millis(250));
+}
+
+#[test]
+fn invalid_duration_in_environment_variable_fails() {
+    env::set_var("SURMIG_DATABASE_CONNECT_TIMEOUT", "ten seconds");
+
+    let settings = Settings::load_from_dir(Path::new("fixtures/custom_config_dir"));
+
+    env::remove_var("SURMIG_DATABASE_CONNECT_TIMEOUT");
+
+    assert_that!(settings).is_equal_to(Err(Error::Configuration(
+        "invalid value: string \"ten seconds\", expected a duration like \"500ms\", \"10s\", \"5m\" or \"1h\" for key `database.connect-timeout`".into(),
+    )));
+}
+
+#[test]
+fn engine_settings_from_environment_variables() {
+    env::set_var("SURMIG_DATABASE_QUERY_TIMEOUT", "30m");
+    env::set_var("SURMIG_DATABASE_TRANSACTION_TIMEOUT", "45m");
+    env::set_var("SURMIG_DATABASE_STRICT", "yes");
+    env::set_var("SURMIG_DATABASE_CAPABILITIES", "denyall");
+
+    let settings = Settings::load_from_dir(Path::new("fixtures/custom_config_dir"));
+
+    env::remove_var("SURMIG_DATABASE_QUERY_TIMEOUT");
+    env::remove_var("SURMIG_DATABASE_TRANSACTION_TIMEOUT");
+    env::remove_var("SURMIG_DATABASE_STRICT");
+    env::remove_var("SURMIG_DATABASE_CAPABILITIES");
+
+    let settings = settings.expect("failed to load settings");
+    let db_client_config = settings.db_client_config();
+    assert_that!(db_client_config.query_timeout).is_equal_to(Some(Duration::from_secs(1800)));
+    assert_that!(db_client_config.transaction_timeout).is_equal_to(Some(Duration::from_secs(2700)));
+    assert_that!(db_client_config.strict).is_true();
+    assert_that!(db_client_config.capabilities).is_equal_to(DbCapabilities::DenyAll);
+}
+
+#[test]
+fn tls_settings_from_environment_variables() {
+    env::set_var("SURMIG_DATABASE_TLS_CA_FILE", "/etc/ssl/internal-ca.pem");
+    env::set_var(
+        "SURMIG_DATABASE_TLS_CLIENT_CERT_FILE",
+        "/run/secrets/client.pem",
+    );
+    env::set_var(
+        "SURMIG_DATABASE_TLS_CLIENT_KEY_FILE",
+        "/run/secrets/client.key",
+    );
+    env::set_var("SURMIG_DATABASE_TLS_SERVER_NAME", "surrealdb.cluster.local");
+
+    let settings = Settings::load_from_dir(Path::new("fixtures/custom_config_dir"));
+
+    env::remove_var("SURMIG_DATABASE_TLS_CA_FILE");
+    env::remove_var("SURMIG_DATABASE_TLS_CLIENT_CERT_FILE");
+    env::remove_var("SURMIG_DATABASE_TLS_CLIENT_KEY_FILE");
+    env::remove_var("SURMIG_DATABASE_TLS_SERVER_NAME");
+
+    let settings = settings.expect("failed to load settings");
+    let db_client_config = settings.db_client_config();
+    assert_that!(db_client_config.tls_ca_file)
+        .is_equal_to(Some(Path::new("/etc/ssl/internal-ca.pem").into()));
+    assert_that!(db_client_config.tls_client_cert_file)
+        .is_equal_to(Some(Path::new("/run/secrets/client.pem").into()));
+    assert_that!(db_client_config.tls_client_key_file)
+        .is_equal_to(Some(Path::new("/run/secrets/client.key").into()));
+    assert_that!(db_client_config.tls_server_name)
+        .is_equal_to(Some("surrealdb.cluster.local".into()));
+    assert_that!(db_client_config.tls_accept_invalid_certs).is_false();
+}
+
+#[test]
+fn invalid_capabilities_in_environment_variable_fails() {
+    env::set_var("SURMIG_DATABASE_CAPABILITIES", "Some");
+
+    let settings = Settings::load_from_dir(Path::new("fixtures/custom_config_dir"));
+
+    env::remove_var("SURMIG_DATABASE_CAPABILITIES");
+
+    assert_that!(settings).is_equal_to(Err(Error::Configuration(
+        "invalid value: string \"Some\", expected Default, AllowAll or DenyAll for key `database.capabilities`".into(),
+    )));
+}
+
+mod duration {
+    use super::*;
+
+    #[test]
+    fn duration_with_unit() {
+        assert_that!(parse_duration("500ms")).is_equal_to(Ok(Duration::from_millis(500)));
+        assert_that!(parse_duration("10s")).is_equal_to(Ok(Duration::from_secs(10)));
+        assert_that!(parse_duration("5m")).is_equal_to(Ok(Duration::from_secs(300)));
+        assert_that!(parse_duration("1h")).is_equal_to(Ok(Duration::from_secs(3600)));
+    }
+
+    #[test]
+    fn duration_without_unit_is_in_seconds() {
+        assert_that!(parse_duration("15")).is_equal_to(Ok(Duration::from_secs(15)));
+    }
+
+    #[test]
+    fn duration_with_unknown_unit_fails() {
+        assert_that!(parse_duration("3d")).is_equal_to(Err(String::from(
+            "invalid duration \"3d\", expected a number followed by one of the units ms, s, m or h, e.g. \"10s\"",
+        )));
+    }
+
+    #[test]
+    fn duration_with_fraction_fails() {
+        assert_that!(parse_duration("1.5s")).is_err();
+    }
+
+    #[test]
+    fn duration_without_number_fails() {
+        assert_that!(parse_duration("s")).is_err();
+    }
+}
+
+#[test]
+fn environment_variable_values_are_not_interpreted_as_toml() {
+    env::set_var("SURMIG_DATABASE_PASSWORD", r#"pa"ss\word"#);
+
+    let settings = Settings::load_from_dir(Path::new("fixtures/custom_config_dir"));
+
+    env::remove_var("SURMIG_DATABASE_PASSWORD");
+
+    let settings = settings.expect("failed to load settings");
+    assert_that!(settings.database.password).is_equal_to(r#"pa"ss\word"#);
+}
+
+#[test]
+fn boolean_settings_from_environment_variables_accept_common_spellings() {
+    env::set_var("SURMIG_MIGRATION_IGNORE_CHECKSUM", "Yes");
+    env::set_var("SURMIG_MIGRATION_IGNORE_ORDER", "on");
+    env::set_var("SURMIG_MIGRATION_ALLOW_IGNORE_CHECKSUM", "1");
+
+    let settings = Settings::load_from_dir(Path::new("fixtures/custom_config_dir"));
+
+    env::remove_var("SURMIG_MIGRATION_IGNORE_CHECKSUM");
+    env::remove_var("SURMIG_MIGRATION_IGNORE_ORDER");
+    env::remove_var("SURMIG_MIGRATION_ALLOW_IGNORE_CHECKSUM");
+
+    let settings = settings.expect("failed to load settings");
+    assert_that!(settings.migration).is_equal_to(MigrationSettings {
+        ignore_checksum: true,
+        ignore_order: true,
+        allow_ignore_checksum: true,
+    });
+}
+
+#[test]
+fn invalid_boolean_in_environment_variable_fails() {
+    env::set_var("SURMIG_MIGRATION_IGNORE_ORDER", "maybe");
+
+    let settings = Settings::load_from_dir(Path::new("fixtures/custom_config_dir"));
+
+    env::remove_var("SURMIG_MIGRATION_IGNORE_ORDER");
+
+    assert_that!(settings).is_equal_to(Err(Error::Configuration(
+        "invalid value of environment variable SURMIG_MIGRATION_IGNORE_ORDER: expected a boolean, like true or false, but got \"maybe\"".into(),
+    )));
+}
+
+#[test]
+fn invalid_integer_in_environment_variable_fails() {
+    env::set_var("SURMIG_DATABASE_CAPACITY", "lots");
+
+    let settings = Settings::load_from_dir(Path::new("fixtures/custom_config_dir"));
+
+    env::remove_var("SURMIG_DATABASE_CAPACITY");
+
+    assert_that!(settings).is_equal_to(Err(Error::Configuration(
+        "invalid value of environment variable SURMIG_DATABASE_CAPACITY: expected a non-negative integer, but got \"lots\"".into(),
+    )));
+}
+
+#[test]
+fn unknown_environment_variables_are_reported_and_ignored() {
+    env::set_var("SURMIG_DATABSE_ADDRESS", "wss://typo.example.com");
+    env::set_var("SURMIG_MIGRATION_IGNORE_CHECKSUMS", "true");
+    env::set_var("SURMIG_PROFILE", "staging");
+    env::set_var("SURMIG_DATABASE_NAMESPACE", "known");
+
+    let unknown_variables = unknown_environment_variables();
+    let settings = Settings::load_from_dir(Path::new("fixtures/with_profiles"));
+
+    env::remove_var("SURMIG_DATABSE_ADDRESS");
+    env::remove_var("SURMIG_MIGRATION_IGNORE_CHECKSUMS");
+    env::remove_var("SURMIG_PROFILE");
+    env::remove_var("SURMIG_DATABASE_NAMESPACE");
+
+    assert_that!(unknown_variables).is_equal_to(vec![
+        "SURMIG_DATABSE_ADDRESS".to_string(),
+        "SURMIG_MIGRATION_IGNORE_CHECKSUMS".to_string(),
+    ]);
+    let settings = settings.expect("failed to load settings");
+    assert_that!(settings.database.address).is_equal_to("wss://staging.example.com:8000");
+    assert_that!(settings.database.namespace).is_equal_to("known");
+}
+
+#[test]
+fn unknown_setting_in_config_file_fails_with_suggestion() {
+    let settings = Settings::load_from_dir(Path::new("fixtures/with_unknown_setting"));
+
+    assert_that!(settings).is_equal_to(Err(Error::Configuration(
+        "unknown setting migration.ignore_checksum in fixtures/with_unknown_setting/surrealdb-migrate.toml, did you mean migration.ignore-checksum?".into(),
+    )));
+}
+
+#[test]
+fn unknown_setting_in_profile_fails_with_suggestion() {
+    let settings = Settings::load_from_dir(Path::new("fixtures/with_unknown_profile_setting"));
+
+    assert_that!(settings).is_equal_to(Err(Error::Configuration(
+        "unknown setting profiles.prod.database.adress in fixtures/with_unknown_profile_setting/surrealdb-migrate.toml, did you mean profiles.prod.database.address?".into(),
+    )));
+}
+
+#[test]
+fn unknown_setting_without_similar_setting_fails_without_suggestion() {
+    let settings = SettingsLoader::new()
+        .with_config_dir("fixtures/custom_config_dir")
+        .with_config_file("fixtures/config_files/unknown_section.toml")
+        .load();
+
+    assert_that!(settings).is_equal_to(Err(Error::Configuration(
+        "unknown setting logging.level in fixtures/config_files/unknown_section.toml".into(),
+    )));
+}
+
+#[test]
+fn json_schema_file_is_up_to_date() {
+    let schema_file: serde_json::Value =
+        serde_json::from_str(include_str!("../resources/surrealdb-migrate.schema.json"))
+            .expect("failed to parse JSON schema file");
+
+    assert_that!(config_file_schema()).is_equal_to(schema_file);
+}
+
+#[test]
+fn json_schema_allows_profiles_and_rejects_unknown_sections() {
+    let schema = config_file_schema();
+
+    assert_that!(schema["additionalProperties"].clone())
+        .is_equal_to(serde_json::Value::Bool(false));
+    assert_that!(
+        schema["properties"]["profiles"]["additionalProperties"]["properties"]["database"]["properties"]["address"]["type"].clone()
+    )
+    .is_equal_to(serde_json::Value::from("string"));
+    assert_that!(schema.get("required")).is_none();
 }
@@ -1,26 +1,48 @@
+pub mod schema;
+mod tls;
+
 use chrono::{NaiveDateTime, Utc};
+use database_migration::callback::CallbackScript;
 use database_migration::checksum::Checksum;
-use database_migration::config::{DbAuthLevel, DbClientConfig, MIGRATION_KEY_FORMAT_STR};
-use database_migration::error::Error;
+use database_migration::config::{
+    DbAuthLevel, DbCapabilities, DbClientConfig, MIGRATION_KEY_FORMAT_STR,
+};
+use database_migration::error::{DatabaseError, Error, ErrorCategory};
 use database_migration::migration::{
-    ApplicableMigration, Execution, Migration, MigrationKind, MigrationsTableInfo, Reversion,
+    ApplicableMigration, Execution, FailedAttempt, HistoryAction, HistoryEvent, Migration,
+    MigrationKind, MigrationsTableInfo, Reversion,
 };
+use database_migration::scope::MigrationScope;
+use database_migration::script::ScriptError;
+use schema::escape_identifier;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::ops::Deref;
+use std::path::Path;
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use surrealdb::engine::any::{Any, connect};
 use surrealdb::opt::auth;
 use surrealdb::opt::auth::Jwt;
-use surrealdb::{Surreal, sql};
+use surrealdb::opt::capabilities::Capabilities;
+use surrealdb::{Response, Surreal, sql};
+use tokio::time::{self, sleep};
 
 pub const SURREALDB_CONTAINER_IMAGE_TAG: &str = "v2.3";
 
 const DEFINE_MIGRATIONS_TABLE: &str = include_str!("../surql/define_migrations_table.surql");
 
+const UPGRADE_MIGRATIONS_TABLE_1_0: &str =
+    include_str!("../surql/upgrade_migrations_table_1_0.surql");
+
 const TABLE_VERSION_KEY: &str = "version:";
 
+const HISTORY_TABLE_SUFFIX: &str = "_history";
+
+const QUERY_NOT_EXECUTED_MESSAGE: &str = "The query was not executed due to a failed transaction";
+
+const MAX_CONNECT_RETRY_INTERVAL: Duration = Duration::from_secs(60);
+
 pub type DbError = surrealdb::Error;
 
 #[derive(Debug)]
@@ -28,6 +50,8 @@ struct Connection {
     client: Surreal<Any>,
     token: Jwt,
     username: String,
+    namespace: String,
+    database: String,
 }
 
 #[derive(Debug, Clone)]
@@ -36,12 +60,20 @@ pub struct DbConnection {
 }
 
 impl DbConnection {
-    fn new(client: Surreal<Any>, token: Jwt, username: String) -> Self {
+    fn new(
+        client: Surreal<Any>,
+        token: Jwt,
+        username: String,
+        namespace: String,
+        database: String,
+    ) -> Self {
         Self {
             inner: Arc::new(Connection {
                 client,
                 token,
                 username,
+                namespace,
+                database,
             }),
         }
     }
@@ -57,6 +89,14 @@ impl DbConnection {
     pub fn username(&self) -> &str {
         &self.inner.username
     }
+
+    pub fn namespace(&self) -> &str {
+        &self.inner.namespace
+    }
+
+    pub fn database(&self) -> &str {
+        &self.inner.database
+    }
 }
 
 impl Deref for DbConnection {
@@ -67,59 +107,402 @@ impl Deref for DbConnection {
     }
 }
 
-pub async fn connect_to_database(config: &DbClientConfig<'_>) -> Result<DbConnection, DbError> {
-    let client = connect(config.address.as_ref()).await?;
+/// Connects to the database and signs in with the configured credentials.
+///
+/// Depending on the configured auth level it signs in as a system user, signs
+/// in via a record access method or authenticates with the configured token.
+///
+/// Each attempt to connect and sign in is limited to the configured
+/// `connect_timeout`. If the database server is not reachable, connecting is
+/// retried as often as configured by `connect_retries`. Before the first retry
+/// it waits for the `connect_retry_interval`, which doubles after each retry
+/// up to a maximum of one minute.
+///
+/// Failing to reach the database server is reported as an error of category
+/// `ErrorCategory::Connection`, failing to sign in as an error of category
+/// `ErrorCategory::Authentication`. A missing record access method or token
+/// is reported as an error of category `ErrorCategory::Configuration`.
+#[cfg_attr(feature = "tracing", tracing::instrument(
+    skip_all,
+    fields(
+        address = %config.address,
+        namespace = %config.namespace,
+        database = %config.database,
+        auth_level = ?config.auth_level,
+        username = %config.username,
+    ),
+    err,
+))]
+pub async fn connect_to_database(config: &DbClientConfig<'_>) -> Result<DbConnection, Error> {
+    check_credentials_given(config)?;
+    let mut retries = 0;
+    let mut retry_interval = config.connect_retry_interval;
+    loop {
+        match connect_with_timeout(config, config.connect_timeout).await {
+            Err(err)
+                if err.category() == ErrorCategory::Connection
+                    && retries < config.connect_retries =>
+            {
+                retries += 1;
+                log::warn!(
+                    "database not reachable, retry {retries} of {} in {retry_interval:?}: {err}",
+                    config.connect_retries
+                );
+                sleep(retry_interval).await;
+                retry_interval = next_retry_interval(retry_interval);
+            },
+            result => return result,
+        }
+    }
+}
 
-    let token = match config.auth_level {
-        DbAuthLevel::Root => client.signin(auth::Root {
-            username: &config.username,
-            password: &config.password,
-        }),
-        DbAuthLevel::Namespace => client.signin(auth::Namespace {
-            namespace: &config.namespace,
-            username: &config.username,
-            password: &config.password,
-        }),
-        DbAuthLevel::Database => client.signin(auth::Database {
-            namespace: &config.namespace,
-            database: &config.database,
+/// Waits until the database accepts authenticated queries and returns the
+/// connection.
+///
+/// Connecting and signing in is retried on connection and authentication
+/// errors until the given timeout is elapsed. The interval between two
+/// attempts starts with the configured `connect_retry_interval` and doubles
+/// after each attempt up to a maximum of one minute. If the database is not
+/// ready in time, the error of the last attempt is returned.
+#[cfg_attr(feature = "tracing", tracing::instrument(
+    skip_all,
+    fields(address = %config.address, timeout = ?timeout),
+    err,
+))]
+pub async fn wait_for_database(
+    config: &DbClientConfig<'_>,
+    timeout: Duration,
+) -> Result<DbConnection, Error> {
+    check_credentials_given(config)?;
+    let deadline = Instant::now() + timeout;
+    let mut retry_interval = config.connect_retry_interval;
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        let result = match connect_with_timeout(config, config.connect_timeout.min(remaining)).await
+        {
+            Ok(db) => check_query_accepted(&db).await.map(|()| db),
+            Err(err) => Err(err),
+        };
+        match result {
+            Err(err)
+                if matches!(
+                    err.category(),
+                    ErrorCategory::Connection | ErrorCategory::Authentication
+                ) && Instant::now() + retry_interval < deadline =>
+            {
+                log::warn!("database not ready, retry in {retry_interval:?}: {err}");
+                sleep(retry_interval).await;
+                retry_interval = next_retry_interval(retry_interval);
+            },
+            result => return result,
+        }
+    }
+}
+
+fn next_retry_interval(retry_interval: Duration) -> Duration {
+    retry_interval
+        .saturating_mul(2)
+        .min(MAX_CONNECT_RETRY_INTERVAL)
+}
+
+async fn check_query_accepted(db: &DbConnection) -> Result<(), Error> {
+    db.query("RETURN true")
+        .await
+        .map_err(db_error)?
+        .check()
+        .map_err(db_error)?;
+    Ok(())
+}
+
+async fn connect_with_timeout(
+    config: &DbClientConfig<'_>,
+    timeout: Duration,
+) -> Result<DbConnection, Error> {
+    time::timeout(timeout, connect_and_sign_in(config))
+        .await
+        .unwrap_or_else(|_| {
+            Err(Error::Db(DatabaseError::new(
+                ErrorCategory::Connection,
+                format!(
+                    "connecting to the database at {} timed out after {timeout:?}",
+                    config.address
+                ),
+            )))
+        })
+}
+
+/// Creates the options of the database engine from the client config.
+fn engine_config(config: &DbClientConfig<'_>) -> Result<surrealdb::opt::Config, Error> {
+    let capabilities = match config.capabilities {
+        DbCapabilities::Default => Capabilities::default(),
+        DbCapabilities::AllowAll => Capabilities::all(),
+        DbCapabilities::DenyAll => Capabilities::none(),
+    };
+    let mut engine_config = surrealdb::opt::Config::new()
+        .query_timeout(config.query_timeout)
+        .transaction_timeout(config.transaction_timeout)
+        .set_strict(config.strict)
+        .capabilities(capabilities);
+    if config.auth_level == DbAuthLevel::Root && is_embedded_engine(&config.address) {
+        // An embedded engine has no users, unless the root user is defined
+        // when the storage is opened.
+        engine_config = engine_config.user(auth::Root {
             username: &config.username,
             password: &config.password,
-        }),
+        });
     }
-    .await?;
+    tls::with_tls_settings(engine_config, config)
+}
+
+/// Returns whether the address selects an embedded database engine, like
+/// `mem://` or `surrealkv://path`, instead of a database server.
+fn is_embedded_engine(address: &str) -> bool {
+    let scheme = address
+        .split_once("://")
+        .map_or(address, |(scheme, _)| scheme);
+    !matches!(
+        &scheme.to_ascii_lowercase()[..],
+        "ws" | "wss" | "http" | "https"
+    )
+}
 
-    let _db = client
+async fn connect_and_sign_in(config: &DbClientConfig<'_>) -> Result<DbConnection, Error> {
+    let client = connect((config.address.as_ref(), engine_config(config)?))
+        .with_capacity(config.capacity)
+        .await
+        .map_err(|err| db_error_of_category(ErrorCategory::Connection, err))?;
+
+    let token = match config.auth_level {
+        DbAuthLevel::Root => {
+            client
+                .signin(auth::Root {
+                    username: &config.username,
+                    password: &config.password,
+                })
+                .await
+        },
+        DbAuthLevel::Namespace => {
+            client
+                .signin(auth::Namespace {
+                    namespace: &config.namespace,
+                    username: &config.username,
+                    password: &config.password,
+                })
+                .await
+        },
+        DbAuthLevel::Database => {
+            client
+                .signin(auth::Database {
+                    namespace: &config.namespace,
+                    database: &config.database,
+                    username: &config.username,
+                    password: &config.password,
+                })
+                .await
+        },
+        DbAuthLevel::Record => {
+            client
+                .signin(auth::Record {
+                    namespace: &config.namespace,
+                    database: &config.database,
+                    access: &config.access,
+                    params: &config.access_variables,
+                })
+                .await
+        },
+        DbAuthLevel::Token => client
+            .authenticate(config.token.to_string())
+            .await
+            .map(|()| Jwt::from(config.token.to_string())),
+    }
+    .map_err(|err| {
+        let category = match categorize(&err) {
+            ErrorCategory::Connection => ErrorCategory::Connection,
+            _ => ErrorCategory::Authentication,
+        };
+        db_error_of_category(category, err)
+    })?;
+
+    client
         .use_ns(config.namespace.to_string())
-        .use_db(config.database.to_string());
+        .use_db(config.database.to_string())
+        .await
+        .map_err(db_error)?;
 
     Ok(DbConnection::new(
         client,
         token,
         config.username.to_string(),
+        config.namespace.to_string(),
+        config.database.to_string(),
+    ))
+}
+
+fn check_credentials_given(config: &DbClientConfig<'_>) -> Result<(), Error> {
+    match config.auth_level {
+        DbAuthLevel::Record if config.access.is_empty() => Err(Error::Configuration(
+            "auth-level is Record, but no record access method is given".into(),
+        )),
+        DbAuthLevel::Token if config.token.is_empty() => Err(Error::Configuration(
+            "auth-level is Token, but no token is given".into(),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Lists the names of all databases in the namespace the given connection is
+/// using, sorted by name.
+#[cfg_attr(feature = "tracing", tracing::instrument(
+    level = "debug",
+    skip_all,
+    fields(namespace = %db.namespace()),
+    err,
+))]
+pub async fn list_databases_in_namespace(db: &DbConnection) -> Result<Vec<String>, Error> {
+    let mut ns_info = db.query("INFO FOR NS").await.map_err(db_error)?;
+    let databases: Option<HashMap<String, String>> = ns_info.take("databases").map_err(db_error)?;
+    let mut databases = databases
+        .map(|databases| databases.into_keys().collect::<Vec<_>>())
+        .unwrap_or_default();
+    databases.sort_unstable();
+    Ok(databases)
+}
+
+/// Selects the given namespace and database on the given connection.
+///
+/// All clones of a connection share the same session, so the selection
+/// applies to all clones of the given connection. The returned connection
+/// reports the selected namespace and database.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(db), err,)
+)]
+pub async fn use_namespace_and_database(
+    namespace: &str,
+    database: &str,
+    db: &DbConnection,
+) -> Result<DbConnection, Error> {
+    db.use_ns(namespace)
+        .use_db(database)
+        .await
+        .map_err(db_error)?;
+    Ok(DbConnection::new(
+        db.client().clone(),
+        db.token().clone(),
+        db.username().to_string(),
+        namespace.to_string(),
+        database.to_string(),
+    ))
+}
+
+/// Selects the namespace and database in which the migrations of the given
+/// scope are tracked, defining them if they do not exist yet.
+///
+/// See [`MigrationScope::tracking_location`] for where the migrations of each
+/// scope are tracked. As with [`use_namespace_and_database`], the selection
+/// applies to all clones of the given connection.
+#[cfg_attr(feature = "tracing", tracing::instrument(
+    level = "debug",
+    skip(db),
+    fields(namespace = %db.namespace(), database = %db.database()),
+    err,
+))]
+pub async fn use_tracking_database(
+    scope: MigrationScope,
+    db: &DbConnection,
+) -> Result<DbConnection, Error> {
+    let (namespace, database) = scope.tracking_location(db.namespace(), db.database());
+    if namespace == db.namespace() && database == db.database() {
+        return Ok(db.clone());
+    }
+    if namespace != db.namespace() {
+        db.query(format!(
+            "DEFINE NAMESPACE IF NOT EXISTS {}",
+            escape_identifier(namespace)
+        ))
+        .await
+        .map_err(db_error)?
+        .check()
+        .map_err(db_error)?;
+        db.use_ns(namespace).await.map_err(db_error)?;
+    }
+    db.query(format!(
+        "DEFINE DATABASE IF NOT EXISTS {}",
+        escape_identifier(database)
     ))
+    .await
+    .map_err(db_error)?
+    .check()
+    .map_err(db_error)?;
+    use_namespace_and_database(namespace, database, db).await
 }
 
+#[cfg_attr(feature = "tracing", tracing::instrument(
+    level = "debug",
+    skip_all,
+    fields(table = %table_name, namespace = %db.namespace(), database = %db.database()),
+    err,
+))]
 pub async fn define_migrations_table(table_name: &str, db: &DbConnection) -> Result<(), Error> {
-    db.query(DEFINE_MIGRATIONS_TABLE.replace("$migrations_table", table_name))
+    db.query(migrations_table_script(DEFINE_MIGRATIONS_TABLE, table_name))
         .await
-        .map_err(|err| Error::DbQuery(err.to_string()))?
+        .map_err(db_error)?
         .check()
-        .map_err(|err| Error::DbQuery(err.to_string()))?;
+        .map_err(db_error)?;
     Ok(())
 }
 
+/// Upgrades a migrations table defined by a former version to the current
+/// version.
+///
+/// A migrations table of version 1.0 gets the fields for recording failed
+/// attempts and a history table. Migrations tables of the current version and
+/// tables that have not been defined explicitly are left untouched.
+#[cfg_attr(feature = "tracing", tracing::instrument(
+    level = "debug",
+    skip_all,
+    fields(table = %table_name, namespace = %db.namespace(), database = %db.database()),
+    err,
+))]
+pub async fn upgrade_migrations_table(table_name: &str, db: &DbConnection) -> Result<(), Error> {
+    let MigrationsTableInfo::Table { version, .. } =
+        find_migrations_table_info(table_name, db).await?
+    else {
+        return Ok(());
+    };
+    if version.as_deref() == Some("1.0") {
+        let response = db
+            .query(migrations_table_script(
+                UPGRADE_MIGRATIONS_TABLE_1_0,
+                table_name,
+            ))
+            .await
+            .map_err(db_error)?;
+        check_transaction(response)?;
+    }
+    Ok(())
+}
+
+/// Fills in the names of the migrations table and its history table into the
+/// given script.
+fn migrations_table_script(script: &str, migrations_table: &str) -> String {
+    script
+        .replace("$migrations_table", migrations_table)
+        .replace("$history_table", &history_table_name(migrations_table))
+}
+
+#[cfg_attr(feature = "tracing", tracing::instrument(
+    level = "debug",
+    skip_all,
+    fields(table = %table_name, namespace = %db.namespace(), database = %db.database()),
+    err,
+))]
 pub async fn find_migrations_table_info(
     table_name: &str,
     db: &DbConnection,
 ) -> Result<MigrationsTableInfo, Error> {
-    let mut db_info = db
-        .query("INFO FOR DB")
-        .await
-        .map_err(|err| Error::DbQuery(err.to_string()))?;
-    let tables: Option<HashMap<String, String>> = db_info
-        .take("tables")
-        .map_err(|err| Error::DbQuery(err.to_string()))?;
+    let mut db_info = db.query("INFO FOR DB").await.map_err(db_error)?;
+    let tables: Option<HashMap<String, String>> = db_info.take("tables").map_err(db_error)?;
     let mut tables = tables.ok_or_else(|| Error::FetchingTableDefinitions(String::new()))?;
     if tables.is_empty() {
         return Ok(MigrationsTableInfo::NoTables);
@@ -173,19 +556,87 @@ struct MigrationExecutionData {
     execution_time: sql::Duration,
 }
 
+#[derive(Serialize, Deserialize)]
+struct FailedAttemptData {
+    key: String,
+    title: String,
+    kind: MigrationKind,
+    script_path: String,
+    checksum: Checksum,
+    success: bool,
+    attempted_at: sql::Datetime,
+    attempted_by: String,
+    errors: Vec<StatementErrorData>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct StatementErrorData {
+    statement: usize,
+    message: String,
+}
+
+impl FailedAttemptData {
+    fn into_failed_attempt(self) -> Result<FailedAttempt, Error> {
+        let key = NaiveDateTime::parse_from_str(&self.key, MIGRATION_KEY_FORMAT_STR)
+            .map_err(key_error)?;
+        Ok(FailedAttempt {
+            key,
+            title: self.title,
+            kind: self.kind,
+            script_path: self.script_path.into(),
+            checksum: self.checksum,
+            attempted_by: self.attempted_by,
+            attempted_at: self.attempted_at.0,
+            errors: self
+                .errors
+                .into_iter()
+                .map(|error| (error.statement, error.message))
+                .collect(),
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct HistoryEventData {
+    key: String,
+    title: String,
+    kind: MigrationKind,
+    action: HistoryAction,
+    checksum: Checksum,
+    performed_by: String,
+    performed_at: sql::Datetime,
+    execution_time: sql::Duration,
+}
+
+/// Returns the name of the table that holds the history of the migrations
+/// tracked in the given migrations table.
+pub fn history_table_name(migrations_table: &str) -> String {
+    format!("{migrations_table}{HISTORY_TABLE_SUFFIX}")
+}
+
+#[cfg_attr(feature = "tracing", tracing::instrument(
+    level = "debug",
+    skip_all,
+    fields(table = %migrations_table, namespace = %db.namespace(), database = %db.database()),
+    err,
+))]
 pub async fn select_all_executions_sorted_by_key(
     migrations_table: &str,
     db: &DbConnection,
 ) -> Result<Vec<Execution>, Error> {
     let execution_data: Vec<MigrationExecutionData> = db
-        .select(migrations_table)
+        .query(format!(
+            "SELECT * FROM {migrations_table} WHERE success != false"
+        ))
         .await
-        .map_err(|err| Error::DbQuery(err.to_string()))?;
+        .map_err(db_error)?
+        .take(0)
+        .map_err(db_error)?;
     let mut executions = execution_data
         .into_iter()
         .map(|data| {
             NaiveDateTime::parse_from_str(&data.key, MIGRATION_KEY_FORMAT_STR)
-                .map_err(|err| Error::DbQuery(err.to_string()))
+                .map_err(key_error)
                 .map(|key| Execution {
                     key,
                     applied_rank: data.applied_rank,
@@ -200,19 +651,29 @@ pub async fn select_all_executions_sorted_by_key(
     Ok(executions)
 }
 
+#[cfg_attr(feature = "tracing", tracing::instrument(
+    level = "debug",
+    skip_all,
+    fields(table = %migrations_table, namespace = %db.namespace(), database = %db.database()),
+    err,
+))]
 pub async fn select_all_executions(
     migrations_table: &str,
     db: &DbConnection,
 ) -> Result<HashMap<NaiveDateTime, Execution>, Error> {
     let execution_data: Vec<MigrationExecutionData> = db
-        .select(migrations_table)
+        .query(format!(
+            "SELECT * FROM {migrations_table} WHERE success != false"
+        ))
         .await
-        .map_err(|err| Error::DbQuery(err.to_string()))?;
+        .map_err(db_error)?
+        .take(0)
+        .map_err(db_error)?;
     execution_data
         .into_iter()
         .map(|data| {
             NaiveDateTime::parse_from_str(&data.key, MIGRATION_KEY_FORMAT_STR)
-                .map_err(|err| Error::DbQuery(err.to_string()))
+                .map_err(key_error)
                 .map(|key| {
                     (
                         key,
@@ -230,6 +691,12 @@ pub async fn select_all_executions(
         .collect::<Result<HashMap<_, _>, _>>()
 }
 
+#[cfg_attr(feature = "tracing", tracing::instrument(
+    level = "debug",
+    skip_all,
+    fields(table = %migrations_table, namespace = %db.namespace(), database = %db.database()),
+    err,
+))]
 pub async fn insert_migration_execution(
     migration: Migration,
     execution: Execution,
@@ -250,16 +717,141 @@ pub async fn insert_migration_execution(
         execution_time: sql::Duration::from(execution.execution_time),
     };
 
-    let response: Option<MigrationExecutionData> = db
-        .create((migrations_table, key.clone()))
+    let event = HistoryEventData {
+        key: key.clone(),
+        title: content.title.clone(),
+        kind: content.kind,
+        action: HistoryAction::Applied,
+        checksum: content.checksum,
+        performed_by: content.applied_by.clone(),
+        performed_at: content.applied_at.clone(),
+        execution_time: content.execution_time,
+    };
+
+    let response = db
+        .query(
+            "\
+BEGIN TRANSACTION;
+DELETE type::thing($table, $key) WHERE success = false;
+CREATE type::thing($table, $key) CONTENT $execution;
+CREATE type::table($history_table) CONTENT $event;
+COMMIT TRANSACTION;
+",
+        )
+        .bind(("table", migrations_table.to_string()))
+        .bind(("history_table", history_table_name(migrations_table)))
+        .bind(("key", key))
+        .bind(("execution", content))
+        .bind(("event", event))
+        .await
+        .map_err(db_error)?;
+    check_transaction(response)?;
+    Ok(())
+}
+
+/// Records a failed attempt to apply a migration in the migrations table.
+///
+/// A previously recorded failed attempt of the same migration is replaced.
+/// The failed attempt is removed when the migration is applied successfully.
+#[cfg_attr(feature = "tracing", tracing::instrument(
+    level = "debug",
+    skip_all,
+    fields(table = %migrations_table, namespace = %db.namespace(), database = %db.database()),
+    err,
+))]
+pub async fn insert_failed_attempt(
+    attempt: FailedAttempt,
+    migrations_table: &str,
+    db: &DbConnection,
+) -> Result<(), Error> {
+    let key = attempt.key.format(MIGRATION_KEY_FORMAT_STR).to_string();
+
+    let content = FailedAttemptData {
+        key: key.clone(),
+        title: attempt.title,
+        kind: attempt.kind,
+        script_path: attempt.script_path.to_string_lossy().into(),
+        checksum: attempt.checksum,
+        success: false,
+        attempted_at: sql::Datetime::from(attempt.attempted_at),
+        attempted_by: attempt.attempted_by,
+        errors: attempt
+            .errors
+            .into_iter()
+            .map(|(statement, message)| StatementErrorData { statement, message })
+            .collect(),
+    };
+
+    let response: Option<FailedAttemptData> = db
+        .upsert((migrations_table, key.clone()))
         .content(content)
         .await
-        .map_err(|err| Error::DbQuery(err.to_string()))?;
+        .map_err(db_error)?;
 
     _ = response.ok_or_else(|| Error::ExecutionNotInserted(key))?;
     Ok(())
 }
 
+#[cfg_attr(feature = "tracing", tracing::instrument(
+    level = "debug",
+    skip_all,
+    fields(table = %migrations_table, namespace = %db.namespace(), database = %db.database()),
+    err,
+))]
+pub async fn select_failed_attempts(
+    migrations_table: &str,
+    db: &DbConnection,
+) -> Result<Vec<FailedAttempt>, Error> {
+    let attempt_data: Vec<FailedAttemptData> = db
+        .query(format!(
+            "SELECT * FROM {migrations_table} WHERE success = false ORDER BY key"
+        ))
+        .await
+        .map_err(db_error)?
+        .take(0)
+        .map_err(db_error)?;
+    attempt_data
+        .into_iter()
+        .map(FailedAttemptData::into_failed_attempt)
+        .collect()
+}
+
+/// Deletes all failed attempts from the migrations table and returns the keys
+/// of the migrations whose failed attempts have been deleted.
+#[cfg_attr(feature = "tracing", tracing::instrument(
+    level = "debug",
+    skip_all,
+    fields(table = %migrations_table, namespace = %db.namespace(), database = %db.database()),
+    err,
+))]
+pub async fn delete_failed_attempts(
+    migrations_table: &str,
+    db: &DbConnection,
+) -> Result<Vec<NaiveDateTime>, Error> {
+    let attempt_data: Vec<FailedAttemptData> = db
+        .query(format!(
+            "DELETE FROM {migrations_table} WHERE success = false RETURN BEFORE"
+        ))
+        .await
+        .map_err(db_error)?
+        .take(0)
+        .map_err(db_error)?;
+    let mut keys = attempt_data
+        .into_iter()
+        .map(|data| {
+            NaiveDateTime::parse_from_str(&data.key, MIGRATION_KEY_FORMAT_STR).map_err(key_error)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    keys.sort_unstable();
+    Ok(keys)
+}
+
+#[cfg_attr(feature = "tracing", tracing::instrument(
+    level = "debug",
+    skip_all,
+    fields(table = %migrations_table, namespace = %db.namespace(), database = %db.database()),
+    err,
+))]
 pub async fn delete_migration_execution(
     reversion: Reversion,
     migrations_table: &str,
@@ -267,35 +859,112 @@ pub async fn delete_migration_execution(
 ) -> Result<(), Error> {
     let key = reversion.key.format(MIGRATION_KEY_FORMAT_STR).to_string();
 
-    let response: Option<MigrationExecutionData> = db
-        .delete((migrations_table, key.clone()))
+    let response = db
+        .query(
+            "\
+BEGIN TRANSACTION;
+LET $deleted = DELETE type::thing($table, $key) WHERE success != false RETURN BEFORE;
+IF !array::is_empty($deleted) {
+    CREATE type::table($history_table) CONTENT {
+        key: $key,
+        title: $deleted[0].title,
+        kind: $kind,
+        action: $action,
+        checksum: $deleted[0].checksum,
+        performed_by: $performed_by,
+        performed_at: $performed_at,
+        execution_time: $execution_time,
+    };
+};
+COMMIT TRANSACTION;
+RETURN array::len($deleted);
+",
+        )
+        .bind(("table", migrations_table.to_string()))
+        .bind(("history_table", history_table_name(migrations_table)))
+        .bind(("key", key.clone()))
+        .bind(("kind", MigrationKind::Down))
+        .bind(("action", HistoryAction::Reverted))
+        .bind(("performed_by", reversion.reverted_by))
+        .bind(("performed_at", sql::Datetime::from(reversion.reverted_at)))
+        .bind((
+            "execution_time",
+            sql::Duration::from(reversion.execution_time),
+        ))
         .await
-        .map_err(|err| Error::DbQuery(err.to_string()))?;
-
-    _ = response.ok_or_else(|| Error::ExecutionNotDeleted(key))?;
+        .map_err(db_error)?;
+    let num_stmts = response.num_statements();
+    let mut response = check_transaction(response)?;
+    let deleted: Option<usize> = response.take(num_stmts - 1).map_err(db_error)?;
+    if deleted.unwrap_or(0) == 0 {
+        return Err(Error::ExecutionNotDeleted(key));
+    }
     Ok(())
 }
 
+/// Selects the history of all migrations applied to and reverted on the
+/// database in the order they have been performed.
+#[cfg_attr(feature = "tracing", tracing::instrument(
+    level = "debug",
+    skip_all,
+    fields(table = %migrations_table, namespace = %db.namespace(), database = %db.database()),
+    err,
+))]
+pub async fn select_migration_history(
+    migrations_table: &str,
+    db: &DbConnection,
+) -> Result<Vec<HistoryEvent>, Error> {
+    let history_table = history_table_name(migrations_table);
+    let event_data: Vec<HistoryEventData> = db
+        .query(format!(
+            "SELECT * FROM {history_table} ORDER BY performed_at"
+        ))
+        .await
+        .map_err(db_error)?
+        .take(0)
+        .map_err(db_error)?;
+    event_data
+        .into_iter()
+        .map(|data| {
+            NaiveDateTime::parse_from_str(&data.key, MIGRATION_KEY_FORMAT_STR)
+                .map_err(key_error)
+                .map(|key| HistoryEvent {
+                    key,
+                    title: data.title,
+                    kind: data.kind,
+                    action: data.action,
+                    checksum: data.checksum,
+                    performed_by: data.performed_by,
+                    performed_at: data.performed_at.0,
+                    execution_time: data.execution_time.0,
+                })
+        })
+        .collect()
+}
+
+#[cfg_attr(feature = "tracing", tracing::instrument(
+    level = "debug",
+    skip_all,
+    fields(table = %migrations_table, namespace = %db.namespace(), database = %db.database()),
+    err,
+))]
 pub async fn find_max_applied_migration_key(
     migrations_table: &str,
     db: &DbConnection,
 ) -> Result<Option<NaiveDateTime>, Error> {
     let mut response = db
         .query(format!(
-            "SELECT key AS max_key FROM (SELECT key FROM {migrations_table} ORDER BY key DESC) LIMIT 1"
+            "SELECT key AS max_key FROM (SELECT key FROM {migrations_table} WHERE success != false ORDER BY key DESC) LIMIT 1"
         ))
         .await
-        .map_err(|err| Error::DbQuery(err.to_string()))?;
+        .map_err(db_error)?;
 
-    let result: Option<HashMap<String, String>> = response
-        .take(0)
-        .map_err(|err| Error::DbQuery(err.to_string()))?;
+    let result: Option<HashMap<String, String>> = response.take(0).map_err(db_error)?;
 
     let max_applied_key = result
         .and_then(|fields| {
             fields.get("max_key").map(|value| {
-                NaiveDateTime::parse_from_str(value, MIGRATION_KEY_FORMAT_STR)
-                    .map_err(|err| Error::DbQuery(err.to_string()))
+                NaiveDateTime::parse_from_str(value, MIGRATION_KEY_FORMAT_STR).map_err(key_error)
             })
         })
         .transpose()?;
@@ -303,6 +972,17 @@ pub async fn find_max_applied_migration_key(
     Ok(max_applied_key)
 }
 
+#[cfg_attr(feature = "tracing", tracing::instrument(
+    skip_all,
+    fields(
+        key = %migration.key.format(MIGRATION_KEY_FORMAT_STR),
+        kind = %migration.kind,
+        checksum = %migration.checksum,
+        namespace = %db.namespace(),
+        database = %db.database(),
+    ),
+    err,
+))]
 pub async fn apply_migration_in_transaction(
     migration: &ApplicableMigration,
     username: &str,
@@ -318,21 +998,17 @@ pub async fn apply_migration_in_transaction(
 BEGIN TRANSACTION;
 {script_content}
 COMMIT TRANSACTION;
-RETURN SELECT math::max(applied_rank) AS max_rank FROM {migrations_table} GROUP ALL;
+RETURN SELECT math::max(applied_rank) AS max_rank FROM {migrations_table} WHERE success != false GROUP ALL;
 "
     );
 
-    let mut response = db
-        .query(query)
-        .await
-        .map_err(|err| Error::DbQuery(err.to_string()))?;
+    let mut response = db.query(query).await.map_err(db_error)?;
 
     let script_errors = response.take_errors();
     if script_errors.is_empty() {
         let num_stmts = response.num_statements();
-        let result: Option<HashMap<String, i64>> = response
-            .take(num_stmts - 1)
-            .map_err(|err| Error::DbQuery(err.to_string()))?;
+        let result: Option<HashMap<String, i64>> =
+            response.take(num_stmts - 1).map_err(db_error)?;
 
         let max_rank = result
             .and_then(|fields| fields.get("max_rank").copied())
@@ -350,14 +1026,25 @@ RETURN SELECT math::max(applied_rank) AS max_rank FROM {migrations_table} GROUP
             execution_time,
         })
     } else {
-        let errors = script_errors
-            .into_iter()
-            .map(|(index, err)| (index, err.to_string()))
-            .collect();
-        Err(Error::DbScript(errors))
+        Err(script_error(
+            &migration.script_path,
+            script_content,
+            script_errors,
+        ))
     }
 }
 
+#[cfg_attr(feature = "tracing", tracing::instrument(
+    skip_all,
+    fields(
+        key = %backward_migration.key.format(MIGRATION_KEY_FORMAT_STR),
+        kind = %backward_migration.kind,
+        checksum = %backward_migration.checksum,
+        namespace = %db.namespace(),
+        database = %db.database(),
+    ),
+    err,
+))]
 pub async fn revert_migration_in_transaction(
     backward_migration: &ApplicableMigration,
     username: &str,
@@ -375,10 +1062,7 @@ COMMIT TRANSACTION;
 "
     );
 
-    let mut response = db
-        .query(query)
-        .await
-        .map_err(|err| Error::DbQuery(err.to_string()))?;
+    let mut response = db.query(query).await.map_err(db_error)?;
 
     let script_errors = response.take_errors();
     if script_errors.is_empty() {
@@ -391,14 +1075,195 @@ COMMIT TRANSACTION;
             execution_time,
         })
     } else {
-        let errors = script_errors
-            .into_iter()
-            .map(|(index, err)| (index, err.to_string()))
-            .collect();
-        Err(Error::DbScript(errors))
+        Err(script_error(
+            &backward_migration.script_path,
+            script_content,
+            script_errors,
+        ))
     }
 }
 
+/// Executes the given callback script in a transaction.
+///
+/// Returns the output of each statement of the script formatted as
+/// `SurrealQL` value.
+#[cfg_attr(feature = "tracing", tracing::instrument(
+    skip_all,
+    fields(
+        callback = %callback_script.callback,
+        namespace = %db.namespace(),
+        database = %db.database(),
+    ),
+    err,
+))]
+pub async fn run_callback_script(
+    callback_script: &CallbackScript,
+    db: &DbConnection,
+) -> Result<Vec<String>, Error> {
+    let script_content = &callback_script.content;
+    let query = format!(
+        "\
+BEGIN TRANSACTION;
+{script_content}
+COMMIT TRANSACTION;
+"
+    );
+
+    let mut response = db.query(query).await.map_err(db_error)?;
+
+    let script_errors = response.take_errors();
+    if script_errors.is_empty() {
+        (0..response.num_statements())
+            .map(|index| {
+                response
+                    .take::<surrealdb::Value>(index)
+                    .map(|value| value.to_string())
+                    .map_err(db_error)
+            })
+            .collect()
+    } else {
+        Err(script_error(
+            &callback_script.script_path,
+            script_content,
+            script_errors,
+        ))
+    }
+}
+
+/// Returns the response of a query that runs a transaction or the error that
+/// caused the transaction to fail.
+///
+/// When a transaction fails, all of its statements report an error. The
+/// statements that did not cause the failure only report that they have not
+/// been executed.
+fn check_transaction(mut response: Response) -> Result<Response, Error> {
+    let mut errors = response.take_errors().into_iter().collect::<Vec<_>>();
+    if errors.is_empty() {
+        return Ok(response);
+    }
+    errors.sort_unstable_by_key(|(index, _)| *index);
+    let cause = errors
+        .iter()
+        .position(|(_, err)| !err.to_string().starts_with(QUERY_NOT_EXECUTED_MESSAGE))
+        .unwrap_or(0);
+    Err(db_error(errors.swap_remove(cause).1))
+}
+
+/// Converts an error returned by the database client into an `Error::Db`.
+///
+/// The category of the error is determined by [`categorize`], which matches
+/// on the variant of the error and falls back to its message for errors that
+/// only carry a message, like the errors reported by a remote database server.
+fn db_error(err: DbError) -> Error {
+    let category = categorize(&err);
+    db_error_of_category(category, err)
+}
+
+fn key_error(err: chrono::ParseError) -> Error {
+    db_error_of_category(ErrorCategory::Query, err)
+}
+
+fn db_error_of_category(
+    category: ErrorCategory,
+    err: impl std::error::Error + Send + Sync + 'static,
+) -> Error {
+    Error::Db(DatabaseError::new(category, err.to_string()).with_source(err))
+}
+
+/// Determines the category of an error by its variant. Errors of a remote
+/// database server are categorized by their message, as they are only
+/// received as text.
+fn categorize(err: &DbError) -> ErrorCategory {
+    use surrealdb::error::{Api, Db};
+
+    match err {
+        DbError::Api(Api::Ws(_) | Api::Http(_) | Api::ConnectionUninitialised) => {
+            ErrorCategory::Connection
+        },
+        DbError::Db(Db::InvalidAuth | Db::ExpiredToken | Db::ExpiredSession) => {
+            ErrorCategory::Authentication
+        },
+        DbError::Db(
+            Db::IamError(_)
+            | Db::NsNotAllowed { .. }
+            | Db::DbNotAllowed { .. }
+            | Db::ScriptingNotAllowed
+            | Db::FunctionNotAllowed(_)
+            | Db::NetTargetNotAllowed(_),
+        ) => ErrorCategory::Permission,
+        DbError::Db(
+            Db::RecordExists { .. }
+            | Db::IndexExists { .. }
+            | Db::FieldCheck { .. }
+            | Db::FieldValue { .. }
+            | Db::CoerceTo { .. },
+        ) => ErrorCategory::Constraint,
+        _ => categorize_message(&err.to_string()),
+    }
+}
+
+fn categorize_message(message: &str) -> ErrorCategory {
+    const CONNECTION_PATTERNS: [&str; 7] = [
+        "connection",
+        "websocket",
+        "remote ws request",
+        "http request",
+        "timed out",
+        "refused",
+        "uninitialised",
+    ];
+    const AUTHENTICATION_PATTERNS: [&str; 4] = [
+        "problem with authentication",
+        "session has expired",
+        "token has expired",
+        "invalid credentials",
+    ];
+    const PERMISSION_PATTERNS: [&str; 3] = ["not allowed", "permission", "iam error"];
+    const CONSTRAINT_PATTERNS: [&str; 4] = [
+        "already exists",
+        "already contains",
+        "failed check",
+        "couldn't coerce",
+    ];
+
+    let message = message.to_lowercase();
+    let matches_any = |patterns: &[&str]| patterns.iter().any(|pattern| message.contains(pattern));
+    if matches_any(&AUTHENTICATION_PATTERNS) {
+        ErrorCategory::Authentication
+    } else if matches_any(&PERMISSION_PATTERNS) {
+        ErrorCategory::Permission
+    } else if matches_any(&CONSTRAINT_PATTERNS) {
+        ErrorCategory::Constraint
+    } else if matches_any(&CONNECTION_PATTERNS) {
+        ErrorCategory::Connection
+    } else {
+        ErrorCategory::Query
+    }
+}
+
+/// Converts the errors of a failed script execution into an `Error::DbScript`.
+///
+/// When one statement of a transaction fails, the database reports all other
+/// statements of the transaction as not executed. Those errors are dropped in
+/// favor of the errors that caused the transaction to fail.
+fn script_error(
+    script_path: &Path,
+    script_content: &str,
+    script_errors: HashMap<usize, surrealdb::Error>,
+) -> Error {
+    let errors = script_errors
+        .into_iter()
+        .map(|(index, err)| (index, err.to_string()))
+        .collect::<Vec<_>>();
+    let has_causing_errors = errors
+        .iter()
+        .any(|(_, message)| !message.starts_with(QUERY_NOT_EXECUTED_MESSAGE));
+    let errors = errors.into_iter().filter(|(_, message)| {
+        !has_causing_errors || !message.starts_with(QUERY_NOT_EXECUTED_MESSAGE)
+    });
+    Error::DbScript(ScriptError::new(script_path, script_content, errors))
+}
+
 #[cfg(test)]
 mod tests;
 
@@ -0,0 +1,436 @@
+use crate::{DbConnection, db_error, history_table_name, script_error};
+use database_migration::error::Error;
+use database_migration::scope::MigrationScope;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// The schema of a database as reported by `INFO FOR DB` and
+/// `INFO FOR TABLE`.
+///
+/// All definitions are kept as the `DEFINE` statements returned by the
+/// database, ordered by their names.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DatabaseSchema {
+    pub analyzers: BTreeMap<String, String>,
+    pub params: BTreeMap<String, String>,
+    pub functions: BTreeMap<String, String>,
+    pub accesses: BTreeMap<String, String>,
+    pub tables: BTreeMap<String, TableSchema>,
+}
+
+/// The definition of a table together with the definitions of its fields,
+/// indexes and events.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TableSchema {
+    pub definition: String,
+    pub fields: BTreeMap<String, String>,
+    pub indexes: BTreeMap<String, String>,
+    pub events: BTreeMap<String, String>,
+}
+
+impl TableSchema {
+    /// Whether this table is a pre-computed view (`DEFINE TABLE ... AS SELECT`).
+    pub fn is_view(&self) -> bool {
+        self.definition.contains(" AS SELECT ")
+    }
+}
+
+impl DatabaseSchema {
+    /// Returns the names of all definitions in this schema.
+    pub fn names(&self) -> Vec<&str> {
+        self.analyzers
+            .keys()
+            .chain(self.params.keys())
+            .chain(self.functions.keys())
+            .chain(self.accesses.keys())
+            .chain(self.tables.keys())
+            .map(String::as_str)
+            .collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.analyzers.is_empty()
+            && self.params.is_empty()
+            && self.functions.is_empty()
+            && self.accesses.is_empty()
+            && self.tables.is_empty()
+    }
+
+    /// Returns a script that defines this schema in an empty database.
+    ///
+    /// Tables are defined after analyzers, params, functions and accesses.
+    /// Views are defined after all other tables, as they select from other
+    /// tables.
+    pub fn to_script(&self) -> String {
+        let mut script = String::new();
+        for definition in self
+            .analyzers
+            .values()
+            .chain(self.params.values())
+            .chain(self.functions.values())
+            .chain(self.accesses.values())
+        {
+            push_statement(&mut script, definition);
+        }
+        let (views, tables): (Vec<_>, Vec<_>) =
+            self.tables.values().partition(|table| table.is_view());
+        for table in tables.into_iter().chain(views) {
+            if !script.is_empty() {
+                script.push('\n');
+            }
+            push_statement(&mut script, &table.definition);
+            for definition in table
+                .fields
+                .values()
+                .chain(table.indexes.values())
+                .chain(table.events.values())
+            {
+                push_statement(&mut script, definition);
+            }
+        }
+        script
+    }
+}
+
+/// The differences between the schemas of two databases.
+///
+/// The `up` statements change the schema of the target database into the
+/// schema of the source database. The `down` statements revert these changes.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SchemaDiff {
+    pub up: Vec<String>,
+    pub down: Vec<String>,
+}
+
+impl SchemaDiff {
+    pub fn between(source: &DatabaseSchema, target: &DatabaseSchema) -> Self {
+        Self {
+            up: migration_statements(target, source),
+            down: migration_statements(source, target),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.up.is_empty() && self.down.is_empty()
+    }
+
+    pub fn up_script(&self) -> String {
+        to_script(&self.up)
+    }
+
+    pub fn down_script(&self) -> String {
+        to_script(&self.down)
+    }
+}
+
+fn to_script(statements: &[String]) -> String {
+    let mut script = String::new();
+    for statement in statements {
+        push_statement(&mut script, statement);
+    }
+    script
+}
+
+/// Collects the statements that change the `current` schema into the
+/// `desired` one.
+///
+/// New and changed definitions come first. Removals follow in reverse order,
+/// so that fields, indexes, events and tables are removed before the
+/// accesses, functions, params and analyzers they may depend on.
+fn migration_statements(current: &DatabaseSchema, desired: &DatabaseSchema) -> Vec<String> {
+    let mut statements = Statements::default();
+    statements.diff(&current.analyzers, &desired.analyzers, |name| {
+        format!("REMOVE ANALYZER {}", escape_identifier(name))
+    });
+    statements.diff(&current.params, &desired.params, |name| {
+        format!("REMOVE PARAM ${name}")
+    });
+    statements.diff(&current.functions, &desired.functions, |name| {
+        format!("REMOVE FUNCTION fn::{}", escape_path(name, "::"))
+    });
+    statements.diff(&current.accesses, &desired.accesses, |name| {
+        format!("REMOVE ACCESS {} ON DATABASE", escape_identifier(name))
+    });
+
+    let (views, tables): (Vec<_>, Vec<_>) = desired
+        .tables
+        .iter()
+        .partition(|(_, table)| table.is_view());
+    for (name, table) in tables.into_iter().chain(views) {
+        let table_name = escape_identifier(name);
+        if let Some(current_table) = current.tables.get(name) {
+            if current_table.definition != table.definition {
+                statements.defines.push(overwrite(&table.definition));
+            }
+            statements.diff(&current_table.fields, &table.fields, |field| {
+                format!("REMOVE FIELD {} ON TABLE {table_name}", escape_field(field))
+            });
+            statements.diff(&current_table.indexes, &table.indexes, |index| {
+                format!(
+                    "REMOVE INDEX {} ON TABLE {table_name}",
+                    escape_identifier(index)
+                )
+            });
+            statements.diff(&current_table.events, &table.events, |event| {
+                format!(
+                    "REMOVE EVENT {} ON TABLE {table_name}",
+                    escape_identifier(event)
+                )
+            });
+        } else {
+            statements.defines.push(table.definition.clone());
+            statements.defines.extend(
+                table
+                    .fields
+                    .values()
+                    .chain(table.indexes.values())
+                    .chain(table.events.values())
+                    .cloned(),
+            );
+        }
+    }
+    for name in current.tables.keys() {
+        if !desired.tables.contains_key(name) {
+            statements
+                .removes
+                .push(format!("REMOVE TABLE {}", escape_identifier(name)));
+        }
+    }
+
+    statements.into_vec()
+}
+
+#[derive(Default)]
+struct Statements {
+    defines: Vec<String>,
+    removes: Vec<String>,
+}
+
+impl Statements {
+    fn diff(
+        &mut self,
+        current: &BTreeMap<String, String>,
+        desired: &BTreeMap<String, String>,
+        remove: impl Fn(&str) -> String,
+    ) {
+        for (name, definition) in desired {
+            match current.get(name) {
+                None => self.defines.push(definition.clone()),
+                Some(existing) if existing != definition => {
+                    self.defines.push(overwrite(definition));
+                },
+                Some(_) => {},
+            }
+        }
+        for name in current.keys() {
+            if !desired.contains_key(name) {
+                self.removes.push(remove(name));
+            }
+        }
+    }
+
+    fn into_vec(self) -> Vec<String> {
+        let mut statements = self.defines;
+        statements.extend(self.removes.into_iter().rev());
+        statements
+    }
+}
+
+/// Turns a `DEFINE` statement into a `DEFINE ... OVERWRITE` statement.
+fn overwrite(definition: &str) -> String {
+    let mut parts = definition.splitn(3, ' ');
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(define), Some(kind), Some(rest)) if !rest.starts_with("OVERWRITE ") => {
+            format!("{define} {kind} OVERWRITE {rest}")
+        },
+        _ => definition.to_string(),
+    }
+}
+
+fn push_statement(script: &mut String, definition: &str) {
+    script.push_str(definition.trim_end_matches(';'));
+    script.push_str(";\n");
+}
+
+#[derive(Deserialize, Default)]
+struct DbInfo {
+    #[serde(default)]
+    analyzers: BTreeMap<String, String>,
+    #[serde(default)]
+    params: BTreeMap<String, String>,
+    #[serde(default)]
+    functions: BTreeMap<String, String>,
+    #[serde(default)]
+    accesses: BTreeMap<String, String>,
+    #[serde(default)]
+    tables: BTreeMap<String, String>,
+}
+
+#[derive(Deserialize, Default)]
+struct TableInfo {
+    #[serde(default)]
+    fields: BTreeMap<String, String>,
+    #[serde(default)]
+    indexes: BTreeMap<String, String>,
+    #[serde(default)]
+    events: BTreeMap<String, String>,
+}
+
+/// Whether the table with the given name tracks the migrations of any scope.
+fn is_tracking_table(name: &str, migrations_table: &str) -> bool {
+    MigrationScope::all().iter().any(|scope| {
+        let tracking_table = scope.migrations_table(migrations_table);
+        name == tracking_table || name == history_table_name(&tracking_table)
+    })
+}
+
+/// Fetches the schema of the database the given connection is using.
+///
+/// The migrations tables of all scopes and their history tables are not part
+/// of the returned schema.
+#[cfg_attr(feature = "tracing", tracing::instrument(
+    level = "debug",
+    skip_all,
+    fields(namespace = %db.namespace(), database = %db.database()),
+    err,
+))]
+pub async fn fetch_database_schema(
+    migrations_table: &str,
+    db: &DbConnection,
+) -> Result<DatabaseSchema, Error> {
+    let db_info: Option<DbInfo> = db
+        .query("INFO FOR DB")
+        .await
+        .map_err(db_error)?
+        .take(0)
+        .map_err(|err| Error::FetchingTableDefinitions(err.to_string()))?;
+    let db_info = db_info.unwrap_or_default();
+
+    let mut tables = BTreeMap::new();
+    for (name, definition) in db_info.tables {
+        if is_tracking_table(&name, migrations_table) {
+            continue;
+        }
+        let table_info: Option<TableInfo> = db
+            .query(format!("INFO FOR TABLE {}", escape_identifier(&name)))
+            .await
+            .map_err(db_error)?
+            .take(0)
+            .map_err(|err| Error::FetchingTableDefinitions(err.to_string()))?;
+        let table_info = table_info.unwrap_or_default();
+        tables.insert(
+            name,
+            TableSchema {
+                definition,
+                fields: table_info.fields,
+                indexes: table_info.indexes,
+                events: table_info.events,
+            },
+        );
+    }
+
+    Ok(DatabaseSchema {
+        analyzers: db_info.analyzers,
+        params: db_info.params,
+        functions: db_info.functions,
+        accesses: db_info.accesses,
+        tables,
+    })
+}
+
+/// Evaluates the given schema definitions and returns the resulting schema.
+///
+/// The definitions are applied to the given scratch database within a
+/// transaction. After the resulting schema has been fetched, all definitions
+/// are removed from the scratch database again. The scratch database must be
+/// empty.
+#[cfg_attr(feature = "tracing", tracing::instrument(
+    level = "debug",
+    skip_all,
+    fields(namespace = %scratch.namespace(), database = %scratch.database()),
+    err,
+))]
+pub async fn evaluate_schema_definitions(
+    definitions: &str,
+    migrations_table: &str,
+    scratch: &DbConnection,
+) -> Result<DatabaseSchema, Error> {
+    let existing_schema = fetch_database_schema(migrations_table, scratch).await?;
+    if !existing_schema.is_empty() {
+        return Err(Error::ScratchDatabaseNotEmpty(
+            existing_schema.names().join(", "),
+        ));
+    }
+
+    define_in_transaction(definitions, Path::new("<desired schema>"), scratch).await?;
+    let schema = fetch_database_schema(migrations_table, scratch).await?;
+    let removals = SchemaDiff::between(&DatabaseSchema::default(), &schema);
+    define_in_transaction(
+        &removals.up_script(),
+        Path::new("<schema removal>"),
+        scratch,
+    )
+    .await?;
+    Ok(schema)
+}
+
+async fn define_in_transaction(
+    script: &str,
+    script_path: &Path,
+    db: &DbConnection,
+) -> Result<(), Error> {
+    if script.trim().is_empty() {
+        return Ok(());
+    }
+    let query = format!(
+        "\
+BEGIN TRANSACTION;
+{script}
+COMMIT TRANSACTION;
+"
+    );
+    let mut response = db.query(query).await.map_err(db_error)?;
+    let script_errors = response.take_errors();
+    if script_errors.is_empty() {
+        Ok(())
+    } else {
+        Err(script_error(script_path, script, script_errors))
+    }
+}
+
+pub(crate) fn escape_identifier(name: &str) -> String {
+    let is_plain = !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if is_plain {
+        name.to_string()
+    } else {
+        format!("`{}`", name.replace('`', "\\`"))
+    }
+}
+
+/// Escapes each part of a path like the name `my::greet` of a function.
+fn escape_path(path: &str, separator: &str) -> String {
+    path.split(separator)
+        .map(escape_identifier)
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+/// Escapes each part of the name of a field, like `address.city` or
+/// `tags[*]`, but keeps the array parts `[*]` and `[$]` as they are.
+fn escape_field(field: &str) -> String {
+    field
+        .split('.')
+        .map(|part| {
+            let name_end = part.find('[').unwrap_or(part.len());
+            let (name, array_parts) = part.split_at(name_end);
+            format!("{}{array_parts}", escape_identifier(name))
+        })
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,354 @@
+use super::*;
+use asserting::prelude::*;
+
+fn definitions<const N: usize>(values: [(&str, &str); N]) -> BTreeMap<String, String> {
+    values
+        .into_iter()
+        .map(|(name, definition)| (name.to_string(), definition.to_string()))
+        .collect()
+}
+
+mod database_schema {
+    use super::*;
+
+    #[test]
+    fn script_of_empty_schema_is_empty() {
+        let schema = DatabaseSchema::default();
+
+        assert_that!(schema.is_empty()).is_true();
+        assert_that!(schema.to_script()).is_empty();
+    }
+
+    #[test]
+    fn names_of_all_definitions() {
+        let schema = DatabaseSchema {
+            analyzers: definitions([("ascii", "DEFINE ANALYZER ascii TOKENIZERS class")]),
+            functions: definitions([("greet", "DEFINE FUNCTION fn::greet() { RETURN 'hi'; }")]),
+            tables: BTreeMap::from_iter([(
+                "quote".to_string(),
+                TableSchema {
+                    definition: "DEFINE TABLE quote TYPE NORMAL SCHEMALESS PERMISSIONS NONE".into(),
+                    ..TableSchema::default()
+                },
+            )]),
+            ..DatabaseSchema::default()
+        };
+
+        assert_that!(schema.names()).contains_exactly(["ascii", "greet", "quote"]);
+    }
+
+    #[test]
+    fn script_defines_tables_with_fields_indexes_and_events() {
+        let schema = DatabaseSchema {
+            tables: BTreeMap::from_iter([(
+                "quote".to_string(),
+                TableSchema {
+                    definition: "DEFINE TABLE quote TYPE NORMAL SCHEMAFULL PERMISSIONS NONE".into(),
+                    fields: definitions([
+                        (
+                            "text",
+                            "DEFINE FIELD text ON quote TYPE string PERMISSIONS FULL",
+                        ),
+                        (
+                            "author",
+                            "DEFINE FIELD author ON quote TYPE string PERMISSIONS FULL",
+                        ),
+                    ]),
+                    indexes: definitions([(
+                        "quote_author",
+                        "DEFINE INDEX quote_author ON quote FIELDS author",
+                    )]),
+                    events: definitions([(
+                        "quote_created",
+                        "DEFINE EVENT quote_created ON quote WHEN $event = 'CREATE' THEN {}",
+                    )]),
+                },
+            )]),
+            ..Default::default()
+        };
+
+        let script = schema.to_script();
+
+        assert_that!(script).is_equal_to(
+            "\
+DEFINE TABLE quote TYPE NORMAL SCHEMAFULL PERMISSIONS NONE;
+DEFINE FIELD author ON quote TYPE string PERMISSIONS FULL;
+DEFINE FIELD text ON quote TYPE string PERMISSIONS FULL;
+DEFINE INDEX quote_author ON quote FIELDS author;
+DEFINE EVENT quote_created ON quote WHEN $event = 'CREATE' THEN {};
+"
+            .to_string(),
+        );
+    }
+
+    #[test]
+    fn script_defines_analyzers_params_functions_and_accesses_before_tables_and_views_last() {
+        let schema = DatabaseSchema {
+            analyzers: definitions([(
+                "simple",
+                "DEFINE ANALYZER simple TOKENIZERS BLANK FILTERS LOWERCASE",
+            )]),
+            params: definitions([("max_quotes", "DEFINE PARAM $max_quotes VALUE 100")]),
+            functions: definitions([(
+                "greet",
+                "DEFINE FUNCTION fn::greet($name: string) { RETURN 'Hello ' + $name; }",
+            )]),
+            accesses: definitions([(
+                "account",
+                "DEFINE ACCESS account ON DATABASE TYPE RECORD DURATION FOR TOKEN 1h",
+            )]),
+            tables: BTreeMap::from_iter([
+                (
+                    "author_count".to_string(),
+                    TableSchema {
+                        definition: "DEFINE TABLE author_count TYPE NORMAL SCHEMALESS AS SELECT count() FROM quote GROUP BY author PERMISSIONS NONE".into(),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    "quote".to_string(),
+                    TableSchema {
+                        definition: "DEFINE TABLE quote TYPE NORMAL SCHEMALESS PERMISSIONS NONE"
+                            .into(),
+                        ..Default::default()
+                    },
+                ),
+            ]),
+        };
+
+        let script = schema.to_script();
+
+        assert_that!(script).is_equal_to(
+            "\
+DEFINE ANALYZER simple TOKENIZERS BLANK FILTERS LOWERCASE;
+DEFINE PARAM $max_quotes VALUE 100;
+DEFINE FUNCTION fn::greet($name: string) { RETURN 'Hello ' + $name; };
+DEFINE ACCESS account ON DATABASE TYPE RECORD DURATION FOR TOKEN 1h;
+
+DEFINE TABLE quote TYPE NORMAL SCHEMALESS PERMISSIONS NONE;
+
+DEFINE TABLE author_count TYPE NORMAL SCHEMALESS AS SELECT count() FROM quote GROUP BY author PERMISSIONS NONE;
+"
+            .to_string(),
+        );
+    }
+}
+
+mod escape_identifier {
+    use super::*;
+
+    #[test]
+    fn plain_identifier_is_not_escaped() {
+        assert_that!(escape_identifier("some_table_1")).is_equal_to("some_table_1".to_string());
+    }
+
+    #[test]
+    fn identifier_with_special_characters_is_escaped() {
+        assert_that!(escape_identifier("some-table")).is_equal_to("`some-table`".to_string());
+    }
+
+    #[test]
+    fn identifier_starting_with_digit_is_escaped() {
+        assert_that!(escape_identifier("1table")).is_equal_to("`1table`".to_string());
+    }
+
+    #[test]
+    fn each_part_of_a_function_name_is_escaped() {
+        assert_that!(escape_path("my::greet-all", "::")).is_equal_to("my::`greet-all`".to_string());
+    }
+
+    #[test]
+    fn each_part_of_a_nested_field_is_escaped() {
+        assert_that!(escape_field("address.zip-code"))
+            .is_equal_to("address.`zip-code`".to_string());
+    }
+
+    #[test]
+    fn array_parts_of_a_field_are_not_escaped() {
+        assert_that!(escape_field("tags[*].first-name"))
+            .is_equal_to("tags[*].`first-name`".to_string());
+    }
+}
+
+mod is_tracking_table {
+    use super::*;
+
+    #[test]
+    fn migrations_tables_of_all_scopes_are_tracking_tables() {
+        assert_that!(is_tracking_table("migrations", "migrations")).is_true();
+        assert_that!(is_tracking_table("migrations_namespace", "migrations")).is_true();
+        assert_that!(is_tracking_table("migrations_root", "migrations")).is_true();
+    }
+
+    #[test]
+    fn history_tables_of_all_scopes_are_tracking_tables() {
+        assert_that!(is_tracking_table("migrations_history", "migrations")).is_true();
+        assert_that!(is_tracking_table(
+            "migrations_namespace_history",
+            "migrations"
+        ))
+        .is_true();
+        assert_that!(is_tracking_table("migrations_root_history", "migrations")).is_true();
+    }
+
+    #[test]
+    fn other_tables_are_not_tracking_tables() {
+        assert_that!(is_tracking_table("quote", "migrations")).is_false();
+        assert_that!(is_tracking_table("migrations_archive", "migrations")).is_false();
+        assert_that!(is_tracking_table("migrations", "schema_migrations")).is_false();
+    }
+}
+
+mod schema_diff {
+    use super::*;
+
+    fn quote_table(fields: BTreeMap<String, String>) -> TableSchema {
+        TableSchema {
+            definition: "DEFINE TABLE quote TYPE NORMAL SCHEMAFULL PERMISSIONS NONE".into(),
+            fields,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn diff_between_equal_schemas_is_empty() {
+        let schema = DatabaseSchema {
+            tables: BTreeMap::from_iter([(
+                "quote".to_string(),
+                quote_table(definitions([(
+                    "text",
+                    "DEFINE FIELD text ON quote TYPE string PERMISSIONS FULL",
+                )])),
+            )]),
+            ..Default::default()
+        };
+
+        let diff = SchemaDiff::between(&schema, &schema.clone());
+
+        assert_that!(diff.is_empty()).is_true();
+        assert_that!(diff.up_script()).is_empty();
+        assert_that!(diff.down_script()).is_empty();
+    }
+
+    #[test]
+    fn diff_with_new_table_in_source_defines_table_up_and_removes_it_down() {
+        let source = DatabaseSchema {
+            tables: BTreeMap::from_iter([(
+                "quote".to_string(),
+                quote_table(definitions([(
+                    "text",
+                    "DEFINE FIELD text ON quote TYPE string PERMISSIONS FULL",
+                )])),
+            )]),
+            ..Default::default()
+        };
+        let target = DatabaseSchema::default();
+
+        let diff = SchemaDiff::between(&source, &target);
+
+        assert_that!(diff.up_script()).is_equal_to(
+            "\
+DEFINE TABLE quote TYPE NORMAL SCHEMAFULL PERMISSIONS NONE;
+DEFINE FIELD text ON quote TYPE string PERMISSIONS FULL;
+"
+            .to_string(),
+        );
+        assert_that!(diff.down_script()).is_equal_to("REMOVE TABLE quote;\n".to_string());
+    }
+
+    #[test]
+    fn diff_with_changed_and_removed_fields_overwrites_and_removes_them() {
+        let source = DatabaseSchema {
+            tables: BTreeMap::from_iter([(
+                "quote".to_string(),
+                quote_table(definitions([(
+                    "text",
+                    "DEFINE FIELD text ON quote TYPE string ASSERT $value != NONE PERMISSIONS FULL",
+                )])),
+            )]),
+            ..Default::default()
+        };
+        let target = DatabaseSchema {
+            tables: BTreeMap::from_iter([(
+                "quote".to_string(),
+                quote_table(definitions([
+                    (
+                        "text",
+                        "DEFINE FIELD text ON quote TYPE string PERMISSIONS FULL",
+                    ),
+                    (
+                        "author",
+                        "DEFINE FIELD author ON quote TYPE string PERMISSIONS FULL",
+                    ),
+                ])),
+            )]),
+            ..Default::default()
+        };
+
+        let diff = SchemaDiff::between(&source, &target);
+
+        assert_that!(diff.up).contains_exactly([
+            "DEFINE FIELD OVERWRITE text ON quote TYPE string ASSERT $value != NONE PERMISSIONS FULL"
+                .to_string(),
+            "REMOVE FIELD author ON TABLE quote".to_string(),
+        ]);
+        assert_that!(diff.down).contains_exactly([
+            "DEFINE FIELD author ON quote TYPE string PERMISSIONS FULL".to_string(),
+            "DEFINE FIELD OVERWRITE text ON quote TYPE string PERMISSIONS FULL".to_string(),
+        ]);
+    }
+
+    #[test]
+    fn diff_removes_tables_before_functions_params_and_analyzers() {
+        let source = DatabaseSchema::default();
+        let target = DatabaseSchema {
+            analyzers: definitions([(
+                "simple",
+                "DEFINE ANALYZER simple TOKENIZERS BLANK FILTERS LOWERCASE",
+            )]),
+            params: definitions([("max_quotes", "DEFINE PARAM $max_quotes VALUE 100")]),
+            functions: definitions([(
+                "greet",
+                "DEFINE FUNCTION fn::greet($name: string) { RETURN 'Hello ' + $name; }",
+            )]),
+            accesses: definitions([(
+                "account",
+                "DEFINE ACCESS account ON DATABASE TYPE RECORD DURATION FOR TOKEN 1h",
+            )]),
+            tables: BTreeMap::from_iter([("quote".to_string(), quote_table(BTreeMap::new()))]),
+        };
+
+        let diff = SchemaDiff::between(&source, &target);
+
+        assert_that!(diff.up).contains_exactly([
+            "REMOVE TABLE quote".to_string(),
+            "REMOVE ACCESS account ON DATABASE".to_string(),
+            "REMOVE FUNCTION fn::greet".to_string(),
+            "REMOVE PARAM $max_quotes".to_string(),
+            "REMOVE ANALYZER simple".to_string(),
+        ]);
+        assert_that!(diff.down).contains_exactly([
+            "DEFINE ANALYZER simple TOKENIZERS BLANK FILTERS LOWERCASE".to_string(),
+            "DEFINE PARAM $max_quotes VALUE 100".to_string(),
+            "DEFINE FUNCTION fn::greet($name: string) { RETURN 'Hello ' + $name; }".to_string(),
+            "DEFINE ACCESS account ON DATABASE TYPE RECORD DURATION FOR TOKEN 1h".to_string(),
+            "DEFINE TABLE quote TYPE NORMAL SCHEMAFULL PERMISSIONS NONE".to_string(),
+        ]);
+    }
+}
+
+mod overwrite {
+    use super::*;
+
+    #[test]
+    fn inserts_overwrite_after_the_kind_of_definition() {
+        assert_that!(overwrite("DEFINE INDEX quote_text ON quote FIELDS text"))
+            .is_equal_to("DEFINE INDEX OVERWRITE quote_text ON quote FIELDS text".to_string());
+    }
+
+    #[test]
+    fn does_not_insert_overwrite_twice() {
+        assert_that!(overwrite("DEFINE PARAM OVERWRITE $max VALUE 10"))
+            .is_equal_to("DEFINE PARAM OVERWRITE $max VALUE 10".to_string());
+    }
+}
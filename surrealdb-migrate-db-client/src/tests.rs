@@ -50,3 +50,99 @@ mod extract_table_definition_version {
         assert_that!(version).is_none();
     }
 }
+
+mod categorize {
+    use super::*;
+    use surrealdb::error::{Api, Db};
+
+    #[test]
+    fn uninitialised_connection_is_a_connection_error() {
+        let category = categorize(&DbError::Api(Api::ConnectionUninitialised));
+
+        assert_that!(category).is_equal_to(ErrorCategory::Connection);
+    }
+
+    #[test]
+    fn invalid_auth_is_an_authentication_error() {
+        let category = categorize(&DbError::Db(Db::InvalidAuth));
+
+        assert_that!(category).is_equal_to(ErrorCategory::Authentication);
+    }
+
+    #[test]
+    fn scripting_not_allowed_is_a_permission_error() {
+        let category = categorize(&DbError::Db(Db::ScriptingNotAllowed));
+
+        assert_that!(category).is_equal_to(ErrorCategory::Permission);
+    }
+
+    #[test]
+    fn query_error_of_a_remote_server_is_categorized_by_its_message() {
+        let category = categorize(&DbError::Api(Api::Query(
+            "There was a problem with authentication".into(),
+        )));
+
+        assert_that!(category).is_equal_to(ErrorCategory::Authentication);
+    }
+}
+
+mod categorize_message {
+    use super::*;
+
+    #[test]
+    fn record_already_exists_is_a_constraint_violation() {
+        let category = categorize_message(
+            "There was a problem with the database: Database record `migrations:⟨20250103_153309⟩` already exists",
+        );
+
+        assert_that!(category).is_equal_to(ErrorCategory::Constraint);
+    }
+
+    #[test]
+    fn table_already_exists_is_a_constraint_violation() {
+        let category = categorize_message("The table 'migrations' already exists");
+
+        assert_that!(category).is_equal_to(ErrorCategory::Constraint);
+    }
+
+    #[test]
+    fn failed_authentication() {
+        let category = categorize_message(
+            "There was a problem with the database: There was a problem with authentication",
+        );
+
+        assert_that!(category).is_equal_to(ErrorCategory::Authentication);
+    }
+
+    #[test]
+    fn expired_session() {
+        let category = categorize_message("The session has expired");
+
+        assert_that!(category).is_equal_to(ErrorCategory::Authentication);
+    }
+
+    #[test]
+    fn operation_not_allowed() {
+        let category =
+            categorize_message("IAM error: Not enough permissions to perform this action");
+
+        assert_that!(category).is_equal_to(ErrorCategory::Permission);
+    }
+
+    #[test]
+    fn connection_refused() {
+        let category = categorize_message(
+            "There was an error processing a remote WS request: IO error: Connection refused (os error 111)",
+        );
+
+        assert_that!(category).is_equal_to(ErrorCategory::Connection);
+    }
+
+    #[test]
+    fn parse_error_is_a_query_error() {
+        let category =
+            categorize_message("Parse error: Unexpected token `an identifier`, expected Eof");
+
+        assert_that!(category).is_equal_to(ErrorCategory::Query);
+    }
+}
@@ -0,0 +1,328 @@
+//! Custom TLS configuration for connections to the database via `wss` or
+//! `https`.
+//!
+//! The TLS settings are applied with `rustls` if the feature `rustls` is
+//! enabled, otherwise with `native-tls`.
+
+use database_migration::config::DbClientConfig;
+use database_migration::error::Error;
+#[cfg(any(feature = "rustls", feature = "native-tls"))]
+use std::fmt::Display;
+#[cfg(any(feature = "rustls", feature = "native-tls"))]
+use std::path::Path;
+use surrealdb::opt::Config;
+
+/// Applies the TLS settings of the client config to the options of the
+/// database engine, if any of them is set.
+pub fn with_tls_settings(
+    engine_config: Config,
+    config: &DbClientConfig<'_>,
+) -> Result<Config, Error> {
+    if !has_tls_settings(config) {
+        return Ok(engine_config);
+    }
+    if config.tls_client_cert_file.is_some() != config.tls_client_key_file.is_some() {
+        return Err(Error::Configuration(
+            "tls-client-cert-file and tls-client-key-file must be set both".into(),
+        ));
+    }
+    if config.tls_accept_invalid_certs {
+        log::warn!(
+            "accepting any certificate of the database server at {}, which must only be used for development",
+            config.address
+        );
+    }
+    apply_tls_settings(engine_config, config)
+}
+
+const fn has_tls_settings(config: &DbClientConfig<'_>) -> bool {
+    config.tls_ca_file.is_some()
+        || config.tls_client_cert_file.is_some()
+        || config.tls_client_key_file.is_some()
+        || config.tls_server_name.is_some()
+        || config.tls_accept_invalid_certs
+}
+
+#[cfg(feature = "rustls")]
+fn apply_tls_settings(engine_config: Config, config: &DbClientConfig<'_>) -> Result<Config, Error> {
+    rustls_config::client_config(config).map(|tls_config| engine_config.rustls(tls_config))
+}
+
+#[cfg(all(feature = "native-tls", not(feature = "rustls")))]
+fn apply_tls_settings(engine_config: Config, config: &DbClientConfig<'_>) -> Result<Config, Error> {
+    native_tls_config::connector(config).map(|connector| engine_config.native_tls(connector))
+}
+
+#[cfg(not(any(feature = "rustls", feature = "native-tls")))]
+#[allow(clippy::needless_pass_by_value)]
+fn apply_tls_settings(
+    _engine_config: Config,
+    _config: &DbClientConfig<'_>,
+) -> Result<Config, Error> {
+    Err(Error::Configuration(
+        "TLS settings are given, but neither the feature `rustls` nor `native-tls` is enabled"
+            .into(),
+    ))
+}
+
+#[cfg(any(feature = "rustls", feature = "native-tls"))]
+fn invalid_tls_config(err: &dyn Display) -> Error {
+    Error::Configuration(format!("invalid TLS configuration: {err}"))
+}
+
+#[cfg(any(feature = "rustls", feature = "native-tls"))]
+fn no_certificates_found(path: &Path) -> Error {
+    Error::Configuration(format!("no certificates found in {}", path.display()))
+}
+
+#[cfg(feature = "rustls")]
+mod rustls_config {
+    use super::{invalid_tls_config, no_certificates_found};
+    use database_migration::config::DbClientConfig;
+    use database_migration::error::Error;
+    use rustls::client::WebPkiServerVerifier;
+    use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+    use rustls::crypto::{
+        CryptoProvider, WebPkiSupportedAlgorithms, verify_tls12_signature, verify_tls13_signature,
+    };
+    use rustls::pki_types::pem::PemObject;
+    use rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName, UnixTime};
+    use rustls::{ClientConfig, DigitallySignedStruct, RootCertStore, SignatureScheme};
+    use std::path::Path;
+    use std::sync::Arc;
+
+    pub fn client_config(config: &DbClientConfig<'_>) -> Result<ClientConfig, Error> {
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+        let verifier = server_cert_verifier(config, &provider)?;
+        let builder = ClientConfig::builder_with_provider(provider)
+            .with_safe_default_protocol_versions()
+            .map_err(|err| invalid_tls_config(&err))?
+            .dangerous()
+            .with_custom_certificate_verifier(verifier);
+        match (&config.tls_client_cert_file, &config.tls_client_key_file) {
+            (Some(cert_file), Some(key_file)) => builder
+                .with_client_auth_cert(read_certificates(cert_file)?, read_private_key(key_file)?)
+                .map_err(|err| invalid_tls_config(&err)),
+            _ => Ok(builder.with_no_client_auth()),
+        }
+    }
+
+    fn server_cert_verifier(
+        config: &DbClientConfig<'_>,
+        provider: &Arc<CryptoProvider>,
+    ) -> Result<Arc<dyn ServerCertVerifier>, Error> {
+        if config.tls_accept_invalid_certs {
+            return Ok(Arc::new(AcceptAnyServerCert {
+                algorithms: provider.signature_verification_algorithms,
+            }));
+        }
+        let mut roots = RootCertStore::empty();
+        if let Some(ca_file) = &config.tls_ca_file {
+            for certificate in read_certificates(ca_file)? {
+                roots
+                    .add(certificate)
+                    .map_err(|err| invalid_tls_config(&err))?;
+            }
+        } else {
+            roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+        }
+        let verifier =
+            WebPkiServerVerifier::builder_with_provider(Arc::new(roots), Arc::clone(provider))
+                .build()
+                .map_err(|err| invalid_tls_config(&err))?;
+        match &config.tls_server_name {
+            Some(server_name) => {
+                let server_name = ServerName::try_from(server_name.to_string()).map_err(|err| {
+                    Error::Configuration(format!(
+                        "invalid TLS server name \"{server_name}\": {err}"
+                    ))
+                })?;
+                Ok(Arc::new(PinnedServerName {
+                    verifier,
+                    server_name,
+                }))
+            },
+            None => Ok(verifier),
+        }
+    }
+
+    fn read_certificates(path: &Path) -> Result<Vec<CertificateDer<'static>>, Error> {
+        let certificates: Vec<_> = CertificateDer::pem_file_iter(path)
+            .and_then(Iterator::collect)
+            .map_err(|err| {
+                Error::Configuration(format!(
+                    "failed to read certificates from {}: {err}",
+                    path.display()
+                ))
+            })?;
+        if certificates.is_empty() {
+            return Err(no_certificates_found(path));
+        }
+        Ok(certificates)
+    }
+
+    fn read_private_key(path: &Path) -> Result<PrivateKeyDer<'static>, Error> {
+        PrivateKeyDer::from_pem_file(path).map_err(|err| {
+            Error::Configuration(format!(
+                "failed to read the private key from {}: {err}",
+                path.display()
+            ))
+        })
+    }
+
+    /// Verifies the certificate of the database server against a configured
+    /// server name instead of the host name of the address.
+    #[derive(Debug)]
+    struct PinnedServerName {
+        verifier: Arc<WebPkiServerVerifier>,
+        server_name: ServerName<'static>,
+    }
+
+    impl ServerCertVerifier for PinnedServerName {
+        fn verify_server_cert(
+            &self,
+            end_entity: &CertificateDer<'_>,
+            intermediates: &[CertificateDer<'_>],
+            _server_name: &ServerName<'_>,
+            ocsp_response: &[u8],
+            now: UnixTime,
+        ) -> Result<ServerCertVerified, rustls::Error> {
+            self.verifier.verify_server_cert(
+                end_entity,
+                intermediates,
+                &self.server_name,
+                ocsp_response,
+                now,
+            )
+        }
+
+        fn verify_tls12_signature(
+            &self,
+            message: &[u8],
+            cert: &CertificateDer<'_>,
+            dss: &DigitallySignedStruct,
+        ) -> Result<HandshakeSignatureValid, rustls::Error> {
+            self.verifier.verify_tls12_signature(message, cert, dss)
+        }
+
+        fn verify_tls13_signature(
+            &self,
+            message: &[u8],
+            cert: &CertificateDer<'_>,
+            dss: &DigitallySignedStruct,
+        ) -> Result<HandshakeSignatureValid, rustls::Error> {
+            self.verifier.verify_tls13_signature(message, cert, dss)
+        }
+
+        fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+            self.verifier.supported_verify_schemes()
+        }
+    }
+
+    /// Accepts any certificate of the database server, but still verifies the
+    /// handshake signatures.
+    #[derive(Debug)]
+    struct AcceptAnyServerCert {
+        algorithms: WebPkiSupportedAlgorithms,
+    }
+
+    impl ServerCertVerifier for AcceptAnyServerCert {
+        fn verify_server_cert(
+            &self,
+            _end_entity: &CertificateDer<'_>,
+            _intermediates: &[CertificateDer<'_>],
+            _server_name: &ServerName<'_>,
+            _ocsp_response: &[u8],
+            _now: UnixTime,
+        ) -> Result<ServerCertVerified, rustls::Error> {
+            Ok(ServerCertVerified::assertion())
+        }
+
+        fn verify_tls12_signature(
+            &self,
+            message: &[u8],
+            cert: &CertificateDer<'_>,
+            dss: &DigitallySignedStruct,
+        ) -> Result<HandshakeSignatureValid, rustls::Error> {
+            verify_tls12_signature(message, cert, dss, &self.algorithms)
+        }
+
+        fn verify_tls13_signature(
+            &self,
+            message: &[u8],
+            cert: &CertificateDer<'_>,
+            dss: &DigitallySignedStruct,
+        ) -> Result<HandshakeSignatureValid, rustls::Error> {
+            verify_tls13_signature(message, cert, dss, &self.algorithms)
+        }
+
+        fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+            self.algorithms.supported_schemes()
+        }
+    }
+}
+
+#[cfg(all(feature = "native-tls", not(feature = "rustls")))]
+mod native_tls_config {
+    use super::{invalid_tls_config, no_certificates_found};
+    use database_migration::config::DbClientConfig;
+    use database_migration::error::Error;
+    use native_tls::{Certificate, Identity, TlsConnector};
+    use std::fs;
+    use std::path::Path;
+
+    const BEGIN_CERTIFICATE: &str = "-----BEGIN CERTIFICATE-----";
+    const END_CERTIFICATE: &str = "-----END CERTIFICATE-----";
+
+    pub fn connector(config: &DbClientConfig<'_>) -> Result<TlsConnector, Error> {
+        if let Some(server_name) = &config.tls_server_name {
+            return Err(Error::Configuration(format!(
+                "tls-server-name \"{server_name}\" is only supported with the feature `rustls`"
+            )));
+        }
+        let mut builder = TlsConnector::builder();
+        if let Some(ca_file) = &config.tls_ca_file {
+            let pem = read_file(ca_file)?;
+            let certificates = pem_certificates(&pem);
+            if certificates.is_empty() {
+                return Err(no_certificates_found(ca_file));
+            }
+            builder.disable_built_in_roots(true);
+            for certificate in certificates {
+                let certificate = Certificate::from_pem(certificate.as_bytes())
+                    .map_err(|err| invalid_tls_config(&err))?;
+                builder.add_root_certificate(certificate);
+            }
+        }
+        if let (Some(cert_file), Some(key_file)) =
+            (&config.tls_client_cert_file, &config.tls_client_key_file)
+        {
+            let identity = Identity::from_pkcs8(
+                read_file(cert_file)?.as_bytes(),
+                read_file(key_file)?.as_bytes(),
+            )
+            .map_err(|err| invalid_tls_config(&err))?;
+            builder.identity(identity);
+        }
+        builder.danger_accept_invalid_certs(config.tls_accept_invalid_certs);
+        builder.build().map_err(|err| invalid_tls_config(&err))
+    }
+
+    fn read_file(path: &Path) -> Result<String, Error> {
+        fs::read_to_string(path).map_err(|err| {
+            Error::Configuration(format!("failed to read {}: {err}", path.display()))
+        })
+    }
+
+    /// Splits a PEM bundle into its certificates, as `native-tls` reads only
+    /// one certificate at a time.
+    fn pem_certificates(pem: &str) -> Vec<&str> {
+        pem.match_indices(BEGIN_CERTIFICATE)
+            .filter_map(|(start, _)| {
+                pem[start..]
+                    .find(END_CERTIFICATE)
+                    .map(|end| &pem[start..start + end + END_CERTIFICATE.len()])
+            })
+            .collect()
+    }
+}
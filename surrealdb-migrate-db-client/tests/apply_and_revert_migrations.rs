@@ -10,6 +10,7 @@ use database_migration::checksum::hash_migration_script;
 use database_migration::config::DEFAULT_MIGRATIONS_TABLE;
 use database_migration::error::Error;
 use database_migration::migration::{ApplicableMigration, Migration, MigrationKind};
+use database_migration::script::{Position, Statement, StatementError};
 use database_migration::test_dsl::key;
 use std::fs;
 use std::path::PathBuf;
@@ -45,6 +46,7 @@ async fn apply_migration_in_transaction_schema_migration() {
     let migration = ApplicableMigration {
         key,
         kind: MigrationKind::Up,
+        script_path: migration.script_path,
         checksum,
         script_content: script_content.clone(),
     };
@@ -96,6 +98,7 @@ async fn apply_migration_in_transaction_schema_migration_with_script_error() {
     let migration = ApplicableMigration {
         key,
         kind: MigrationKind::Up,
+        script_path: migration.script_path,
         checksum,
         script_content: script_content.clone(),
     };
@@ -108,10 +111,22 @@ async fn apply_migration_in_transaction_schema_migration_with_script_error() {
         Ok(value) => {
             panic!("expected script error, but was Ok({value:?}");
         },
-        Err(Error::DbScript(err_map)) => {
-            assert_that!(err_map.get(&4))
-                .some()
-                .is_equal_to(&"An error occurred: test migrate script error".to_string());
+        Err(Error::DbScript(script_error)) => {
+            assert_that!(script_error.script_path).is_equal_to(PathBuf::from(
+                "../fixtures/basic/migrations/20250103_140520_define_quote_table.surql",
+            ));
+            assert_that!(script_error.errors).is_equal_to(vec![StatementError {
+                number: 5,
+                statement: Some(Statement {
+                    text: r#"THROW "test migrate script error""#.into(),
+                    start: Position { line: 9, column: 1 },
+                    end: Position {
+                        line: 9,
+                        column: 33,
+                    },
+                }),
+                message: "An error occurred: test migrate script error".into(),
+            }]);
         },
         Err(other) => {
             panic!("expected Error::DbScript, but was {other:?}");
@@ -164,6 +179,7 @@ async fn revert_migration_in_transaction_schema_migration() {
     let migration = ApplicableMigration {
         key: mig_key,
         kind: MigrationKind::Down,
+        script_path: migration.script_path,
         checksum,
         script_content,
     };
@@ -224,6 +240,7 @@ async fn revert_migration_in_transaction_schema_migration_with_script_error() {
     let migration = ApplicableMigration {
         key: mig_key,
         kind: MigrationKind::Down,
+        script_path: migration.script_path,
         checksum,
         script_content,
     };
@@ -234,10 +251,22 @@ async fn revert_migration_in_transaction_schema_migration_with_script_error() {
         Ok(value) => {
             panic!("expected script error, but was Ok({value:?}");
         },
-        Err(Error::DbScript(err_map)) => {
-            assert_that!(err_map.get(&1))
-                .some()
-                .is_equal_to(&"An error occurred: test revert script error".to_string());
+        Err(Error::DbScript(script_error)) => {
+            assert_that!(script_error.script_path).is_equal_to(PathBuf::from(
+                "../fixtures/with_down_migrations/migrations/20250103_140520_define_quote_table.down.surql",
+            ));
+            assert_that!(script_error.errors).is_equal_to(vec![StatementError {
+                number: 2,
+                statement: Some(Statement {
+                    text: r#"THROW "test revert script error""#.into(),
+                    start: Position { line: 2, column: 1 },
+                    end: Position {
+                        line: 2,
+                        column: 32,
+                    },
+                }),
+                message: "An error occurred: test revert script error".into(),
+            }]);
         },
         Err(other) => {
             panic!("expected Error::DbScript, but was {other:?}");
@@ -1,14 +1,35 @@
 mod fixtures;
 
 use crate::fixtures::db::{
-    client_config_for_testcontainer, db_password, db_username, ns_password, ns_username,
-    start_surrealdb_testcontainer,
+    client_config_for_testcontainer, connect_as_root_user, db_password, db_username, ns_password,
+    ns_username, start_surrealdb_testcontainer,
 };
 use crate::fixtures::load_environment_variables;
 use asserting::prelude::*;
-use database_migration::config::DbAuthLevel;
+use database_migration::config::{DbAuthLevel, DbCapabilities, DbClientConfig};
+use database_migration::error::{Error, ErrorCategory};
 use fixtures::db::initialize_database;
-use surrealdb_migrate_db_client::connect_to_database;
+use std::path::Path;
+use std::time::Duration;
+use surrealdb_migrate_db_client::{connect_to_database, list_databases_in_namespace};
+
+const DEFINE_RECORD_ACCESS: &str = "
+    USE NS playground DB test;
+    DEFINE ACCESS migrator ON DATABASE TYPE RECORD
+        SIGNIN (SELECT * FROM migrator WHERE email = $email AND crypto::argon2::compare(pass, $password));
+    CREATE migrator SET email = 'ci@example.com', pass = crypto::argon2::generate('s3cr3t');
+";
+
+async fn define_record_access(config: &DbClientConfig<'_>) {
+    let db = connect_as_root_user(config)
+        .await
+        .expect("failed to connect to database as root user");
+    db.query(DEFINE_RECORD_ACCESS)
+        .await
+        .unwrap_or_else(|err| panic!("failed to define record access: {err}"))
+        .check()
+        .unwrap_or_else(|err| panic!("failed to define record access: {err}"));
+}
 
 #[tokio::test]
 async fn test_surrealdb_version() {
@@ -37,6 +58,24 @@ async fn can_connect_to_database_as_root_user() {
     assert_that!(db).is_ok();
 }
 
+#[tokio::test]
+async fn can_connect_to_database_with_engine_options() {
+    load_environment_variables();
+    let db_server = start_surrealdb_testcontainer().await;
+
+    let config = client_config_for_testcontainer(&db_server)
+        .await
+        .with_auth_level(DbAuthLevel::Root)
+        .with_capacity(0)
+        .with_query_timeout(Some(Duration::from_secs(3600)))
+        .with_transaction_timeout(Some(Duration::from_secs(3600)))
+        .with_strict(true)
+        .with_capabilities(DbCapabilities::AllowAll);
+    let db = connect_to_database(&config).await;
+
+    assert_that!(db).is_ok();
+}
+
 #[tokio::test]
 async fn can_connect_to_database_as_namespace_user() {
     load_environment_variables();
@@ -71,3 +110,155 @@ async fn can_connect_to_database_as_database_user() {
 
     assert_that!(db).is_ok();
 }
+
+#[tokio::test]
+async fn can_connect_to_database_with_token() {
+    load_environment_variables();
+    let db_server = start_surrealdb_testcontainer().await;
+    let config = client_config_for_testcontainer(&db_server).await;
+    initialize_database(&config).await;
+    let config = config
+        .with_namespace("playground")
+        .with_database("test")
+        .with_auth_level(DbAuthLevel::Database)
+        .with_username(db_username())
+        .with_password(db_password());
+    let token = connect_to_database(&config)
+        .await
+        .unwrap_or_else(|err| panic!("failed to sign in as database user: {err}"))
+        .token()
+        .as_insecure_token()
+        .to_string();
+
+    let config = config
+        .with_auth_level(DbAuthLevel::Token)
+        .with_token(token)
+        .with_password("");
+    let db = connect_to_database(&config).await;
+
+    assert_that!(db).is_ok();
+}
+
+#[tokio::test]
+async fn connect_to_database_with_invalid_token_fails() {
+    load_environment_variables();
+    let db_server = start_surrealdb_testcontainer().await;
+
+    let config = client_config_for_testcontainer(&db_server)
+        .await
+        .with_auth_level(DbAuthLevel::Token)
+        .with_token("not-a-valid-token");
+    let db = connect_to_database(&config).await;
+
+    assert_that!(db.map(|_| ()).map_err(|err| err.category()))
+        .is_equal_to(Err(ErrorCategory::Authentication));
+}
+
+#[tokio::test]
+async fn connect_to_database_with_token_auth_level_but_without_token_fails() {
+    let config = DbClientConfig::default().with_auth_level(DbAuthLevel::Token);
+
+    let db = connect_to_database(&config).await;
+
+    assert_that!(db.map(|_| ())).is_equal_to(Err(Error::Configuration(
+        "auth-level is Token, but no token is given".into(),
+    )));
+}
+
+#[tokio::test]
+async fn connect_to_database_with_client_certificate_but_without_key_fails() {
+    let config = DbClientConfig::default()
+        .with_address("wss://localhost:8000")
+        .with_tls_client_cert_file(Path::new("tests/fixtures/tls/client.pem"));
+
+    let db = connect_to_database(&config).await;
+
+    assert_that!(db.map(|_| ())).is_equal_to(Err(Error::Configuration(
+        "tls-client-cert-file and tls-client-key-file must be set both".into(),
+    )));
+}
+
+#[cfg(any(feature = "rustls", feature = "native-tls"))]
+#[tokio::test]
+async fn connect_to_database_with_ca_file_without_certificates_fails() {
+    let config = DbClientConfig::default()
+        .with_address("wss://localhost:8000")
+        .with_tls_ca_file(Path::new("tests/fixtures/tls/no_certificates.pem"));
+
+    let db = connect_to_database(&config).await;
+
+    assert_that!(db.map(|_| ())).is_equal_to(Err(Error::Configuration(
+        "no certificates found in tests/fixtures/tls/no_certificates.pem".into(),
+    )));
+}
+
+#[tokio::test]
+async fn can_connect_to_database_via_record_access() {
+    load_environment_variables();
+    let db_server = start_surrealdb_testcontainer().await;
+    let config = client_config_for_testcontainer(&db_server).await;
+    initialize_database(&config).await;
+    define_record_access(&config).await;
+
+    let config = config
+        .with_namespace("playground")
+        .with_database("test")
+        .with_auth_level(DbAuthLevel::Record)
+        .with_access("migrator")
+        .with_access_variable("email", "ci@example.com")
+        .with_access_variable("password", "s3cr3t");
+    let db = connect_to_database(&config).await;
+
+    assert_that!(db).is_ok();
+}
+
+#[tokio::test]
+async fn connect_to_database_via_record_access_with_wrong_variables_fails() {
+    load_environment_variables();
+    let db_server = start_surrealdb_testcontainer().await;
+    let config = client_config_for_testcontainer(&db_server).await;
+    initialize_database(&config).await;
+    define_record_access(&config).await;
+
+    let config = config
+        .with_namespace("playground")
+        .with_database("test")
+        .with_auth_level(DbAuthLevel::Record)
+        .with_access("migrator")
+        .with_access_variable("email", "ci@example.com")
+        .with_access_variable("password", "wrong");
+    let db = connect_to_database(&config).await;
+
+    assert_that!(db.map(|_| ()).map_err(|err| err.category()))
+        .is_equal_to(Err(ErrorCategory::Authentication));
+}
+
+#[tokio::test]
+async fn list_databases_in_namespace_as_namespace_user() {
+    load_environment_variables();
+    let db_server = start_surrealdb_testcontainer().await;
+    let config = client_config_for_testcontainer(&db_server).await;
+    initialize_database(&config).await;
+
+    let config = config
+        .with_namespace("playground")
+        .with_auth_level(DbAuthLevel::Namespace)
+        .with_username(ns_username())
+        .with_password(ns_password());
+    let db = connect_to_database(&config)
+        .await
+        .unwrap_or_else(|err| panic!("failed to connect to namespace: {err}"));
+    db.query("USE NS playground; DEFINE DATABASE tenant_b; DEFINE DATABASE tenant_a;")
+        .await
+        .unwrap_or_else(|err| panic!("failed to define tenant databases: {err}"))
+        .check()
+        .unwrap_or_else(|err| panic!("failed to define tenant databases: {err}"));
+
+    let databases = list_databases_in_namespace(&db).await;
+
+    assert_that!(databases).ok().is_equal_to(vec![
+        "tenant_a".to_string(),
+        "tenant_b".into(),
+        "test".into(),
+    ]);
+}
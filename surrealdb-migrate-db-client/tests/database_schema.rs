@@ -0,0 +1,110 @@
+mod fixtures;
+
+use crate::fixtures::db::{
+    client_config_for_testcontainer, connect_to_test_database_as_database_user,
+    define_default_migrations_table, start_surrealdb_testcontainer,
+};
+use asserting::prelude::*;
+use database_migration::config::DEFAULT_MIGRATIONS_TABLE;
+use database_migration::error::Error;
+use surrealdb_migrate_db_client::schema::{evaluate_schema_definitions, fetch_database_schema};
+
+#[tokio::test]
+async fn fetch_schema_of_empty_database() {
+    let db_server = start_surrealdb_testcontainer().await;
+    let config = client_config_for_testcontainer(&db_server).await;
+    let db = connect_to_test_database_as_database_user(config).await;
+
+    let schema = fetch_database_schema(DEFAULT_MIGRATIONS_TABLE, &db)
+        .await
+        .expect("failed to fetch database schema");
+
+    assert_that!(schema.is_empty()).is_true();
+}
+
+#[tokio::test]
+async fn fetch_schema_excludes_the_migrations_table() {
+    let db_server = start_surrealdb_testcontainer().await;
+    let config = client_config_for_testcontainer(&db_server).await;
+    let db = connect_to_test_database_as_database_user(config).await;
+    define_default_migrations_table(&db).await;
+    db.query(
+        "\
+DEFINE TABLE quote SCHEMAFULL;
+DEFINE FIELD text ON quote TYPE string;
+DEFINE INDEX quote_text ON quote FIELDS text;
+",
+    )
+    .await
+    .expect("failed to define quote table")
+    .check()
+    .expect("failed to define quote table");
+
+    let schema = fetch_database_schema(DEFAULT_MIGRATIONS_TABLE, &db)
+        .await
+        .expect("failed to fetch database schema");
+
+    assert_that!(schema.tables.keys().cloned().collect::<Vec<_>>()).contains_exactly(["quote"]);
+    let quote_table = &schema.tables["quote"];
+    assert_that!(quote_table.fields.keys().cloned().collect::<Vec<_>>()).contains_exactly(["text"]);
+    assert_that!(quote_table.indexes.keys().cloned().collect::<Vec<_>>())
+        .contains_exactly(["quote_text"]);
+    assert_that!(schema.to_script())
+        .starts_with("DEFINE TABLE quote")
+        .contains("DEFINE FIELD text ON quote TYPE string")
+        .contains("DEFINE INDEX quote_text ON quote FIELDS text");
+}
+
+#[tokio::test]
+async fn evaluate_schema_definitions_in_empty_scratch_database() {
+    let db_server = start_surrealdb_testcontainer().await;
+    let config = client_config_for_testcontainer(&db_server).await;
+    let scratch = connect_to_test_database_as_database_user(config).await;
+
+    let schema = evaluate_schema_definitions(
+        "\
+DEFINE TABLE quote SCHEMAFULL;
+DEFINE FIELD text ON quote TYPE string;
+",
+        DEFAULT_MIGRATIONS_TABLE,
+        &scratch,
+    )
+    .await
+    .expect("failed to evaluate schema definitions");
+
+    assert_that!(schema.tables.keys().cloned().collect::<Vec<_>>()).contains_exactly(["quote"]);
+    assert_that!(
+        schema.tables["quote"]
+            .fields
+            .keys()
+            .cloned()
+            .collect::<Vec<_>>()
+    )
+    .contains_exactly(["text"]);
+    let scratch_schema = fetch_database_schema(DEFAULT_MIGRATIONS_TABLE, &scratch)
+        .await
+        .expect("failed to fetch database schema");
+    assert_that!(scratch_schema.is_empty()).is_true();
+}
+
+#[tokio::test]
+async fn evaluate_schema_definitions_in_not_empty_scratch_database() {
+    let db_server = start_surrealdb_testcontainer().await;
+    let config = client_config_for_testcontainer(&db_server).await;
+    let scratch = connect_to_test_database_as_database_user(config).await;
+    scratch
+        .query("DEFINE TABLE author SCHEMALESS;")
+        .await
+        .expect("failed to define author table")
+        .check()
+        .expect("failed to define author table");
+
+    let schema = evaluate_schema_definitions(
+        "DEFINE TABLE quote SCHEMAFULL;",
+        DEFAULT_MIGRATIONS_TABLE,
+        &scratch,
+    )
+    .await;
+
+    assert_that!(schema).is_equal_to(Err(Error::ScratchDatabaseNotEmpty("author".into())));
+}
@@ -0,0 +1,42 @@
+mod fixtures;
+
+use asserting::prelude::*;
+use database_migration::config::{DEFAULT_MIGRATIONS_TABLE, DbAuthLevel, DbClientConfig};
+use database_migration::migration::MigrationsTableInfo;
+use surrealdb_migrate_db_client::{
+    connect_to_database, define_migrations_table, find_migrations_table_info,
+};
+
+#[tokio::test]
+async fn can_connect_to_in_memory_database_as_root_user() {
+    let config = DbClientConfig::default()
+        .with_address("mem://")
+        .with_auth_level(DbAuthLevel::Root);
+
+    let db = connect_to_database(&config).await;
+
+    assert_that!(db).is_ok();
+}
+
+#[tokio::test]
+async fn define_migrations_table_in_in_memory_database() {
+    let config = DbClientConfig::default()
+        .with_address("mem://")
+        .with_namespace("playground")
+        .with_database("test");
+    let db = connect_to_database(&config)
+        .await
+        .expect("failed to connect to in-memory database");
+
+    define_migrations_table(DEFAULT_MIGRATIONS_TABLE, &db)
+        .await
+        .expect("failed to define migrations table");
+
+    let result = find_migrations_table_info(DEFAULT_MIGRATIONS_TABLE, &db).await;
+
+    assert_that!(result).ok().is_equal_to(MigrationsTableInfo::Table {
+        name: DEFAULT_MIGRATIONS_TABLE.into(),
+        version: Some("1.1".into()),
+        definition: "DEFINE TABLE migrations TYPE NORMAL SCHEMAFULL COMMENT 'version:1.1' PERMISSIONS FOR select FULL, FOR create, update, delete NONE".into(),
+    });
+}
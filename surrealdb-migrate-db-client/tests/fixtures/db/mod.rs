@@ -1,5 +1,6 @@
 use crate::fixtures::load_environment_variables;
 use database_migration::config::{DEFAULT_MIGRATIONS_TABLE, DbAuthLevel, DbClientConfig};
+use database_migration::error::Error;
 use std::collections::HashMap;
 use std::env;
 use surrealdb_migrate_db_client::{
@@ -36,7 +37,7 @@ pub async fn start_surrealdb_testcontainer() -> ContainerAsync<SurrealDb> {
         .expect("failed to start SurrealDb testcontainer")
 }
 
-pub async fn connect_as_root_user(config: &DbClientConfig<'_>) -> Result<DbConnection, DbError> {
+pub async fn connect_as_root_user(config: &DbClientConfig<'_>) -> Result<DbConnection, Error> {
     let config = config.clone().with_auth_level(DbAuthLevel::Root);
     connect_to_database(&config).await
 }
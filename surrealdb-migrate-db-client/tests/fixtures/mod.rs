@@ -9,6 +9,15 @@ pub fn load_environment_variables() {
 #[cfg(test)]
 mod dummy_extern_uses {
     use chrono as _;
+    use log as _;
+    #[cfg(feature = "native-tls")]
+    use native_tls as _;
+    #[cfg(feature = "rustls")]
+    use rustls as _;
     use serde as _;
     use surrealdb as _;
+    #[cfg(feature = "tracing")]
+    use tracing as _;
+    #[cfg(feature = "rustls")]
+    use webpki_roots as _;
 }
@@ -10,22 +10,53 @@ use asserting::prelude::*;
 use chrono::DateTime;
 use database_migration::checksum::{Checksum, hash_migration_script};
 use database_migration::config::{DEFAULT_MIGRATIONS_TABLE, MIGRATION_KEY_FORMAT_STR};
-use database_migration::error::Error;
+use database_migration::error::{DatabaseError, Error, ErrorCategory};
 use database_migration::migration::{
-    Execution, Migration, MigrationKind, MigrationsTableInfo, Reversion,
+    Execution, FailedAttempt, HistoryAction, HistoryEvent, Migration, MigrationKind,
+    MigrationsTableInfo, Reversion,
 };
 use database_migration::test_dsl::{datetime, key};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::iter;
 use std::path::PathBuf;
 use std::time::Duration;
 use surrealdb::sql;
 use surrealdb_migrate_db_client::{
-    define_migrations_table, delete_migration_execution, find_max_applied_migration_key,
-    find_migrations_table_info, insert_migration_execution,
+    define_migrations_table, delete_failed_attempts, delete_migration_execution,
+    find_max_applied_migration_key, find_migrations_table_info, history_table_name,
+    insert_failed_attempt, insert_migration_execution, select_all_executions,
+    select_failed_attempts, select_migration_history, upgrade_migrations_table,
 };
 
 const DEFINE_MIGRATIONS_TABLE: &str = include_str!("../surql/define_migrations_table.surql");
 
+const DEFINE_MIGRATIONS_TABLE_1_0: &str = "\
+DEFINE TABLE migrations SCHEMAFULL TYPE NORMAL COMMENT 'version:1.0'
+    PERMISSIONS
+        FOR select FULL
+        FOR create, update, delete NONE;
+
+DEFINE FIELD applied_rank ON migrations TYPE int;
+DEFINE FIELD key ON migrations TYPE string;
+DEFINE FIELD title ON migrations TYPE string;
+DEFINE FIELD kind ON migrations TYPE string;
+DEFINE FIELD script_path ON migrations TYPE string;
+DEFINE FIELD checksum ON migrations TYPE string;
+DEFINE FIELD applied_at ON migrations TYPE datetime;
+DEFINE FIELD applied_by ON migrations TYPE string;
+DEFINE FIELD execution_time ON migrations TYPE duration;
+
+DEFINE INDEX rank_idx on TABLE migrations FIELDS applied_rank UNIQUE;
+DEFINE INDEX key_idx ON TABLE migrations FIELDS key UNIQUE;
+";
+
+fn define_migrations_table_script(table_name: &str) -> String {
+    DEFINE_MIGRATIONS_TABLE
+        .replace("$migrations_table", table_name)
+        .replace("$history_table", &history_table_name(table_name))
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 struct MigrationExecutionData {
     applied_rank: i64,
@@ -51,7 +82,75 @@ async fn define_migrations_table_in_empty_database() {
 
     let db_tables_info = get_db_tables_info(&db).await;
 
-    assert_that!(db_tables_info).contains_key("my_migrations");
+    assert_that!(&db_tables_info).contains_key("my_migrations");
+    assert_that!(db_tables_info).contains_key("my_migrations_history");
+}
+
+#[tokio::test]
+async fn upgrade_migrations_table_of_version_1_0() {
+    let db_server = start_surrealdb_testcontainer().await;
+    let config = client_config_for_testcontainer(&db_server).await;
+    let db = connect_to_test_database_as_database_user(config).await;
+
+    let migrations_table_defined = db
+        .query(DEFINE_MIGRATIONS_TABLE_1_0)
+        .await
+        .expect("failed to read response")
+        .check();
+    assert_that!(migrations_table_defined).is_ok();
+
+    let result = upgrade_migrations_table(DEFAULT_MIGRATIONS_TABLE, &db).await;
+
+    assert_that!(result).is_ok();
+
+    let table_info = find_migrations_table_info(DEFAULT_MIGRATIONS_TABLE, &db).await;
+
+    assert_that!(table_info).ok().is_equal_to(MigrationsTableInfo::Table {
+        name: DEFAULT_MIGRATIONS_TABLE.into(),
+        version: Some("1.1".into()),
+        definition: "DEFINE TABLE migrations TYPE NORMAL SCHEMAFULL COMMENT 'version:1.1' PERMISSIONS FOR select FULL, FOR create, update, delete NONE".into(),
+    });
+
+    let db_tables_info = get_db_tables_info(&db).await;
+
+    assert_that!(db_tables_info).contains_key("migrations_history");
+
+    let result = insert_failed_attempt(
+        failed_attempt("20250103_153309"),
+        DEFAULT_MIGRATIONS_TABLE,
+        &db,
+    )
+    .await;
+
+    assert_that!(result).is_ok();
+}
+
+#[tokio::test]
+async fn upgrade_migrations_table_of_current_version_leaves_it_untouched() {
+    let db_server = start_surrealdb_testcontainer().await;
+    let config = client_config_for_testcontainer(&db_server).await;
+    let db = connect_to_test_database_as_database_user(config).await;
+
+    let migrations_table_defined = define_migrations_table(DEFAULT_MIGRATIONS_TABLE, &db).await;
+    assert_that!(migrations_table_defined).is_ok();
+    let tables_before = get_db_tables_info(&db).await;
+
+    let result = upgrade_migrations_table(DEFAULT_MIGRATIONS_TABLE, &db).await;
+
+    assert_that!(result).is_ok();
+    assert_that!(get_db_tables_info(&db).await).is_equal_to(tables_before);
+}
+
+#[tokio::test]
+async fn upgrade_migrations_table_in_empty_database() {
+    let db_server = start_surrealdb_testcontainer().await;
+    let config = client_config_for_testcontainer(&db_server).await;
+    let db = connect_to_test_database_as_database_user(config).await;
+
+    let result = upgrade_migrations_table(DEFAULT_MIGRATIONS_TABLE, &db).await;
+
+    assert_that!(result).is_ok();
+    assert_that!(get_db_tables_info(&db).await).is_empty();
 }
 
 #[tokio::test]
@@ -61,7 +160,7 @@ async fn define_migrations_table_in_database_with_existing_migrations_table() {
     let db = connect_to_test_database_as_database_user(config).await;
 
     let migrations_table_defined = db
-        .query(DEFINE_MIGRATIONS_TABLE.replace("$migrations_table", DEFAULT_MIGRATIONS_TABLE))
+        .query(define_migrations_table_script(DEFAULT_MIGRATIONS_TABLE))
         .await
         .expect("failed to read response")
         .check();
@@ -69,9 +168,12 @@ async fn define_migrations_table_in_database_with_existing_migrations_table() {
 
     let result = define_migrations_table(DEFAULT_MIGRATIONS_TABLE, &db).await;
 
-    assert_that!(result).err().is_equal_to(Error::DbQuery(
-        "The table 'migrations' already exists".into(),
-    ));
+    assert_that!(result)
+        .err()
+        .is_equal_to(Error::Db(DatabaseError::new(
+            ErrorCategory::Constraint,
+            "The table 'migrations' already exists",
+        )));
 }
 
 #[tokio::test]
@@ -116,7 +218,7 @@ async fn find_migrations_table_info_in_database_with_migrations_table_existing()
     let table_name = "my_migrations";
 
     let migrations_table_defined = db
-        .query(DEFINE_MIGRATIONS_TABLE.replace("$migrations_table", table_name))
+        .query(define_migrations_table_script(table_name))
         .await
         .expect("failed to read response")
         .check();
@@ -126,8 +228,8 @@ async fn find_migrations_table_info_in_database_with_migrations_table_existing()
 
     assert_that!(result).ok().is_equal_to(MigrationsTableInfo::Table {
         name: table_name.into(),
-        version: Some("1.0".into()),
-        definition: "DEFINE TABLE my_migrations TYPE NORMAL SCHEMAFULL COMMENT 'version:1.0' PERMISSIONS FOR select FULL, FOR create, update, delete NONE".into(),
+        version: Some("1.1".into()),
+        definition: "DEFINE TABLE my_migrations TYPE NORMAL SCHEMAFULL COMMENT 'version:1.1' PERMISSIONS FOR select FULL, FOR create, update, delete NONE".into(),
     });
 }
 
@@ -141,7 +243,7 @@ async fn find_migrations_table_info_in_database_with_migrations_table_existing_b
     let table_name = "my_migrations";
 
     let migrations_table_defined = db
-        .query(DEFINE_MIGRATIONS_TABLE.replace("$migrations_table", DEFAULT_MIGRATIONS_TABLE))
+        .query(define_migrations_table_script(DEFAULT_MIGRATIONS_TABLE))
         .await
         .expect("failed to read response")
         .check();
@@ -253,7 +355,16 @@ async fn insert_migration_execution_with_same_key_as_existing_one() {
     let result =
         insert_migration_execution(migration, execution, DEFAULT_MIGRATIONS_TABLE, &db).await;
 
-    assert_that!(result).err().is_equal_to(Error::DbQuery("There was a problem with the database: Database record `migrations:⟨20250103_153309⟩` already exists".into()));
+    assert_that!(result)
+        .err()
+        .is_equal_to(Error::Db(DatabaseError::new(
+            ErrorCategory::Constraint,
+            "Database record `migrations:⟨20250103_153309⟩` already exists",
+        )));
+
+    let history = select_migration_history(DEFAULT_MIGRATIONS_TABLE, &db).await;
+
+    assert_that!(history).ok().has_length(1);
 }
 
 #[tokio::test]
@@ -591,3 +702,216 @@ async fn find_max_applied_migration_key_in_table_with_2_records() {
         .ok()
         .is_equal_to(Some(key("20250103_140521")));
 }
+
+#[tokio::test]
+async fn select_migration_history_of_applied_and_reverted_migration() {
+    let db_server = start_surrealdb_testcontainer().await;
+    let config = client_config_for_testcontainer(&db_server).await;
+    let db = connect_to_test_database_as_database_user(config).await;
+
+    let mig_key = key("20250103_153309");
+
+    let migration = Migration {
+        key: mig_key,
+        title: "define some tables".into(),
+        kind: MigrationKind::Up,
+        script_path: PathBuf::from("migrations/20250103_153309_define_some_tables.surql"),
+    };
+
+    let checksum = hash_migration_script(
+        &migration,
+        r#"LET $data = ["J. Jonah Jameson", "James Earl Jones"];"#,
+    );
+
+    let execution = Execution {
+        key: mig_key,
+        applied_rank: 1,
+        applied_by: "some.user".into(),
+        applied_at: datetime("2025-01-06 07:12:50+01:00"),
+        checksum,
+        execution_time: Duration::from_millis(380),
+    };
+
+    insert_migration_execution(migration, execution, DEFAULT_MIGRATIONS_TABLE, &db)
+        .await
+        .expect("failed to insert migration execution");
+
+    let reversion = Reversion {
+        key: mig_key,
+        reverted_by: "other.user".into(),
+        reverted_at: datetime("2025-01-30 12:42:31+01:00"),
+        execution_time: Duration::from_micros(230),
+    };
+
+    delete_migration_execution(reversion, DEFAULT_MIGRATIONS_TABLE, &db)
+        .await
+        .expect("failed to delete migration execution");
+
+    let history = select_migration_history(DEFAULT_MIGRATIONS_TABLE, &db).await;
+
+    assert_that!(history).ok().is_equal_to(vec![
+        HistoryEvent {
+            key: mig_key,
+            title: "define some tables".into(),
+            kind: MigrationKind::Up,
+            action: HistoryAction::Applied,
+            checksum,
+            performed_by: "some.user".into(),
+            performed_at: datetime("2025-01-06 07:12:50+01:00"),
+            execution_time: Duration::from_millis(380),
+        },
+        HistoryEvent {
+            key: mig_key,
+            title: "define some tables".into(),
+            kind: MigrationKind::Down,
+            action: HistoryAction::Reverted,
+            checksum,
+            performed_by: "other.user".into(),
+            performed_at: datetime("2025-01-30 12:42:31+01:00"),
+            execution_time: Duration::from_micros(230),
+        },
+    ]);
+}
+
+#[tokio::test]
+async fn select_migration_history_from_empty_database() {
+    let db_server = start_surrealdb_testcontainer().await;
+    let config = client_config_for_testcontainer(&db_server).await;
+    let db = connect_to_test_database_as_database_user(config).await;
+
+    let history = select_migration_history(DEFAULT_MIGRATIONS_TABLE, &db).await;
+
+    assert_that!(history).ok().is_empty();
+}
+
+fn failed_attempt(mig_key: &str) -> FailedAttempt {
+    let migration = Migration {
+        key: key(mig_key),
+        title: "define some tables".into(),
+        kind: MigrationKind::Up,
+        script_path: PathBuf::from(format!("migrations/{mig_key}_define_some_tables.surql")),
+    };
+    FailedAttempt {
+        key: key(mig_key),
+        title: "define some tables".into(),
+        kind: MigrationKind::Up,
+        checksum: hash_migration_script(&migration, "DEFINE TABLE foo SCHEMAFULL; BOOM;"),
+        script_path: migration.script_path,
+        attempted_by: "some.user".into(),
+        attempted_at: datetime("2025-01-06 07:12:50+01:00"),
+        errors: iter::once((1, "Parse error: unexpected token `BOOM`".to_string())).collect(),
+    }
+}
+
+#[tokio::test]
+async fn insert_failed_attempt_and_select_it() {
+    let db_server = start_surrealdb_testcontainer().await;
+    let config = client_config_for_testcontainer(&db_server).await;
+    let db = connect_to_test_database_as_database_user(config).await;
+
+    let attempt = failed_attempt("20250103_153309");
+
+    let result = insert_failed_attempt(attempt.clone(), DEFAULT_MIGRATIONS_TABLE, &db).await;
+
+    assert_that!(result).is_ok();
+
+    let failed_attempts = select_failed_attempts(DEFAULT_MIGRATIONS_TABLE, &db).await;
+
+    assert_that!(failed_attempts)
+        .ok()
+        .is_equal_to(vec![attempt]);
+
+    let executions = select_all_executions(DEFAULT_MIGRATIONS_TABLE, &db).await;
+
+    assert_that!(executions).ok().is_empty();
+
+    let max_key = find_max_applied_migration_key(DEFAULT_MIGRATIONS_TABLE, &db).await;
+
+    assert_that!(max_key).ok().is_none();
+}
+
+#[tokio::test]
+async fn insert_migration_execution_replaces_failed_attempt() {
+    let db_server = start_surrealdb_testcontainer().await;
+    let config = client_config_for_testcontainer(&db_server).await;
+    let db = connect_to_test_database_as_database_user(config).await;
+
+    let attempt = failed_attempt("20250103_153309");
+
+    insert_failed_attempt(attempt, DEFAULT_MIGRATIONS_TABLE, &db)
+        .await
+        .expect("failed to insert failed attempt");
+
+    let migration = Migration {
+        key: key("20250103_153309"),
+        title: "define some tables".into(),
+        kind: MigrationKind::Up,
+        script_path: PathBuf::from("migrations/20250103_153309_define_some_tables.surql"),
+    };
+
+    let execution = Execution {
+        key: key("20250103_153309"),
+        applied_rank: 1,
+        applied_by: "some.user".into(),
+        applied_at: datetime("2025-01-06 07:22:10+01:00"),
+        checksum: hash_migration_script(&migration, "DEFINE TABLE foo SCHEMAFULL;"),
+        execution_time: Duration::from_millis(120),
+    };
+
+    insert_migration_execution(migration, execution.clone(), DEFAULT_MIGRATIONS_TABLE, &db)
+        .await
+        .expect("failed to insert migration execution");
+
+    let failed_attempts = select_failed_attempts(DEFAULT_MIGRATIONS_TABLE, &db).await;
+
+    assert_that!(failed_attempts).ok().is_empty();
+
+    let executions = select_all_executions(DEFAULT_MIGRATIONS_TABLE, &db).await;
+
+    assert_that!(executions)
+        .ok()
+        .is_equal_to(HashMap::from([(key("20250103_153309"), execution)]));
+}
+
+#[tokio::test]
+async fn delete_failed_attempts_returns_keys_of_removed_attempts() {
+    let db_server = start_surrealdb_testcontainer().await;
+    let config = client_config_for_testcontainer(&db_server).await;
+    let db = connect_to_test_database_as_database_user(config).await;
+
+    insert_failed_attempt(
+        failed_attempt("20250103_153309"),
+        DEFAULT_MIGRATIONS_TABLE,
+        &db,
+    )
+    .await
+    .expect("failed to insert failed attempt 1");
+    insert_failed_attempt(
+        failed_attempt("20250102_091500"),
+        DEFAULT_MIGRATIONS_TABLE,
+        &db,
+    )
+    .await
+    .expect("failed to insert failed attempt 2");
+
+    let deleted = delete_failed_attempts(DEFAULT_MIGRATIONS_TABLE, &db).await;
+
+    assert_that!(deleted)
+        .ok()
+        .is_equal_to(vec![key("20250102_091500"), key("20250103_153309")]);
+
+    let failed_attempts = select_failed_attempts(DEFAULT_MIGRATIONS_TABLE, &db).await;
+
+    assert_that!(failed_attempts).ok().is_empty();
+}
+
+#[tokio::test]
+async fn delete_failed_attempts_from_empty_database() {
+    let db_server = start_surrealdb_testcontainer().await;
+    let config = client_config_for_testcontainer(&db_server).await;
+    let db = connect_to_test_database_as_database_user(config).await;
+
+    let deleted = delete_failed_attempts(DEFAULT_MIGRATIONS_TABLE, &db).await;
+
+    assert_that!(deleted).ok().is_empty();
+}
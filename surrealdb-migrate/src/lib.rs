@@ -46,6 +46,40 @@
 //!
 //! For a fully working example see the [run_migrations] example.
 //!
+//! ### Observing the progress
+//!
+//! Applications that want to report the progress of a migration in their own
+//! way, e.g. as telemetry or on a status page, can register a
+//! [`MigrationListener`](event::MigrationListener) with the
+//! [`MigrationRunner`]. The listener receives a [`MigrationEvent`](event::MigrationEvent)
+//! when a run starts and finishes, when a migration is started, applied,
+//! reverted or has failed, and when a problem is found while verifying the
+//! migrations. Each event names the namespace and database it refers to, so
+//! that the events of several databases migrated concurrently can be told
+//! apart.
+//!
+//! ```no_run
+//! use surrealdb_migrate::config::RunnerConfig;
+//! use surrealdb_migrate::event::MigrationEvent;
+//! use surrealdb_migrate::runner::MigrationRunner;
+//!
+//! let runner = MigrationRunner::new(RunnerConfig::default()).with_listener(
+//!     |event: &MigrationEvent<'_>| {
+//!         if let MigrationEvent::MigrationApplied {
+//!             target,
+//!             migration,
+//!             execution,
+//!         } = event
+//!         {
+//!             println!(
+//!                 "applied {} to {target} in {:?}",
+//!                 migration.title, execution.execution_time
+//!             );
+//!         }
+//!     },
+//! );
+//! ```
+//!
 //! The configuration of the database connection and the migration runner can be
 //! provided in an application specific way or by using the configuration
 //! mechanism provided by this crate (see next chapter below).
@@ -62,12 +96,34 @@
 //!
 //! ## Crate features
 //!
-//! | Feature  | Description                                                                                                 | Default |
-//! |----------|-------------------------------------------------------------------------------------------------------------|:-------:|
-//! | `config` | Provides a configuration mechanism for the DB-connection and the migration runner (see [`settings`] module) | no      |
+//! | Feature         | Description                                                                                                 | Default |
+//! |-----------------|-------------------------------------------------------------------------------------------------------------|:-------:|
+//! | `config`        | Provides a configuration mechanism for the DB-connection and the migration runner (see [`settings`] module) | no      |
+//! | `tracing`       | Instruments connecting to the database, verifying, applying and reverting migrations with [`tracing`] spans | no      |
+//! | `protocol-ws`   | Connects to a database server via `ws://` or `wss://`                                                       | yes     |
+//! | `protocol-http` | Connects to a database server via `http://` or `https://`                                                   | no      |
+//! | `rustls`        | Uses `rustls` for TLS connections                                                                           | yes     |
+//! | `native-tls`    | Uses the TLS implementation of the platform for TLS connections                                             | no      |
+//! | `kv-mem`        | Embedded in-memory database engine, selected by the address `mem://`                                        | no      |
+//! | `kv-surrealkv`  | Embedded database engine storing the data in files, selected by an address like `surrealkv://path`          | no      |
+//! | `kv-rocksdb`    | Embedded `RocksDB` database engine, selected by an address like `rocksdb://path`                            | no      |
+//!
+//! With the `tracing` feature enabled, each run of the migration runner is
+//! recorded as a span with the migrations folder and the namespace and
+//! database of the connection as fields. Each applied or reverted migration
+//! gets a child span with the fields `key`, `title`, `kind`, `checksum` and
+//! `execution_time_ms`. Queries on the migrations table are recorded as
+//! spans on the `debug` level.
+//!
+//! The features `protocol-ws`, `protocol-http`, `rustls`, `native-tls`,
+//! `kv-mem`, `kv-surrealkv` and `kv-rocksdb` enable the related features of
+//! the `surrealdb` crate. With one of the embedded database engines enabled,
+//! migrations can be applied to an in-memory database or to local files
+//! without running a database server, e.g. in tests.
 //!
 //! [run_migrations]: https://github.com/innoave/surrealdb-migrate/blob/main/surrealdb-migrate/examples/run_migrations.rs
 //! [SurrealDB]: https://surrealdb.com
+//! [`tracing`]: https://docs.rs/tracing
 #![doc(html_root_url = "https://docs.rs/surrealdb-migrate/0.2.0")]
 
 // imports for crate level doc
@@ -95,6 +151,10 @@ pub mod db_client {
     pub use surrealdb_migrate_db_client::DbError;
     #[doc(inline)]
     pub use surrealdb_migrate_db_client::connect_to_database;
+    #[doc(inline)]
+    pub use surrealdb_migrate_db_client::schema::SchemaDiff;
+    #[doc(inline)]
+    pub use surrealdb_migrate_db_client::wait_for_database;
 }
 
 // test code snippets in the README.md
@@ -1,50 +1,95 @@
-use chrono::NaiveDateTime;
+use chrono::{NaiveDateTime, Utc};
 use database_migration::action::{
     Checks, ListChangedAfterExecution, ListOutOfOrder, Migrate, MigrationsToApply, Revert, Verify,
 };
-use database_migration::config::{MIGRATION_KEY_FORMAT_STR, RunnerConfig};
-use database_migration::definition::ExcludedFiles;
+use database_migration::callback::{Callback, CallbackScript};
+use database_migration::config::{DbClientConfig, MIGRATION_KEY_FORMAT_STR, RunnerConfig};
+use database_migration::definition::{ExcludedFiles, MigrationFilenameStrategy};
 use database_migration::error::Error;
-use database_migration::migration::{Execution, Migration, MigrationKind};
-use database_migration::repository::{ListMigrations, ReadScriptContent};
-use database_migration::result::{Migrated, Reverted, Verified};
+use database_migration::event::{MigrationEvent, MigrationListener, Operation, Target};
+use database_migration::migration::{
+    ApplicableMigration, Execution, FailedAttempt, HistoryEvent, Migration, MigrationKind,
+    NewMigration, ProblematicMigration, Reversion,
+};
+use database_migration::repository::{
+    CreateNewMigration, ListMigrations, ReadCallbackScript, ReadScriptContent,
+};
+use database_migration::result::{Diffed, Migrated, Repaired, Reverted, Verified};
+use database_migration::scope::MigrationScope;
+use database_migration::targets::{FailurePolicy, TargetOutcome, TargetResult, TargetsConfig};
 use database_migration_files::MigrationDirectory;
+use database_migration_files::schema::SchemaFolder;
+use futures::StreamExt;
+use futures::stream;
 use indexmap::IndexMap;
+use std::borrow::Cow;
 use std::cmp::Reverse;
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 #[cfg(feature = "config")]
 use surrealdb_migrate_config::Settings;
+use surrealdb_migrate_db_client::schema::{
+    SchemaDiff, evaluate_schema_definitions, fetch_database_schema,
+};
 use surrealdb_migrate_db_client::{
-    DbConnection, apply_migration_in_transaction, delete_migration_execution,
-    find_max_applied_migration_key, insert_migration_execution, revert_migration_in_transaction,
-    select_all_executions, select_all_executions_sorted_by_key,
+    DbConnection, apply_migration_in_transaction, connect_to_database, delete_failed_attempts,
+    delete_migration_execution, find_max_applied_migration_key, insert_failed_attempt,
+    insert_migration_execution, list_databases_in_namespace, revert_migration_in_transaction,
+    run_callback_script, select_all_executions, select_all_executions_sorted_by_key,
+    select_failed_attempts, select_migration_history, upgrade_migrations_table,
+    use_namespace_and_database, use_tracking_database,
 };
 
 pub struct MigrationRunner {
     migrations_folder: PathBuf,
+    root_migrations_folder: Option<PathBuf>,
+    namespace_migrations_folder: Option<PathBuf>,
     excluded_files: ExcludedFiles,
     migrations_table: String,
     ignore_checksum: bool,
     ignore_order: bool,
+    schema_folder: PathBuf,
+    listeners: Vec<Box<dyn MigrationListener>>,
 }
 
 impl MigrationRunner {
     pub fn new(config: RunnerConfig<'_>) -> Self {
         Self {
             migrations_folder: config.migrations_folder.into(),
+            root_migrations_folder: config.root_migrations_folder.map(Into::into),
+            namespace_migrations_folder: config.namespace_migrations_folder.map(Into::into),
             excluded_files: config.excluded_files,
             migrations_table: config.migrations_table.into(),
             ignore_checksum: config.ignore_checksum,
             ignore_order: config.ignore_order,
+            schema_folder: config.schema_folder.into(),
+            listeners: Vec::new(),
         }
     }
 
+    /// Registers a listener that gets notified about the progress of the
+    /// migrate, revert and verify operations.
+    ///
+    /// Several listeners can be registered. They are notified in the order
+    /// they have been registered.
+    #[must_use]
+    pub fn with_listener(mut self, listener: impl MigrationListener + 'static) -> Self {
+        self.listeners.push(Box::new(listener));
+        self
+    }
+
     #[cfg(feature = "config")]
     pub fn with_settings(settings: &Settings) -> Self {
         Self::new(settings.runner_config())
     }
 
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(migrations_folder = %self.migrations_folder.display()),
+        err,
+    ))]
     pub fn list_defined_migrations<P>(&self, predicate: P) -> Result<Vec<Migration>, Error>
     where
         P: Fn(&MigrationKind) -> bool,
@@ -72,57 +117,236 @@ impl MigrationRunner {
         select_all_executions(&self.migrations_table, db).await
     }
 
-    pub async fn migrate(&self, db: &DbConnection) -> Result<Migrated, Error> {
-        let mig_dir =
-            MigrationDirectory::new(self.migrations_folder.as_path(), &self.excluded_files);
-        let mut migrations = mig_dir
-            .list_all_migrations()?
-            .filter(|maybe_mig| maybe_mig.as_ref().map_or(true, |mig| mig.kind.is_forward()))
-            .collect::<Result<Vec<_>, _>>()?;
-        if migrations.is_empty() {
-            return Ok(Migrated::NoForwardMigrationsFound);
+    /// Lists the failed attempts to apply migrations recorded in the migrations
+    /// table.
+    pub async fn list_failed_attempts(
+        &self,
+        db: &DbConnection,
+    ) -> Result<Vec<FailedAttempt>, Error> {
+        select_failed_attempts(&self.migrations_table, db).await
+    }
+
+    /// Removes all failed attempts from the migrations table.
+    ///
+    /// After the cause of a failed migration has been fixed, the failed attempt
+    /// can be cleared. Applying the migration successfully clears its failed
+    /// attempt as well.
+    pub async fn repair(&self, db: &DbConnection) -> Result<Repaired, Error> {
+        let cleared_keys = delete_failed_attempts(&self.migrations_table, db).await?;
+        if cleared_keys.is_empty() {
+            Ok(Repaired::Nothing)
+        } else {
+            Ok(Repaired::Cleared(cleared_keys))
         }
-        migrations.sort_unstable_by_key(|mig| mig.key);
+    }
 
-        self.migrate_list(mig_dir, migrations, db).await
+    /// Returns the timeline of all migrations applied to and reverted on the
+    /// database in the order they have been performed.
+    pub async fn history(&self, db: &DbConnection) -> Result<Vec<HistoryEvent>, Error> {
+        select_migration_history(&self.migrations_table, db).await
     }
 
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        skip_all,
+        fields(
+            migrations_folder = %self.migrations_folder.display(),
+            namespace = %db.namespace(),
+            database = %db.database(),
+        ),
+        err,
+    ))]
+    pub async fn migrate(&self, db: &DbConnection) -> Result<Migrated, Error> {
+        self.run(Operation::Migrate, target_of(db), self.migrate_all(db))
+            .await
+    }
+
+    async fn migrate_all(&self, db: &DbConnection) -> Result<Migrated, Error> {
+        for migration_set in self.scoped_migration_sets() {
+            self.migrate_set(&migration_set, None, db).await?;
+        }
+        self.migrate_set(&self.database_migration_set(), None, db)
+            .await
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        skip_all,
+        fields(
+            migrations_folder = %self.migrations_folder.display(),
+            namespace = %db.namespace(),
+            database = %db.database(),
+        ),
+        err,
+    ))]
     pub async fn migrate_to(
         &self,
         max_key: NaiveDateTime,
         db: &DbConnection,
     ) -> Result<Migrated, Error> {
-        let mig_dir =
-            MigrationDirectory::new(self.migrations_folder.as_path(), &self.excluded_files);
+        self.run(
+            Operation::Migrate,
+            target_of(db),
+            self.migrate_up_to(max_key, db),
+        )
+        .await
+    }
+
+    async fn migrate_up_to(
+        &self,
+        max_key: NaiveDateTime,
+        db: &DbConnection,
+    ) -> Result<Migrated, Error> {
+        for migration_set in self.scoped_migration_sets() {
+            self.migrate_set(&migration_set, Some(max_key), db).await?;
+        }
+        self.migrate_set(&self.database_migration_set(), Some(max_key), db)
+            .await
+    }
+
+    /// Applies the forward migrations of the given migration set.
+    ///
+    /// If `max_key` is given, only migrations up to this key are applied.
+    async fn migrate_set(
+        &self,
+        migration_set: &MigrationSet<'_>,
+        max_key: Option<NaiveDateTime>,
+        db: &DbConnection,
+    ) -> Result<Migrated, Error> {
+        let mig_dir = MigrationDirectory::new(migration_set.folder, &self.excluded_files);
         let mut migrations = mig_dir
             .list_all_migrations()?
             .filter(|maybe_mig| {
-                maybe_mig
-                    .as_ref()
-                    .map_or(true, |mig| mig.kind.is_forward() && mig.key <= max_key)
+                maybe_mig.as_ref().map_or(true, |mig| {
+                    mig.kind.is_forward() && max_key.is_none_or(|max_key| mig.key <= max_key)
+                })
             })
             .collect::<Result<Vec<_>, _>>()?;
+        if max_key.is_none() && migrations.is_empty() {
+            return Ok(Migrated::NoForwardMigrationsFound);
+        }
         migrations.sort_unstable_by_key(|mig| mig.key);
 
-        self.migrate_list(mig_dir, migrations, db).await
+        let target = target_of(db);
+        in_tracking_database(migration_set.scope, db, |db| async move {
+            self.migrate_list(mig_dir, migrations, &migration_set.table, target, &db)
+                .await
+        })
+        .await
+    }
+
+    /// Migrates several databases of one namespace with the same set of
+    /// migrations.
+    ///
+    /// The target databases are selected by the given targets config. If they
+    /// are selected by a pattern or all databases of the namespace shall be
+    /// migrated, the databases are enumerated using the given client config.
+    /// Each target database is migrated using its own connection, which is
+    /// derived from the given client config. Up to
+    /// [`TargetsConfig::concurrency`] databases are migrated concurrently.
+    ///
+    /// If `max_key` is given, the target databases are migrated up to the
+    /// migration with this key. Otherwise, all migrations are applied.
+    ///
+    /// Root and namespace level migrations are applied once before any target
+    /// database is migrated. They are tracked outside the target databases,
+    /// see [`MigrationScope::tracking_location`].
+    ///
+    /// Returns the result of each target database sorted by the name of the
+    /// database. An error is returned only if the root or namespace level
+    /// migrations fail or the target databases can not be determined.
+    pub async fn migrate_targets(
+        &self,
+        targets: &TargetsConfig,
+        max_key: Option<NaiveDateTime>,
+        db_config: &DbClientConfig<'_>,
+    ) -> Result<Vec<TargetResult<Migrated>>, Error> {
+        let scoped_migration_sets = self.scoped_migration_sets();
+        if !scoped_migration_sets.is_empty() {
+            let db = connect_to_database(db_config).await?;
+            self.run(Operation::Migrate, target_of(&db), async {
+                for migration_set in &scoped_migration_sets {
+                    self.migrate_set(migration_set, max_key, &db).await?;
+                }
+                Ok::<_, Error>(())
+            })
+            .await?;
+        }
+
+        let databases = if targets.databases.needs_enumeration() {
+            let db = connect_to_database(db_config).await?;
+            targets
+                .databases
+                .select(list_databases_in_namespace(&db).await?)
+        } else {
+            targets.databases.select([])
+        };
+
+        let stopped = AtomicBool::new(false);
+        let stopped = &stopped;
+        let mut results = stream::iter(databases)
+            .map(|database| async move {
+                if stopped.load(Ordering::Acquire) {
+                    return TargetResult {
+                        database,
+                        outcome: TargetOutcome::Skipped,
+                    };
+                }
+                let outcome = match self.migrate_target(&database, max_key, db_config).await {
+                    Ok(migrated) => TargetOutcome::Succeeded(migrated),
+                    Err(error) => {
+                        if targets.failure_policy == FailurePolicy::Stop {
+                            stopped.store(true, Ordering::Release);
+                        }
+                        TargetOutcome::Failed(error)
+                    },
+                };
+                TargetResult { database, outcome }
+            })
+            .buffer_unordered(targets.concurrency.max(1))
+            .collect::<Vec<_>>()
+            .await;
+        results.sort_unstable_by(|result1, result2| result1.database.cmp(&result2.database));
+        Ok(results)
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        skip(self, db_config),
+        fields(namespace = %db_config.namespace),
+        err,
+    ))]
+    async fn migrate_target(
+        &self,
+        database: &str,
+        max_key: Option<NaiveDateTime>,
+        db_config: &DbClientConfig<'_>,
+    ) -> Result<Migrated, Error> {
+        let db_config = db_config.clone().with_database(database.to_string());
+        let db = connect_to_database(&db_config).await?;
+        self.run(
+            Operation::Migrate,
+            target_of(&db),
+            self.migrate_set(&self.database_migration_set(), max_key, &db),
+        )
+        .await
     }
 
     async fn migrate_list(
         &self,
         mig_dir: MigrationDirectory<'_>,
         migration_list: Vec<Migration>,
+        migrations_table: &str,
+        target: Target<'_>,
         db: &DbConnection,
     ) -> Result<Migrated, Error> {
         let script_contents = mig_dir.read_script_content_for_migrations(&migration_list)?;
-        let existing_executions =
-            select_all_executions_sorted_by_key(&self.migrations_table, db).await?;
+        upgrade_migrations_table(migrations_table, db).await?;
+        let existing_executions = select_all_executions_sorted_by_key(migrations_table, db).await?;
         let executed_migrations = existing_executions
             .into_iter()
             .map(|exec| (exec.key, exec))
             .collect::<IndexMap<_, _>>();
         let mut migrations = migration_list
             .into_iter()
-            .map(|mig| (mig.key, mig))
+            .map(|mig| ((mig.key, mig.kind), mig))
             .collect::<HashMap<_, _>>();
 
         let verify = Verify::default()
@@ -131,19 +355,30 @@ impl MigrationRunner {
         let changed_after_execution =
             verify.list_changed_after_execution(&script_contents, &executed_migrations);
         if !changed_after_execution.is_empty() {
+            for problem in &changed_after_execution {
+                self.notify(&MigrationEvent::ProblemFound { target, problem });
+            }
             return Err(Error::ChangedAfterExecution(changed_after_execution));
         }
         let out_of_order = verify.list_out_of_order(&script_contents, &executed_migrations);
         if !out_of_order.is_empty() {
+            for problem in &out_of_order {
+                self.notify(&MigrationEvent::ProblemFound { target, problem });
+            }
             return Err(Error::OutOfOrder(out_of_order));
         }
 
         let migrate = Migrate::default();
         let to_apply = migrate.list_migrations_to_apply(&script_contents, &executed_migrations);
 
+        let before_each = mig_dir.read_callback_script(Callback::BeforeEachMigrate)?;
+        let after_each = mig_dir.read_callback_script(Callback::AfterEachMigrate)?;
+        let before_migrate = mig_dir.read_callback_script(Callback::BeforeMigrate)?;
+        run_callback(before_migrate.as_ref(), db).await?;
+
         let mut last_applied_migration = None;
         for migration in to_apply.values() {
-            let definition = migrations.remove(&migration.key).expect(
+            let definition = migrations.remove(&(migration.key, migration.kind)).expect(
                 "migration to be applied not found in migrations folder - should be unreachable - please report a bug",
             );
             let migration_applied = format!(
@@ -152,83 +387,224 @@ impl MigrationRunner {
                 &definition.title,
                 &migration.kind.as_str(),
             );
-            let execution = apply_migration_in_transaction(
-                migration,
-                db.username(),
-                &self.migrations_table,
-                db,
-            )
-            .await?;
-            insert_migration_execution(definition, execution, &self.migrations_table, db).await?;
+            run_callback(before_each.as_ref(), db).await?;
+            self.notify(&MigrationEvent::MigrationStarted {
+                target,
+                migration: &definition,
+            });
+            let execution = match self
+                .apply_migration(&definition, migration, migrations_table, db)
+                .await
+            {
+                Ok(execution) => execution,
+                Err(error) => {
+                    self.notify(&MigrationEvent::MigrationFailed {
+                        target,
+                        migration: &definition,
+                        error: &error,
+                    });
+                    return Err(error);
+                },
+            };
             last_applied_migration = Some(migration.key);
             log::info!("{migration_applied}");
+            self.notify(&MigrationEvent::MigrationApplied {
+                target,
+                migration: &definition,
+                execution: &execution,
+            });
+            run_callback(after_each.as_ref(), db).await?;
         }
+        let after_migrate = mig_dir.read_callback_script(Callback::AfterMigrate)?;
+        run_callback(after_migrate.as_ref(), db).await?;
 
         Ok(last_applied_migration.map_or(Migrated::Nothing, Migrated::UpTo))
     }
 
+    /// Applies the given migration and records its execution in the
+    /// migrations table.
+    ///
+    /// If the migration script fails, the failed attempt is recorded in the
+    /// migrations table.
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        skip_all,
+        fields(
+            key = %migration.key.format(MIGRATION_KEY_FORMAT_STR),
+            title = %definition.title,
+            kind = %migration.kind,
+            checksum = %migration.checksum,
+            execution_time_ms = tracing::field::Empty,
+        ),
+    ))]
+    async fn apply_migration(
+        &self,
+        definition: &Migration,
+        migration: &ApplicableMigration,
+        migrations_table: &str,
+        db: &DbConnection,
+    ) -> Result<Execution, Error> {
+        let attempted_at = Utc::now();
+        let execution =
+            match apply_migration_in_transaction(migration, db.username(), migrations_table, db)
+                .await
+            {
+                Ok(execution) => execution,
+                Err(Error::DbScript(script_error)) => {
+                    let attempt = FailedAttempt {
+                        key: migration.key,
+                        title: definition.title.clone(),
+                        kind: migration.kind,
+                        script_path: definition.script_path.clone(),
+                        checksum: migration.checksum,
+                        attempted_by: db.username().into(),
+                        attempted_at,
+                        errors: script_error
+                            .errors
+                            .iter()
+                            .map(|error| (error.number, error.message.clone()))
+                            .collect(),
+                    };
+                    if let Err(err) = insert_failed_attempt(attempt, migrations_table, db).await {
+                        log::error!(
+                            "failed to record the failed attempt to apply migration {}: {err}",
+                            migration.key
+                        );
+                    }
+                    return Err(Error::DbScript(script_error));
+                },
+                Err(err) => return Err(err),
+            };
+        #[cfg(feature = "tracing")]
+        record_execution_time(execution.execution_time);
+        insert_migration_execution(definition.clone(), execution.clone(), migrations_table, db)
+            .await?;
+        Ok(execution)
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        skip_all,
+        fields(
+            migrations_folder = %self.migrations_folder.display(),
+            namespace = %db.namespace(),
+            database = %db.database(),
+        ),
+        err,
+    ))]
     pub async fn revert(&self, db: &DbConnection) -> Result<Reverted, Error> {
-        let mig_dir =
-            MigrationDirectory::new(self.migrations_folder.as_path(), &self.excluded_files);
-        let mut migrations = mig_dir
-            .list_all_migrations()?
-            .filter(|maybe_mig| {
-                maybe_mig
-                    .as_ref()
-                    .map_or(true, |mig| mig.kind.is_backward())
-            })
-            .collect::<Result<Vec<_>, _>>()?;
-        if migrations.is_empty() {
-            return Ok(Reverted::NoBackwardMigrationsFound);
-        }
-        migrations.sort_unstable_by_key(|mig| Reverse(mig.key));
+        self.run(Operation::Revert, target_of(db), self.revert_all(db))
+            .await
+    }
 
-        self.revert_list(mig_dir, migrations, db).await
+    async fn revert_all(&self, db: &DbConnection) -> Result<Reverted, Error> {
+        let reverted = self
+            .revert_set(&self.database_migration_set(), None, db)
+            .await?;
+        for migration_set in self.scoped_migration_sets().iter().rev() {
+            self.revert_set(migration_set, None, db).await?;
+        }
+        Ok(reverted)
     }
 
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        skip_all,
+        fields(
+            migrations_folder = %self.migrations_folder.display(),
+            namespace = %db.namespace(),
+            database = %db.database(),
+        ),
+        err,
+    ))]
     pub async fn revert_to(
         &self,
         max_key: NaiveDateTime,
         db: &DbConnection,
     ) -> Result<Reverted, Error> {
-        let mig_dir =
-            MigrationDirectory::new(self.migrations_folder.as_path(), &self.excluded_files);
+        self.run(
+            Operation::Revert,
+            target_of(db),
+            self.revert_down_to(max_key, db),
+        )
+        .await
+    }
+
+    async fn revert_down_to(
+        &self,
+        max_key: NaiveDateTime,
+        db: &DbConnection,
+    ) -> Result<Reverted, Error> {
+        let reverted = self
+            .revert_set(&self.database_migration_set(), Some(max_key), db)
+            .await?;
+        for migration_set in self.scoped_migration_sets().iter().rev() {
+            self.revert_set(migration_set, Some(max_key), db).await?;
+        }
+        Ok(reverted)
+    }
+
+    /// Reverts the applied migrations of the given migration set.
+    ///
+    /// If `max_key` is given, only migrations with a key greater than this key
+    /// are reverted.
+    async fn revert_set(
+        &self,
+        migration_set: &MigrationSet<'_>,
+        max_key: Option<NaiveDateTime>,
+        db: &DbConnection,
+    ) -> Result<Reverted, Error> {
+        let mig_dir = MigrationDirectory::new(migration_set.folder, &self.excluded_files);
         let mut migrations = mig_dir
             .list_all_migrations()?
             .filter(|maybe_mig| {
-                maybe_mig
-                    .as_ref()
-                    .map_or(true, |mig| mig.kind.is_backward() && mig.key > max_key)
+                maybe_mig.as_ref().map_or(true, |mig| {
+                    mig.kind.is_baseline()
+                        || (mig.kind.is_backward()
+                            && max_key.is_none_or(|max_key| mig.key > max_key))
+                })
             })
             .collect::<Result<Vec<_>, _>>()?;
+        if max_key.is_none() && !migrations.iter().any(|mig| mig.kind.is_backward()) {
+            return Ok(Reverted::NoBackwardMigrationsFound);
+        }
         migrations.sort_unstable_by_key(|mig| Reverse(mig.key));
 
-        self.revert_list(mig_dir, migrations, db).await
+        let target = target_of(db);
+        in_tracking_database(migration_set.scope, db, |db| async move {
+            self.revert_list(mig_dir, migrations, &migration_set.table, target, &db)
+                .await
+        })
+        .await
     }
 
     async fn revert_list(
         &self,
         mig_dir: MigrationDirectory<'_>,
         migration_list: Vec<Migration>,
+        migrations_table: &str,
+        target: Target<'_>,
         db: &DbConnection,
     ) -> Result<Reverted, Error> {
         let script_contents = mig_dir.read_script_content_for_migrations(&migration_list)?;
-        let existing_executions =
-            select_all_executions_sorted_by_key(&self.migrations_table, db).await?;
+        upgrade_migrations_table(migrations_table, db).await?;
+        let existing_executions = select_all_executions_sorted_by_key(migrations_table, db).await?;
         let executed_migrations = existing_executions
             .into_iter()
             .map(|exec| (exec.key, exec))
             .collect::<IndexMap<_, _>>();
         let mut migrations = migration_list
             .into_iter()
-            .map(|mig| (mig.key, mig))
+            .map(|mig| ((mig.key, mig.kind), mig))
             .collect::<HashMap<_, _>>();
 
         let revert = Revert::default();
         let to_apply = revert.list_migrations_to_apply(&script_contents, &executed_migrations);
 
+        let before_each = mig_dir.read_callback_script(Callback::BeforeEachRevert)?;
+        let after_each = mig_dir.read_callback_script(Callback::AfterEachRevert)?;
+        let before_revert = mig_dir.read_callback_script(Callback::BeforeRevert)?;
+        run_callback(before_revert.as_ref(), db).await?;
+
         for migration in to_apply.values() {
-            let definition = migrations.remove(&migration.key).expect(
+            let definition = migrations.remove(&(migration.key, migration.kind)).expect(
                 "down migration to be applied not found in migrations folder - should be unreachable - please report a bug",
             );
             let migration_reverted = format!(
@@ -237,12 +613,36 @@ impl MigrationRunner {
                 &definition.title,
                 &migration.kind.as_str(),
             );
-            let reversion = revert_migration_in_transaction(migration, db.username(), db).await?;
-            delete_migration_execution(reversion, &self.migrations_table, db).await?;
+            run_callback(before_each.as_ref(), db).await?;
+            self.notify(&MigrationEvent::MigrationStarted {
+                target,
+                migration: &definition,
+            });
+            let reversion = match self
+                .revert_migration(&definition, migration, migrations_table, db)
+                .await
+            {
+                Ok(reversion) => reversion,
+                Err(error) => {
+                    self.notify(&MigrationEvent::MigrationFailed {
+                        target,
+                        migration: &definition,
+                        error: &error,
+                    });
+                    return Err(error);
+                },
+            };
             log::info!("{migration_reverted}");
+            self.notify(&MigrationEvent::MigrationReverted {
+                target,
+                migration: &definition,
+                reversion: &reversion,
+            });
+            run_callback(after_each.as_ref(), db).await?;
         }
-        let max_remaining_migration =
-            find_max_applied_migration_key(&self.migrations_table, db).await?;
+        let after_revert = mig_dir.read_callback_script(Callback::AfterRevert)?;
+        run_callback(after_revert.as_ref(), db).await?;
+        let max_remaining_migration = find_max_applied_migration_key(migrations_table, db).await?;
 
         let completely_or_nothing = || {
             if executed_migrations.is_empty() {
@@ -255,14 +655,233 @@ impl MigrationRunner {
         Ok(max_remaining_migration.map_or_else(completely_or_nothing, Reverted::DownTo))
     }
 
+    /// Reverts the given migration and removes its execution from the
+    /// migrations table.
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        skip_all,
+        fields(
+            key = %migration.key.format(MIGRATION_KEY_FORMAT_STR),
+            title = %definition.title,
+            kind = %migration.kind,
+            checksum = %migration.checksum,
+            execution_time_ms = tracing::field::Empty,
+        ),
+    ))]
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    async fn revert_migration(
+        &self,
+        definition: &Migration,
+        migration: &ApplicableMigration,
+        migrations_table: &str,
+        db: &DbConnection,
+    ) -> Result<Reversion, Error> {
+        let reversion = revert_migration_in_transaction(migration, db.username(), db).await?;
+        #[cfg(feature = "tracing")]
+        record_execution_time(reversion.execution_time);
+        delete_migration_execution(reversion.clone(), migrations_table, db).await?;
+        Ok(reversion)
+    }
+
+    /// Returns the migration sets of the root and the namespace scope that
+    /// have a migrations folder configured in the order they are applied.
+    fn scoped_migration_sets(&self) -> Vec<MigrationSet<'_>> {
+        [
+            (MigrationScope::Root, self.root_migrations_folder.as_deref()),
+            (
+                MigrationScope::Namespace,
+                self.namespace_migrations_folder.as_deref(),
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(scope, folder)| {
+            folder.map(|folder| MigrationSet {
+                folder,
+                scope,
+                table: scope.migrations_table(&self.migrations_table),
+            })
+        })
+        .collect()
+    }
+
+    fn database_migration_set(&self) -> MigrationSet<'_> {
+        MigrationSet {
+            folder: &self.migrations_folder,
+            scope: MigrationScope::Database,
+            table: MigrationScope::Database.migrations_table(&self.migrations_table),
+        }
+    }
+
+    /// Performs a run of the given operation and notifies the registered
+    /// listeners about the start and the end of the run.
+    async fn run<T>(
+        &self,
+        operation: Operation,
+        target: Target<'_>,
+        run: impl Future<Output = Result<T, Error>>,
+    ) -> Result<T, Error> {
+        self.notify(&MigrationEvent::RunStarted { target, operation });
+        let result = run.await;
+        self.notify(&MigrationEvent::RunFinished {
+            target,
+            operation,
+            error: result.as_ref().err(),
+        });
+        result
+    }
+
+    fn notify(&self, event: &MigrationEvent<'_>) {
+        for listener in &self.listeners {
+            listener.on_event(event);
+        }
+    }
+
+    /// Creates a baseline migration from the current schema of the database.
+    ///
+    /// The database must be migrated exactly up to the migration with the
+    /// given key. The baseline gets the same key, so that fresh databases
+    /// apply the baseline instead of all migrations up to this key, while
+    /// databases that have been migrated already skip the baseline.
+    ///
+    /// Only the schema is squashed into the baseline. Data inserted or updated
+    /// by the squashed migrations is not part of the baseline.
+    pub async fn squash(
+        &self,
+        key: NaiveDateTime,
+        title: &str,
+        db: &DbConnection,
+    ) -> Result<Migration, Error> {
+        let max_applied_key = find_max_applied_migration_key(&self.migrations_table, db).await?;
+        if max_applied_key != Some(key) {
+            return Err(Error::NotMigratedUpTo(
+                key.format(MIGRATION_KEY_FORMAT_STR).to_string(),
+            ));
+        }
+        let schema = fetch_database_schema(&self.migrations_table, db).await?;
+
+        let mig_dir =
+            MigrationDirectory::new(self.migrations_folder.as_path(), &self.excluded_files);
+        mig_dir
+            .files(MigrationFilenameStrategy::default())
+            .create_new_migration_with_content(
+                NewMigration {
+                    key,
+                    title: title.into(),
+                    kind: MigrationKind::Baseline,
+                },
+                &schema.to_script(),
+            )
+    }
+
+    /// Creates a new migration from the differences between the schemas of
+    /// two databases.
+    ///
+    /// The forward migration changes the schema of the `target` database into
+    /// the schema of the `source` database. The backward migration reverts
+    /// these changes.
+    pub async fn diff(
+        &self,
+        key: NaiveDateTime,
+        title: &str,
+        source: &DbConnection,
+        target: &DbConnection,
+    ) -> Result<Diffed, Error> {
+        let source_schema = fetch_database_schema(&self.migrations_table, source).await?;
+        let target_schema = fetch_database_schema(&self.migrations_table, target).await?;
+        let diff = SchemaDiff::between(&source_schema, &target_schema);
+        self.create_migration_from_diff(key, title, &diff)
+    }
+
+    /// Compares the desired schema defined by the files in the schema folder
+    /// with the current schema of the database.
+    ///
+    /// The schema definition files are evaluated in the given `scratch`
+    /// database, which must be empty. The returned diff holds the statements
+    /// that migrate the database to the desired schema and the statements
+    /// that revert this change.
+    pub async fn plan_schema(
+        &self,
+        scratch: &DbConnection,
+        db: &DbConnection,
+    ) -> Result<SchemaDiff, Error> {
+        let definitions = SchemaFolder::new(&self.schema_folder).read_definitions()?;
+        let script = definitions
+            .iter()
+            .map(|definition| definition.content.as_str())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let desired_schema =
+            evaluate_schema_definitions(&script, &self.migrations_table, scratch).await?;
+        let current_schema = fetch_database_schema(&self.migrations_table, db).await?;
+        Ok(SchemaDiff::between(&desired_schema, &current_schema))
+    }
+
+    /// Creates a new forward migration and the related backward migration from
+    /// the given schema diff.
+    ///
+    /// No migration is created if the diff is empty.
+    pub fn create_migration_from_diff(
+        &self,
+        key: NaiveDateTime,
+        title: &str,
+        diff: &SchemaDiff,
+    ) -> Result<Diffed, Error> {
+        if diff.is_empty() {
+            return Ok(Diffed::NoDifferences);
+        }
+
+        let mig_dir =
+            MigrationDirectory::new(self.migrations_folder.as_path(), &self.excluded_files);
+        mig_dir.create_directory_if_not_existing()?;
+        let mig_files = mig_dir.files(MigrationFilenameStrategy::default());
+        let up = mig_files.create_new_migration_with_content(
+            NewMigration {
+                key,
+                title: title.into(),
+                kind: MigrationKind::Up,
+            },
+            &diff.up_script(),
+        )?;
+        let down = mig_files.create_new_migration_with_content(
+            NewMigration {
+                key,
+                title: title.into(),
+                kind: MigrationKind::Down,
+            },
+            &diff.down_script(),
+        )?;
+        Ok(Diffed::Created { up, down })
+    }
+
     pub async fn verify(&self, db: &DbConnection) -> Result<Verified, Error> {
         self.verify_checks(Checks::all(), db).await
     }
 
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        skip_all,
+        fields(
+            migrations_folder = %self.migrations_folder.display(),
+            namespace = %db.namespace(),
+            database = %db.database(),
+        ),
+        err,
+    ))]
     pub async fn verify_checks(
         &self,
         checks: Checks,
         db: &DbConnection,
+    ) -> Result<Verified, Error> {
+        self.run(
+            Operation::Verify,
+            target_of(db),
+            self.verify_with_checks(checks, db),
+        )
+        .await
+    }
+
+    async fn verify_with_checks(
+        &self,
+        checks: Checks,
+        db: &DbConnection,
     ) -> Result<Verified, Error> {
         let mig_dir =
             MigrationDirectory::new(self.migrations_folder.as_path(), &self.excluded_files);
@@ -283,14 +902,26 @@ impl MigrationRunner {
             .map(|exec| (exec.key, exec))
             .collect::<IndexMap<_, _>>();
 
+        let target = target_of(db);
         let verify = Verify::from(checks);
         let out_of_order_migrations =
             verify.list_out_of_order(&script_contents, &executed_migrations);
         let changed_migrations =
             verify.list_changed_after_execution(&script_contents, &executed_migrations);
 
+        let failed_migrations = select_failed_attempts(&self.migrations_table, db).await?;
+
         let mut problematic_migrations = out_of_order_migrations;
         problematic_migrations.extend(changed_migrations);
+        problematic_migrations.extend(
+            failed_migrations
+                .into_iter()
+                .map(ProblematicMigration::from),
+        );
+
+        for problem in &problematic_migrations {
+            self.notify(&MigrationEvent::ProblemFound { target, problem });
+        }
 
         if problematic_migrations.is_empty() {
             Ok(Verified::NoProblemsFound)
@@ -300,5 +931,72 @@ impl MigrationRunner {
     }
 }
 
+/// Returns the namespace and database the given connection is using as the
+/// target of migration events.
+fn target_of(db: &DbConnection) -> Target<'_> {
+    Target {
+        namespace: db.namespace(),
+        database: db.database(),
+    }
+}
+
+/// Records the execution time of a migration on the current span.
+#[cfg(feature = "tracing")]
+fn record_execution_time(execution_time: std::time::Duration) {
+    tracing::Span::current().record(
+        "execution_time_ms",
+        u64::try_from(execution_time.as_millis()).unwrap_or(u64::MAX),
+    );
+}
+
+/// The migrations of one scope together with the table they are tracked in.
+struct MigrationSet<'a> {
+    folder: &'a Path,
+    scope: MigrationScope,
+    table: Cow<'a, str>,
+}
+
+/// Performs the given operation on the database that tracks the migrations of
+/// the given scope.
+///
+/// Afterward, the namespace and database of the given connection are selected
+/// again, even if the operation failed.
+async fn in_tracking_database<T, F>(
+    scope: MigrationScope,
+    db: &DbConnection,
+    operation: impl FnOnce(DbConnection) -> F,
+) -> Result<T, Error>
+where
+    F: Future<Output = Result<T, Error>>,
+{
+    if scope == MigrationScope::Database {
+        return operation(db.clone()).await;
+    }
+    let result = match use_tracking_database(scope, db).await {
+        Ok(tracking_db) => operation(tracking_db).await,
+        Err(err) => Err(err),
+    };
+    let restored = use_namespace_and_database(db.namespace(), db.database(), db).await;
+    let value = result?;
+    restored?;
+    Ok(value)
+}
+
+/// Executes the given callback script, if any, and logs its output.
+async fn run_callback(
+    callback_script: Option<&CallbackScript>,
+    db: &DbConnection,
+) -> Result<(), Error> {
+    let Some(callback_script) = callback_script else {
+        return Ok(());
+    };
+    let outputs = run_callback_script(callback_script, db).await?;
+    log::info!("{}: executed", callback_script.callback);
+    for (number, output) in (1..).zip(outputs) {
+        log::info!("{}: statement {number}: {output}", callback_script.callback);
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests;
@@ -35,6 +35,15 @@ mod migration_runner {
         assert_that!(migration_runner.ignore_order).is_equal_to(false);
     }
 
+    #[test]
+    fn listeners_can_be_registered() {
+        let runner = MigrationRunner::new(RunnerConfig::default())
+            .with_listener(|_: &MigrationEvent<'_>| {})
+            .with_listener(|_: &MigrationEvent<'_>| {});
+
+        assert_that!(runner.listeners.len()).is_equal_to(2);
+    }
+
     #[test]
     fn list_defined_forward_migrations_in_basic_fixture() {
         let config = RunnerConfig::default()
@@ -0,0 +1,67 @@
+mod fixtures;
+
+use assert_fs::TempDir;
+use asserting::prelude::*;
+use std::path::Path;
+use surrealdb_migrate::config::{DbClientConfig, RunnerConfig};
+use surrealdb_migrate::db_client::connect_to_database;
+use surrealdb_migrate::result::Migrated;
+use surrealdb_migrate::runner::MigrationRunner;
+use surrealdb_migrate::test_dsl::key;
+
+#[tokio::test]
+async fn migrate_in_memory_database() {
+    let db_config = DbClientConfig::default()
+        .with_address("mem://")
+        .with_namespace("playground")
+        .with_database("test");
+    let db = connect_to_database(&db_config)
+        .await
+        .expect("failed to connect to in-memory database");
+
+    let config =
+        RunnerConfig::default().with_migrations_folder(Path::new("../fixtures/basic/migrations"));
+    let runner = MigrationRunner::new(config);
+
+    let migrated = runner
+        .migrate(&db)
+        .await
+        .unwrap_or_else(|err| panic!("failed to run migrations: {err}"));
+
+    assert_that!(migrated).is_equal_to(Migrated::UpTo(key("20250103_140521")));
+}
+
+#[tokio::test]
+async fn migrate_surrealkv_database() {
+    let temp_dir = TempDir::new().expect("failed to create temporary directory");
+    let db_path = temp_dir.path().join("db");
+    let db_config = DbClientConfig::default()
+        .with_address(format!("surrealkv://{}", db_path.display()))
+        .with_namespace("playground")
+        .with_database("test");
+    let db = connect_to_database(&db_config)
+        .await
+        .expect("failed to open surrealkv database");
+
+    let config =
+        RunnerConfig::default().with_migrations_folder(Path::new("../fixtures/basic/migrations"));
+    let runner = MigrationRunner::new(config);
+
+    let migrated = runner
+        .migrate(&db)
+        .await
+        .unwrap_or_else(|err| panic!("failed to run migrations: {err}"));
+
+    assert_that!(migrated).is_equal_to(Migrated::UpTo(key("20250103_140521")));
+
+    let applied_keys = runner
+        .list_applied_migrations(&db)
+        .await
+        .unwrap_or_else(|err| panic!("failed to list applied migrations: {err}"))
+        .into_iter()
+        .map(|exec| exec.key)
+        .collect::<Vec<_>>();
+
+    assert_that!(applied_keys).contains_exactly([key("20250103_140520"), key("20250103_140521")]);
+    assert_that!(db_path.is_dir()).is_true();
+}
@@ -16,10 +16,13 @@ mod dummy_extern_uses {
     use color_eyre as _;
     use database_migration as _;
     use database_migration_files as _;
+    use futures as _;
     use indexmap as _;
     use log as _;
     #[cfg(feature = "config")]
     use surrealdb_migrate_config as _;
     use surrealdb_migrate_db_client as _;
+    #[cfg(feature = "tracing")]
+    use tracing as _;
     use version_sync as _;
 }
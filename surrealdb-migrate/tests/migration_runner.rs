@@ -1,7 +1,8 @@
 mod fixtures;
 
 use crate::fixtures::db::{
-    connect_to_test_database_as_database_user, get_db_tables_info, prepare_test_database,
+    connect_as_root_user, connect_to_test_database_as_database_user, db_username,
+    get_db_tables_info, ns_password, ns_username, prepare_test_database,
     start_surrealdb_testcontainer,
 };
 use assert_fs::TempDir;
@@ -12,15 +13,25 @@ use std::fs;
 use std::fs::File;
 use std::io::read_to_string;
 use std::path::Path;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use surrealdb_migrate::checksum::hash_migration_script;
-use surrealdb_migrate::config::DEFAULT_MIGRATIONS_TABLE;
-use surrealdb_migrate::config::RunnerConfig;
-use surrealdb_migrate::migration::{Execution, Migration, MigrationKind, Problem};
-use surrealdb_migrate::result::{Migrated, Reverted, Verified};
+use surrealdb_migrate::config::{DEFAULT_MIGRATIONS_TABLE, MIGRATION_KEY_FORMAT_STR};
+use surrealdb_migrate::config::{DbAuthLevel, DbClientConfig, RunnerConfig};
+use surrealdb_migrate::error::Error;
+use surrealdb_migrate::event::MigrationEvent;
+use surrealdb_migrate::migration::{Execution, HistoryAction, Migration, MigrationKind, Problem};
+use surrealdb_migrate::result::{Diffed, Migrated, Repaired, Reverted, Verified};
 use surrealdb_migrate::runner::MigrationRunner;
+use surrealdb_migrate::scope::MigrationScope;
+use surrealdb_migrate::targets::{
+    FailurePolicy, TargetDatabases, TargetOutcome, TargetResult, TargetsConfig,
+};
 use surrealdb_migrate::test_dsl::{datetime, key};
-use surrealdb_migrate_db_client::insert_migration_execution;
+use surrealdb_migrate_db_client::{
+    DbConnection, connect_to_database, insert_migration_execution,
+    select_all_executions_sorted_by_key, use_namespace_and_database, use_tracking_database,
+};
 
 #[tokio::test]
 async fn list_applied_migrations_from_an_empty_database() {
@@ -133,7 +144,11 @@ async fn run_migrations_on_empty_db() {
 
     let tables_info = get_db_tables_info(&db).await;
 
-    assert_that!(tables_info.keys()).contains_exactly_in_any_order(["migrations", "quote"]);
+    assert_that!(tables_info.keys()).contains_exactly_in_any_order([
+        "migrations",
+        "migrations_history",
+        "quote",
+    ]);
 
     let quotes: Vec<HashMap<String, String>> = db
         .query("SELECT text FROM quote ORDER BY text")
@@ -166,8 +181,11 @@ async fn run_migrations_on_fully_migrated_db() {
 
     let tables_info = get_db_tables_info(&db).await;
 
-    assert_that!(tables_info.keys())
-        .contains_exactly_in_any_order([DEFAULT_MIGRATIONS_TABLE, "quote"]);
+    assert_that!(tables_info.keys()).contains_exactly_in_any_order([
+        DEFAULT_MIGRATIONS_TABLE,
+        "migrations_history",
+        "quote",
+    ]);
 
     let migrated = runner.migrate(&db).await.expect("failed to run migrations");
 
@@ -175,7 +193,11 @@ async fn run_migrations_on_fully_migrated_db() {
 
     let tables_info = get_db_tables_info(&db).await;
 
-    assert_that!(tables_info.keys()).contains_exactly_in_any_order(["migrations", "quote"]);
+    assert_that!(tables_info.keys()).contains_exactly_in_any_order([
+        "migrations",
+        "migrations_history",
+        "quote",
+    ]);
 
     let quotes: Vec<HashMap<String, String>> = db
         .query("SELECT text FROM quote ORDER BY text")
@@ -213,7 +235,11 @@ async fn migrate_an_empty_db_up_to_migration_20250103_140520() {
 
     let tables_info = get_db_tables_info(&db).await;
 
-    assert_that!(tables_info.keys()).contains_exactly_in_any_order(["migrations", "quote"]);
+    assert_that!(tables_info.keys()).contains_exactly_in_any_order([
+        "migrations",
+        "migrations_history",
+        "quote",
+    ]);
 
     let quotes: Vec<HashMap<String, String>> = db
         .query("SELECT text FROM quote ORDER BY text")
@@ -239,8 +265,11 @@ async fn revert_migrations_on_fully_migrated_db() {
 
     let tables_info = get_db_tables_info(&db).await;
 
-    assert_that!(tables_info.keys())
-        .contains_exactly_in_any_order([DEFAULT_MIGRATIONS_TABLE, "quote"]);
+    assert_that!(tables_info.keys()).contains_exactly_in_any_order([
+        DEFAULT_MIGRATIONS_TABLE,
+        "migrations_history",
+        "quote",
+    ]);
 
     let reverted = runner
         .revert(&db)
@@ -251,7 +280,8 @@ async fn revert_migrations_on_fully_migrated_db() {
 
     let tables_info = get_db_tables_info(&db).await;
 
-    assert_that!(tables_info.keys()).contains_exactly_in_any_order([DEFAULT_MIGRATIONS_TABLE]);
+    assert_that!(tables_info.keys())
+        .contains_exactly_in_any_order([DEFAULT_MIGRATIONS_TABLE, "migrations_history"]);
 }
 
 #[tokio::test]
@@ -294,8 +324,11 @@ async fn revert_a_fully_migrated_db_down_to_migration_20250103_140520() {
 
     let tables_info = get_db_tables_info(&db).await;
 
-    assert_that!(tables_info.keys())
-        .contains_exactly_in_any_order([DEFAULT_MIGRATIONS_TABLE, "quote"]);
+    assert_that!(tables_info.keys()).contains_exactly_in_any_order([
+        DEFAULT_MIGRATIONS_TABLE,
+        "migrations_history",
+        "quote",
+    ]);
 
     let reverted = runner
         .revert_to(key("20250103_140520"), &db)
@@ -306,8 +339,609 @@ async fn revert_a_fully_migrated_db_down_to_migration_20250103_140520() {
 
     let tables_info = get_db_tables_info(&db).await;
 
-    assert_that!(tables_info.keys())
-        .contains_exactly_in_any_order([DEFAULT_MIGRATIONS_TABLE, "quote"]);
+    assert_that!(tables_info.keys()).contains_exactly_in_any_order([
+        DEFAULT_MIGRATIONS_TABLE,
+        "migrations_history",
+        "quote",
+    ]);
+}
+
+#[tokio::test]
+async fn migrate_and_revert_run_callback_scripts() {
+    let db_server = start_surrealdb_testcontainer().await;
+    let db_config = prepare_test_database(&db_server).await;
+    let db = connect_to_test_database_as_database_user(&db_config).await;
+
+    let config = RunnerConfig::default()
+        .with_migrations_folder(Path::new("../fixtures/with_callbacks/migrations"));
+    let runner = MigrationRunner::new(config);
+
+    let migrated = runner
+        .migrate(&db)
+        .await
+        .unwrap_or_else(|err| panic!("failed to run migrations: {err}"));
+
+    assert_that!(migrated).is_equal_to(Migrated::UpTo(key("20250103_141521")));
+
+    let reverted = runner
+        .revert(&db)
+        .await
+        .unwrap_or_else(|err| panic!("failed to revert migrations: {err}"));
+
+    assert_that!(reverted).is_equal_to(Reverted::Completely);
+
+    let executed_callbacks: Vec<String> = db
+        .query("RETURN (SELECT callback, at FROM audit ORDER BY at).callback")
+        .await
+        .unwrap_or_else(|err| panic!("failed to select audit entries: {err}"))
+        .take(0)
+        .unwrap_or_else(|err| panic!("failed to select audit entries: {err}"));
+
+    assert_that!(executed_callbacks).is_equal_to(vec![
+        "before_migrate".to_string(),
+        "after_each_migrate".into(),
+        "after_each_migrate".into(),
+        "after_migrate".into(),
+        "before_revert".into(),
+        "after_revert".into(),
+    ]);
+}
+
+fn write_scoped_migrations(migrations_folder: &Path) {
+    let scripts = [
+        (
+            "root/20250101_000000_define_tenants_namespace",
+            "DEFINE NAMESPACE tenants;",
+            "REMOVE NAMESPACE tenants;",
+        ),
+        (
+            "namespace/20250101_000100_define_shop_database",
+            "DEFINE DATABASE shop;",
+            "REMOVE DATABASE shop;",
+        ),
+        (
+            "database/20250101_000200_define_quote_table",
+            "DEFINE TABLE quote SCHEMALESS;",
+            "REMOVE TABLE quote;",
+        ),
+    ];
+    for (name, up_script, down_script) in scripts {
+        let path = migrations_folder.join(name);
+        fs::create_dir_all(path.parent().expect("script path without parent"))
+            .unwrap_or_else(|err| panic!("could not create migrations folder: {err}"));
+        fs::write(path.with_extension("up.surql"), up_script)
+            .unwrap_or_else(|err| panic!("could not write migration script: {err}"));
+        fs::write(path.with_extension("down.surql"), down_script)
+            .unwrap_or_else(|err| panic!("could not write migration script: {err}"));
+    }
+}
+
+async fn applied_keys(migrations_table: &str, db: &DbConnection) -> Vec<String> {
+    select_all_executions_sorted_by_key(migrations_table, db)
+        .await
+        .unwrap_or_else(|err| panic!("failed to select executions: {err}"))
+        .into_iter()
+        .map(|execution| execution.key.format(MIGRATION_KEY_FORMAT_STR).to_string())
+        .collect()
+}
+
+async fn applied_keys_of_scope(scope: MigrationScope, db: &DbConnection) -> Vec<String> {
+    let tracking_db = use_tracking_database(scope, db)
+        .await
+        .unwrap_or_else(|err| panic!("failed to use tracking database: {err}"));
+    let keys = applied_keys(
+        &scope.migrations_table(DEFAULT_MIGRATIONS_TABLE),
+        &tracking_db,
+    )
+    .await;
+    use_namespace_and_database(db.namespace(), db.database(), db)
+        .await
+        .unwrap_or_else(|err| panic!("failed to use migrated database: {err}"));
+    keys
+}
+
+#[tokio::test]
+async fn migrate_and_revert_root_namespace_and_database_migrations() {
+    let db_server = start_surrealdb_testcontainer().await;
+    let db_config = prepare_test_database(&db_server).await;
+    let db = connect_as_root_user(&db_config).await;
+
+    let temp_dir = TempDir::new().expect("failed to create temp dir");
+    let migrations_folder = temp_dir.path();
+    write_scoped_migrations(migrations_folder);
+
+    let config = RunnerConfig::default()
+        .with_root_migrations_folder(migrations_folder.join("root"))
+        .with_namespace_migrations_folder(migrations_folder.join("namespace"))
+        .with_migrations_folder(migrations_folder.join("database"));
+    let runner = MigrationRunner::new(config);
+
+    let migrated = runner
+        .migrate(&db)
+        .await
+        .unwrap_or_else(|err| panic!("failed to run migrations: {err}"));
+
+    assert_that!(migrated).is_equal_to(Migrated::UpTo(key("20250101_000200")));
+    assert_that!(applied_keys_of_scope(MigrationScope::Root, &db).await)
+        .is_equal_to(vec!["20250101_000000".to_string()]);
+    assert_that!(applied_keys_of_scope(MigrationScope::Namespace, &db).await)
+        .is_equal_to(vec!["20250101_000100".to_string()]);
+    assert_that!(applied_keys(DEFAULT_MIGRATIONS_TABLE, &db).await)
+        .is_equal_to(vec!["20250101_000200".to_string()]);
+
+    let namespaces: Option<HashMap<String, String>> = db
+        .query("RETURN (INFO FOR ROOT).namespaces")
+        .await
+        .unwrap_or_else(|err| panic!("failed to get info for root: {err}"))
+        .take(0)
+        .unwrap_or_else(|err| panic!("failed to get info for root: {err}"));
+    let databases: Option<HashMap<String, String>> = db
+        .query("RETURN (INFO FOR NS).databases")
+        .await
+        .unwrap_or_else(|err| panic!("failed to get info for namespace: {err}"))
+        .take(0)
+        .unwrap_or_else(|err| panic!("failed to get info for namespace: {err}"));
+
+    assert_that!(namespaces.is_some_and(|namespaces| namespaces.contains_key("tenants"))).is_true();
+    assert_that!(databases.is_some_and(|databases| databases.contains_key("shop"))).is_true();
+
+    let reverted = runner
+        .revert(&db)
+        .await
+        .unwrap_or_else(|err| panic!("failed to revert migrations: {err}"));
+
+    assert_that!(reverted).is_equal_to(Reverted::Completely);
+    assert_that!(applied_keys_of_scope(MigrationScope::Root, &db).await).is_empty();
+    assert_that!(applied_keys_of_scope(MigrationScope::Namespace, &db).await).is_empty();
+    assert_that!(applied_keys(DEFAULT_MIGRATIONS_TABLE, &db).await).is_empty();
+
+    let namespaces: Option<HashMap<String, String>> = db
+        .query("RETURN (INFO FOR ROOT).namespaces")
+        .await
+        .unwrap_or_else(|err| panic!("failed to get info for root: {err}"))
+        .take(0)
+        .unwrap_or_else(|err| panic!("failed to get info for root: {err}"));
+
+    assert_that!(namespaces.is_some_and(|namespaces| namespaces.contains_key("tenants")))
+        .is_false();
+}
+
+#[tokio::test]
+async fn migrate_second_database_does_not_apply_root_and_namespace_migrations_again() {
+    let db_server = start_surrealdb_testcontainer().await;
+    let db_config = prepare_test_database(&db_server).await;
+    let db = connect_as_root_user(&db_config).await;
+
+    let temp_dir = TempDir::new().expect("failed to create temp dir");
+    let migrations_folder = temp_dir.path();
+    write_scoped_migrations(migrations_folder);
+
+    let config = RunnerConfig::default()
+        .with_root_migrations_folder(migrations_folder.join("root"))
+        .with_namespace_migrations_folder(migrations_folder.join("namespace"))
+        .with_migrations_folder(migrations_folder.join("database"));
+    let runner = MigrationRunner::new(config);
+
+    runner
+        .migrate(&db)
+        .await
+        .unwrap_or_else(|err| panic!("failed to migrate first database: {err}"));
+
+    let second_db = use_namespace_and_database(db.namespace(), "second", &db)
+        .await
+        .unwrap_or_else(|err| panic!("failed to use second database: {err}"));
+
+    let migrated = runner
+        .migrate(&second_db)
+        .await
+        .unwrap_or_else(|err| panic!("failed to migrate second database: {err}"));
+
+    assert_that!(migrated).is_equal_to(Migrated::UpTo(key("20250101_000200")));
+    assert_that!(applied_keys_of_scope(MigrationScope::Root, &second_db).await)
+        .is_equal_to(vec!["20250101_000000".to_string()]);
+    assert_that!(applied_keys_of_scope(MigrationScope::Namespace, &second_db).await)
+        .is_equal_to(vec!["20250101_000100".to_string()]);
+    assert_that!(applied_keys(DEFAULT_MIGRATIONS_TABLE, &second_db).await)
+        .is_equal_to(vec!["20250101_000200".to_string()]);
+    let mut tables = get_db_tables_info(&second_db)
+        .await
+        .into_keys()
+        .collect::<Vec<_>>();
+    tables.sort();
+    assert_that!(tables).is_equal_to(vec![
+        "migrations".to_string(),
+        "migrations_history".to_string(),
+        "quote".to_string(),
+    ]);
+}
+
+#[tokio::test]
+async fn migrate_and_revert_notify_registered_listener() {
+    let db_server = start_surrealdb_testcontainer().await;
+    let db_config = prepare_test_database(&db_server).await;
+    let db = connect_to_test_database_as_database_user(&db_config).await;
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let recorded_events = Arc::clone(&events);
+    let config = RunnerConfig::default()
+        .with_migrations_folder(Path::new("../fixtures/with_down_migrations/migrations"));
+    let runner = MigrationRunner::new(config).with_listener(move |event: &MigrationEvent<'_>| {
+        let event = match event {
+            MigrationEvent::RunStarted { operation, .. } => format!("{operation} started"),
+            MigrationEvent::MigrationStarted { migration, .. } => {
+                format!("{} {} started", migration.key, migration.kind)
+            },
+            MigrationEvent::MigrationApplied {
+                migration,
+                execution,
+                ..
+            } => format!(
+                "{} {} applied with rank {}",
+                migration.key, migration.kind, execution.applied_rank
+            ),
+            MigrationEvent::MigrationFailed { migration, .. } => {
+                format!("{} {} failed", migration.key, migration.kind)
+            },
+            MigrationEvent::MigrationReverted { reversion, .. } => {
+                format!("{} reverted", reversion.key)
+            },
+            MigrationEvent::ProblemFound { problem, .. } => {
+                format!("{} has a problem", problem.key)
+            },
+            MigrationEvent::RunFinished {
+                operation, error, ..
+            } => {
+                format!("{operation} finished with error: {}", error.is_some())
+            },
+        };
+        recorded_events
+            .lock()
+            .unwrap_or_else(|err| panic!("lock poisoned: {err}"))
+            .push(event);
+    });
+
+    runner
+        .migrate(&db)
+        .await
+        .unwrap_or_else(|err| panic!("failed to run migrations: {err}"));
+    runner
+        .revert_to(key("20250103_140520"), &db)
+        .await
+        .unwrap_or_else(|err| panic!("failed to revert migrations: {err}"));
+
+    let events = events
+        .lock()
+        .unwrap_or_else(|err| panic!("lock poisoned: {err}"))
+        .clone();
+    assert_that!(events).is_equal_to(vec![
+        "migrate started".to_string(),
+        "2025-01-03 14:05:20 up started".into(),
+        "2025-01-03 14:05:20 up applied with rank 1".into(),
+        "2025-01-03 14:15:21 up started".into(),
+        "2025-01-03 14:15:21 up applied with rank 2".into(),
+        "migrate finished with error: false".into(),
+        "revert started".into(),
+        "2025-01-03 14:15:21 down started".into(),
+        "2025-01-03 14:15:21 reverted".into(),
+        "revert finished with error: false".into(),
+    ]);
+}
+
+#[tokio::test]
+async fn verify_notifies_registered_listener_about_problems() {
+    let db_server = start_surrealdb_testcontainer().await;
+    let db_config = prepare_test_database(&db_server).await;
+    let db = connect_to_test_database_as_database_user(&db_config).await;
+
+    let temp_dir = TempDir::new().unwrap_or_else(|err| panic!("could not create temp dir: {err}"));
+    let migrations_folder = temp_dir.path();
+    fs::write(
+        migrations_folder.join("20250103_140520_define_quote_table.surql"),
+        "DEFINE TABLE quote SCHEMALESS;",
+    )
+    .unwrap_or_else(|err| panic!("could not write migration script: {err}"));
+
+    let problems = Arc::new(Mutex::new(Vec::new()));
+    let recorded_problems = Arc::clone(&problems);
+    let config = RunnerConfig::default().with_migrations_folder(migrations_folder);
+    let runner = MigrationRunner::new(config).with_listener(move |event: &MigrationEvent<'_>| {
+        if let MigrationEvent::ProblemFound { problem, .. } = event {
+            recorded_problems
+                .lock()
+                .unwrap_or_else(|err| panic!("lock poisoned: {err}"))
+                .push((*problem).clone());
+        }
+    });
+
+    runner
+        .migrate(&db)
+        .await
+        .unwrap_or_else(|err| panic!("failed to run migrations: {err}"));
+    fs::write(
+        migrations_folder.join("20250103_140520_define_quote_table.surql"),
+        "DEFINE TABLE quote SCHEMAFULL;",
+    )
+    .unwrap_or_else(|err| panic!("could not write migration script: {err}"));
+
+    let verified = runner
+        .verify(&db)
+        .await
+        .unwrap_or_else(|err| panic!("failed to verify migrations: {err}"));
+
+    let Verified::FoundProblems(found_problems) = verified else {
+        panic!("expected problems to be found, but got {verified:?}");
+    };
+    let problems = problems
+        .lock()
+        .unwrap_or_else(|err| panic!("lock poisoned: {err}"))
+        .clone();
+    assert_that!(problems).is_equal_to(found_problems);
+}
+
+async fn define_tenant_databases(db_config: &DbClientConfig<'_>, databases: &[&str]) {
+    let db = connect_as_root_user(db_config).await;
+    let statements = databases
+        .iter()
+        .map(|database| format!("DEFINE DATABASE IF NOT EXISTS {database};"))
+        .collect::<Vec<_>>()
+        .concat();
+    db.query(format!("USE NS playground; {statements}"))
+        .await
+        .unwrap_or_else(|err| panic!("failed to define tenant databases: {err}"))
+        .check()
+        .unwrap_or_else(|err| panic!("failed to define tenant databases: {err}"));
+}
+
+fn write_tenant_migrations(migrations_folder: &Path) {
+    fs::write(
+        migrations_folder.join("20250103_140520_define_quote_table.surql"),
+        "DEFINE TABLE quote SCHEMALESS;",
+    )
+    .unwrap_or_else(|err| panic!("could not write migration script: {err}"));
+    fs::write(
+        migrations_folder.join("20250103_140521_fail_for_tenant_b.surql"),
+        "IF session::db() = 'tenant_b' { THROW 'boom'; };",
+    )
+    .unwrap_or_else(|err| panic!("could not write migration script: {err}"));
+}
+
+#[tokio::test]
+async fn migrate_tenant_databases_matching_a_pattern() {
+    let db_server = start_surrealdb_testcontainer().await;
+    let db_config = prepare_test_database(&db_server).await;
+    define_tenant_databases(&db_config, &["tenant_a", "tenant_b", "admin"]).await;
+    let ns_config = db_config
+        .with_auth_level(DbAuthLevel::Namespace)
+        .with_username(ns_username())
+        .with_password(ns_password());
+
+    let config =
+        RunnerConfig::default().with_migrations_folder(Path::new("../fixtures/basic/migrations"));
+    let runner = MigrationRunner::new(config);
+    let targets = TargetsConfig::new(TargetDatabases::Matching(
+        "tenant_*"
+            .parse()
+            .unwrap_or_else(|err| panic!("invalid pattern: {err}")),
+    ));
+
+    let results = runner
+        .migrate_targets(&targets, None, &ns_config)
+        .await
+        .unwrap_or_else(|err| panic!("failed to migrate tenant databases: {err}"));
+
+    assert_that!(results).is_equal_to(vec![
+        TargetResult {
+            database: "tenant_a".into(),
+            outcome: TargetOutcome::Succeeded(Migrated::UpTo(key("20250103_140521"))),
+        },
+        TargetResult {
+            database: "tenant_b".into(),
+            outcome: TargetOutcome::Succeeded(Migrated::UpTo(key("20250103_140521"))),
+        },
+    ]);
+}
+
+#[tokio::test]
+async fn migrate_tenant_databases_stops_after_failure() {
+    let temp_dir = TempDir::new().unwrap_or_else(|err| panic!("could not create temp dir: {err}"));
+    let migrations_folder = temp_dir.path();
+    write_tenant_migrations(migrations_folder);
+
+    let db_server = start_surrealdb_testcontainer().await;
+    let db_config = prepare_test_database(&db_server).await;
+    define_tenant_databases(&db_config, &["tenant_a", "tenant_b", "tenant_c"]).await;
+    let ns_config = db_config
+        .with_auth_level(DbAuthLevel::Namespace)
+        .with_username(ns_username())
+        .with_password(ns_password());
+
+    let config = RunnerConfig::default().with_migrations_folder(migrations_folder);
+    let runner = MigrationRunner::new(config);
+    let targets = TargetsConfig::new(TargetDatabases::Names(vec![
+        "tenant_c".into(),
+        "tenant_b".into(),
+        "tenant_a".into(),
+    ]))
+    .with_concurrency(1);
+
+    let results = runner
+        .migrate_targets(&targets, None, &ns_config)
+        .await
+        .unwrap_or_else(|err| panic!("failed to migrate tenant databases: {err}"));
+
+    assert_that!(&results).has_length(3);
+    assert_that!(results[0].is_succeeded()).is_true();
+    assert_that!(results[1].database.as_str()).is_equal_to("tenant_b");
+    assert_that!(matches!(
+        results[1].outcome,
+        TargetOutcome::Failed(Error::DbScript(_))
+    ))
+    .is_true();
+    assert_that!(results[2].is_skipped()).is_true();
+}
+
+#[tokio::test]
+async fn migrate_tenant_databases_continues_after_failure() {
+    let temp_dir = TempDir::new().unwrap_or_else(|err| panic!("could not create temp dir: {err}"));
+    let migrations_folder = temp_dir.path();
+    write_tenant_migrations(migrations_folder);
+
+    let db_server = start_surrealdb_testcontainer().await;
+    let db_config = prepare_test_database(&db_server).await;
+    define_tenant_databases(&db_config, &["tenant_a", "tenant_b", "tenant_c"]).await;
+    let ns_config = db_config
+        .with_auth_level(DbAuthLevel::Namespace)
+        .with_username(ns_username())
+        .with_password(ns_password());
+
+    let config = RunnerConfig::default().with_migrations_folder(migrations_folder);
+    let runner = MigrationRunner::new(config);
+    let targets = TargetsConfig::new(TargetDatabases::All)
+        .with_concurrency(1)
+        .with_failure_policy(FailurePolicy::Continue);
+
+    let results = runner
+        .migrate_targets(&targets, None, &ns_config)
+        .await
+        .unwrap_or_else(|err| panic!("failed to migrate tenant databases: {err}"));
+
+    assert_that!(
+        results
+            .iter()
+            .map(|result| (
+                result.database.as_str(),
+                result.is_succeeded(),
+                result.is_failed()
+            ))
+            .collect::<Vec<_>>()
+    )
+    .is_equal_to(vec![
+        ("tenant_a", true, false),
+        ("tenant_b", false, true),
+        ("tenant_c", true, false),
+        ("test", true, false),
+    ]);
+}
+
+#[tokio::test]
+async fn migrate_all_tenant_databases_with_namespace_migrations_does_not_migrate_the_tracking_database()
+ {
+    let temp_dir = TempDir::new().unwrap_or_else(|err| panic!("could not create temp dir: {err}"));
+    let namespace_migrations_folder = temp_dir.path();
+    fs::write(
+        namespace_migrations_folder.join("20250101_000100_define_tenant_c_database.surql"),
+        "DEFINE DATABASE tenant_c;",
+    )
+    .unwrap_or_else(|err| panic!("could not write migration script: {err}"));
+
+    let db_server = start_surrealdb_testcontainer().await;
+    let db_config = prepare_test_database(&db_server).await;
+    define_tenant_databases(&db_config, &["tenant_a", "tenant_b"]).await;
+    let ns_config = db_config
+        .with_auth_level(DbAuthLevel::Namespace)
+        .with_username(ns_username())
+        .with_password(ns_password());
+
+    let config = RunnerConfig::default()
+        .with_namespace_migrations_folder(namespace_migrations_folder)
+        .with_migrations_folder(Path::new("../fixtures/basic/migrations"));
+    let runner = MigrationRunner::new(config);
+    let targets = TargetsConfig::new(TargetDatabases::All);
+
+    let results = runner
+        .migrate_targets(&targets, None, &ns_config)
+        .await
+        .unwrap_or_else(|err| panic!("failed to migrate tenant databases: {err}"));
+
+    assert_that!(
+        results
+            .iter()
+            .map(|result| (result.database.as_str(), result.is_succeeded()))
+            .collect::<Vec<_>>()
+    )
+    .is_equal_to(vec![
+        ("tenant_a", true),
+        ("tenant_b", true),
+        ("tenant_c", true),
+        ("test", true),
+    ]);
+
+    let db = connect_to_database(&ns_config)
+        .await
+        .unwrap_or_else(|err| panic!("failed to connect to database: {err}"));
+    let tracking_db = use_tracking_database(MigrationScope::Namespace, &db)
+        .await
+        .unwrap_or_else(|err| panic!("failed to use tracking database: {err}"));
+    let mut tracking_tables = get_db_tables_info(&tracking_db)
+        .await
+        .into_keys()
+        .collect::<Vec<_>>();
+    tracking_tables.sort();
+    assert_that!(tracking_tables).is_equal_to(vec![
+        "migrations_namespace".to_string(),
+        "migrations_namespace_history".to_string(),
+    ]);
+}
+
+#[tokio::test]
+async fn migrate_tenant_databases_notifies_listener_about_the_target_of_each_event() {
+    let db_server = start_surrealdb_testcontainer().await;
+    let db_config = prepare_test_database(&db_server).await;
+    define_tenant_databases(&db_config, &["tenant_a", "tenant_b"]).await;
+    let ns_config = db_config
+        .with_auth_level(DbAuthLevel::Namespace)
+        .with_username(ns_username())
+        .with_password(ns_password());
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let recorded_events = Arc::clone(&events);
+    let config =
+        RunnerConfig::default().with_migrations_folder(Path::new("../fixtures/basic/migrations"));
+    let runner = MigrationRunner::new(config).with_listener(move |event: &MigrationEvent<'_>| {
+        let description = match event {
+            MigrationEvent::RunStarted { .. } => "run started".to_string(),
+            MigrationEvent::MigrationApplied { migration, .. } => {
+                format!("{} applied", migration.key)
+            },
+            MigrationEvent::RunFinished { .. } => "run finished".to_string(),
+            _ => return,
+        };
+        recorded_events
+            .lock()
+            .unwrap_or_else(|err| panic!("lock poisoned: {err}"))
+            .push((event.target().to_string(), description));
+    });
+    let targets = TargetsConfig::new(TargetDatabases::Names(vec![
+        "tenant_a".into(),
+        "tenant_b".into(),
+    ]))
+    .with_concurrency(2);
+
+    runner
+        .migrate_targets(&targets, None, &ns_config)
+        .await
+        .unwrap_or_else(|err| panic!("failed to migrate tenant databases: {err}"));
+
+    let events = events
+        .lock()
+        .unwrap_or_else(|err| panic!("lock poisoned: {err}"))
+        .clone();
+    for database in ["tenant_a", "tenant_b"] {
+        let target = format!("{}/{database}", ns_config.namespace);
+        assert_that!(
+            events
+                .iter()
+                .filter(|(event_target, _)| *event_target == target)
+                .map(|(_, description)| description.as_str())
+                .collect::<Vec<_>>()
+        )
+        .is_equal_to(vec![
+            "run started",
+            "2025-01-03 14:05:20 applied",
+            "2025-01-03 14:05:21 applied",
+            "run finished",
+        ]);
+    }
 }
 
 #[tokio::test]
@@ -587,3 +1221,356 @@ async fn verify_fully_migrated_database_one_migration_changed_and_one_out_of_ord
         panic!("expected Ok(Verified::FoundProblems), but got {result:?}");
     }
 }
+
+fn copy_migration_files(source_folder: &Path, migrations_folder: &Path) {
+    let read_dir = fs::read_dir(source_folder)
+        .unwrap_or_else(|err| panic!("could not read migrations folder: {err}"));
+    for dir_entry in read_dir.flatten() {
+        let src_path = dir_entry.path();
+        if src_path.is_file() {
+            let filename = src_path.file_name().expect("src path has no filename");
+            fs::copy(&src_path, migrations_folder.join(filename)).unwrap_or_else(|err| {
+                panic!(
+                    "failed to copy migration file {}: {err}",
+                    src_path.display()
+                )
+            });
+        }
+    }
+}
+
+#[tokio::test]
+async fn squash_database_migrated_up_to_migration_20250103_140520() {
+    let temp_dir = TempDir::new().unwrap_or_else(|err| panic!("could not create temp dir: {err}"));
+    let migrations_folder = temp_dir.path();
+    copy_migration_files(Path::new("../fixtures/basic/migrations"), migrations_folder);
+
+    let db_server = start_surrealdb_testcontainer().await;
+    let db_config = prepare_test_database(&db_server).await;
+    let db = connect_to_test_database_as_database_user(&db_config).await;
+
+    let config = RunnerConfig::default().with_migrations_folder(migrations_folder);
+    let runner = MigrationRunner::new(config);
+
+    runner
+        .migrate_to(key("20250103_140520"), &db)
+        .await
+        .unwrap_or_else(|err| panic!("failed to run migrations: {err}"));
+
+    let baseline = runner
+        .squash(key("20250103_140520"), "squashed schema", &db)
+        .await
+        .unwrap_or_else(|err| panic!("failed to squash migrations: {err}"));
+
+    assert_that!(baseline.kind).is_equal_to(MigrationKind::Baseline);
+    assert_that!(baseline.script_path.file_name().and_then(OsStr::to_str))
+        .is_equal_to(Some("20250103_140520_squashed_schema.baseline.surql"));
+    let baseline_script =
+        fs::read_to_string(&baseline.script_path).expect("failed to read baseline script");
+    assert_that!(baseline_script)
+        .starts_with("DEFINE TABLE quote")
+        .contains("DEFINE FIELD text ON quote TYPE string")
+        .does_not_contain(DEFAULT_MIGRATIONS_TABLE);
+
+    let migrated = runner
+        .migrate(&db)
+        .await
+        .unwrap_or_else(|err| panic!("failed to run migrations: {err}"));
+
+    assert_that!(migrated).is_equal_to(Migrated::UpTo(key("20250103_140521")));
+
+    let verified = runner
+        .verify(&db)
+        .await
+        .unwrap_or_else(|err| panic!("failed to verify migrations: {err}"));
+
+    assert_that!(verified).is_equal_to(Verified::NoProblemsFound);
+}
+
+#[tokio::test]
+async fn squash_database_not_migrated_up_to_the_given_key() {
+    let db_server = start_surrealdb_testcontainer().await;
+    let db_config = prepare_test_database(&db_server).await;
+    let db = connect_to_test_database_as_database_user(&db_config).await;
+
+    let config =
+        RunnerConfig::default().with_migrations_folder(Path::new("../fixtures/basic/migrations"));
+    let runner = MigrationRunner::new(config);
+
+    let result = runner
+        .squash(key("20250103_140520"), "squashed schema", &db)
+        .await;
+
+    assert_that!(result).is_equal_to(Err(Error::NotMigratedUpTo("20250103_140520".into())));
+}
+
+#[tokio::test]
+async fn run_migrations_on_empty_db_applies_baseline_instead_of_squashed_migrations() {
+    let temp_dir = TempDir::new().unwrap_or_else(|err| panic!("could not create temp dir: {err}"));
+    let migrations_folder = temp_dir.path();
+    copy_migration_files(Path::new("../fixtures/basic/migrations"), migrations_folder);
+    fs::write(
+        migrations_folder.join("20250103_140520_squashed_schema.baseline.surql"),
+        "DEFINE TABLE quote SCHEMALESS;\nDEFINE FIELD text ON quote TYPE string;\n",
+    )
+    .unwrap_or_else(|err| panic!("failed to write baseline script: {err}"));
+
+    let db_server = start_surrealdb_testcontainer().await;
+    let db_config = prepare_test_database(&db_server).await;
+    let db = connect_to_test_database_as_database_user(&db_config).await;
+
+    let config = RunnerConfig::default().with_migrations_folder(migrations_folder);
+    let runner = MigrationRunner::new(config);
+
+    let migrated = runner
+        .migrate(&db)
+        .await
+        .unwrap_or_else(|err| panic!("failed to run migrations: {err}"));
+
+    assert_that!(migrated).is_equal_to(Migrated::UpTo(key("20250103_140521")));
+
+    let applied_keys = runner
+        .list_applied_migrations(&db)
+        .await
+        .unwrap_or_else(|err| panic!("failed to list applied migrations: {err}"))
+        .into_iter()
+        .map(|exec| exec.key)
+        .collect::<Vec<_>>();
+
+    assert_that!(applied_keys).contains_exactly([key("20250103_140520"), key("20250103_140521")]);
+
+    let verified = runner
+        .verify(&db)
+        .await
+        .unwrap_or_else(|err| panic!("failed to verify migrations: {err}"));
+
+    assert_that!(verified).is_equal_to(Verified::NoProblemsFound);
+}
+
+#[tokio::test]
+async fn diff_between_prototype_database_and_test_database() {
+    let temp_dir = TempDir::new().unwrap_or_else(|err| panic!("could not create temp dir: {err}"));
+    let migrations_folder = temp_dir.path();
+
+    let db_server = start_surrealdb_testcontainer().await;
+    let db_config = prepare_test_database(&db_server).await;
+    let target = connect_to_test_database_as_database_user(&db_config).await;
+    let source = connect_to_database(
+        &DbClientConfig::default()
+            .with_address(db_config.address.clone())
+            .with_namespace("playground")
+            .with_database("dev"),
+    )
+    .await
+    .unwrap_or_else(|err| panic!("failed to connect to source database: {err}"));
+    source
+        .query("DEFINE TABLE quote SCHEMAFULL; DEFINE FIELD text ON quote TYPE string;")
+        .await
+        .unwrap_or_else(|err| panic!("failed to define schema in source database: {err}"))
+        .check()
+        .unwrap_or_else(|err| panic!("failed to define schema in source database: {err}"));
+
+    let config = RunnerConfig::default().with_migrations_folder(migrations_folder);
+    let runner = MigrationRunner::new(config);
+
+    let diffed = runner
+        .diff(key("20250614_101520"), "define quote", &source, &target)
+        .await
+        .unwrap_or_else(|err| panic!("failed to diff databases: {err}"));
+
+    let Diffed::Created { up, down } = diffed else {
+        panic!("expected Diffed::Created, but got {diffed:?}");
+    };
+    assert_that!(up.script_path.file_name().and_then(OsStr::to_str))
+        .is_equal_to(Some("20250614_101520_define_quote.up.surql"));
+    assert_that!(down.script_path.file_name().and_then(OsStr::to_str))
+        .is_equal_to(Some("20250614_101520_define_quote.down.surql"));
+    let up_script = fs::read_to_string(&up.script_path).expect("failed to read up script");
+    assert_that!(up_script)
+        .starts_with("DEFINE TABLE quote")
+        .contains("DEFINE FIELD text ON quote TYPE string");
+    let down_script = fs::read_to_string(&down.script_path).expect("failed to read down script");
+    assert_that!(down_script).is_equal_to("REMOVE TABLE quote;\n".to_string());
+
+    let migrated = runner
+        .migrate(&target)
+        .await
+        .unwrap_or_else(|err| panic!("failed to run migrations: {err}"));
+
+    assert_that!(migrated).is_equal_to(Migrated::UpTo(key("20250614_101520")));
+
+    let diffed = runner
+        .diff(key("20250614_111520"), "nothing changed", &source, &target)
+        .await
+        .unwrap_or_else(|err| panic!("failed to diff databases: {err}"));
+
+    assert_that!(diffed).is_equal_to(Diffed::NoDifferences);
+}
+
+#[tokio::test]
+async fn plan_desired_schema_and_apply_it_as_new_migration() {
+    let temp_dir = TempDir::new().unwrap_or_else(|err| panic!("could not create temp dir: {err}"));
+    let migrations_folder = temp_dir.path().join("migrations");
+    let schema_folder = temp_dir.path().join("schema");
+    fs::create_dir_all(&schema_folder)
+        .unwrap_or_else(|err| panic!("could not create schema folder: {err}"));
+    fs::write(
+        schema_folder.join("quote.surql"),
+        "DEFINE TABLE quote SCHEMAFULL;\nDEFINE FIELD text ON quote TYPE string;\n",
+    )
+    .unwrap_or_else(|err| panic!("could not write schema definition: {err}"));
+
+    let db_server = start_surrealdb_testcontainer().await;
+    let db_config = prepare_test_database(&db_server).await;
+    let db = connect_to_test_database_as_database_user(&db_config).await;
+    let scratch = connect_to_database(
+        &DbClientConfig::default()
+            .with_address(db_config.address.clone())
+            .with_namespace("playground")
+            .with_database("scratch"),
+    )
+    .await
+    .unwrap_or_else(|err| panic!("failed to connect to scratch database: {err}"));
+
+    let config = RunnerConfig::default()
+        .with_migrations_folder(migrations_folder.as_path())
+        .with_schema_folder(schema_folder.as_path());
+    let runner = MigrationRunner::new(config);
+
+    let plan = runner
+        .plan_schema(&scratch, &db)
+        .await
+        .unwrap_or_else(|err| panic!("failed to plan schema: {err}"));
+
+    assert_that!(plan.up_script())
+        .starts_with("DEFINE TABLE quote")
+        .contains("DEFINE FIELD text ON quote TYPE string");
+    assert_that!(plan.down_script()).is_equal_to("REMOVE TABLE quote;\n".to_string());
+
+    let created = runner
+        .create_migration_from_diff(key("20250614_101520"), "desired schema", &plan)
+        .unwrap_or_else(|err| panic!("failed to create migration: {err}"));
+    assert_that!(matches!(created, Diffed::Created { .. })).is_true();
+
+    let migrated = runner
+        .migrate_to(key("20250614_101520"), &db)
+        .await
+        .unwrap_or_else(|err| panic!("failed to run migrations: {err}"));
+    assert_that!(migrated).is_equal_to(Migrated::UpTo(key("20250614_101520")));
+
+    let plan = runner
+        .plan_schema(&scratch, &db)
+        .await
+        .unwrap_or_else(|err| panic!("failed to plan schema: {err}"));
+
+    assert_that!(plan.is_empty()).is_true();
+}
+
+#[tokio::test]
+async fn history_of_migrated_and_partially_reverted_db() {
+    let db_server = start_surrealdb_testcontainer().await;
+    let db_config = prepare_test_database(&db_server).await;
+    let db = connect_to_test_database_as_database_user(&db_config).await;
+
+    let config = RunnerConfig::default()
+        .with_migrations_folder(Path::new("../fixtures/with_down_migrations/migrations"));
+    let runner = MigrationRunner::new(config);
+
+    runner.migrate(&db).await.expect("failed to run migrations");
+    runner
+        .revert_to(key("20250103_140520"), &db)
+        .await
+        .expect("failed to revert migrations");
+
+    let history = runner
+        .history(&db)
+        .await
+        .expect("failed to fetch migration history");
+
+    assert_that!(
+        history
+            .iter()
+            .map(|event| (
+                event.key,
+                event.action,
+                event.kind,
+                event.performed_by.clone()
+            ))
+            .collect::<Vec<_>>()
+    )
+    .contains_exactly([
+        (
+            key("20250103_140520"),
+            HistoryAction::Applied,
+            MigrationKind::Up,
+            db_username(),
+        ),
+        (
+            key("20250103_141521"),
+            HistoryAction::Applied,
+            MigrationKind::Up,
+            db_username(),
+        ),
+        (
+            key("20250103_141521"),
+            HistoryAction::Reverted,
+            MigrationKind::Down,
+            db_username(),
+        ),
+    ]);
+    assert_that!(history[1].checksum).is_equal_to(history[2].checksum);
+}
+
+#[tokio::test]
+async fn failed_migration_is_reported_by_verify_and_cleared_by_repair() {
+    let temp_dir = TempDir::new().unwrap_or_else(|err| panic!("could not create temp dir: {err}"));
+    let migrations_folder = temp_dir.path();
+
+    fs::write(
+        migrations_folder.join("20250103_140520_failing_migration.surql"),
+        "DEFINE TABLE quote SCHEMAFULL;\nTHROW 'something went wrong';\n",
+    )
+    .unwrap_or_else(|err| panic!("failed to write failing migration file: {err}"));
+
+    let db_server = start_surrealdb_testcontainer().await;
+    let db_config = prepare_test_database(&db_server).await;
+    let db = connect_to_test_database_as_database_user(&db_config).await;
+
+    let config = RunnerConfig::default().with_migrations_folder(migrations_folder);
+    let runner = MigrationRunner::new(config);
+
+    let migrated = runner.migrate(&db).await;
+
+    assert_that!(matches!(migrated, Err(Error::DbScript(_)))).is_true();
+
+    let failed_attempts = runner
+        .list_failed_attempts(&db)
+        .await
+        .expect("failed to list failed attempts");
+
+    assert_that!(&failed_attempts).has_length(1);
+    assert_that!(failed_attempts[0].key).is_equal_to(key("20250103_140520"));
+    assert_that!(&failed_attempts[0].attempted_by).is_equal_to(&db_username());
+
+    let result = runner.verify(&db).await;
+
+    if let Ok(Verified::FoundProblems(problems)) = result {
+        assert_that!(&problems).has_length(1);
+        assert_that!(problems[0].key).is_equal_to(key("20250103_140520"));
+        assert_that!(matches!(problems[0].problem, Problem::Failed { .. })).is_true();
+    } else {
+        panic!("expected Ok(Verified::FoundProblems), but got {result:?}");
+    }
+
+    let repaired = runner.repair(&db).await;
+
+    assert_that!(repaired)
+        .ok()
+        .is_equal_to(Repaired::Cleared(vec![key("20250103_140520")]));
+
+    let result = runner.verify(&db).await;
+
+    assert_that!(result)
+        .ok()
+        .is_equal_to(Verified::NoProblemsFound);
+}
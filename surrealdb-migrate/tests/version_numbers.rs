@@ -10,6 +10,7 @@ mod dummy_extern_uses {
     use database_migration as _;
     use database_migration_files as _;
     use dotenvy as _;
+    use futures as _;
     use indexmap as _;
     use log as _;
     use surrealdb_migrate as _;
@@ -18,6 +19,8 @@ mod dummy_extern_uses {
     use surrealdb_migrate_db_client as _;
     use testcontainers_modules as _;
     use tokio as _;
+    #[cfg(feature = "tracing")]
+    use tracing as _;
 }
 
 #[test]